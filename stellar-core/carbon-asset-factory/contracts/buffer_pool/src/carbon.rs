@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Subset of the carbon asset interface the pool calls into
+#[allow(dead_code)]
+#[contractclient(name = "CarbonAssetClient")]
+pub trait CarbonAssetInterface {
+    fn transfer(env: Env, from: Address, to: Address, amount: i128);
+}
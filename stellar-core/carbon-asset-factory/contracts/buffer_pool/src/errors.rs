@@ -1,14 +1,25 @@
 use soroban_sdk::contracterror;
 
+/// Codes occupy the buffer pool range, see `shared_types::BUFFER_POOL_ERROR_BASE`.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
-    Unauthorized = 1,
-    InvalidTokenId = 2,
-    InvalidPercentage = 3,
-    InsufficientBalance = 4,
-    TokenNotFound = 5,
-    AlreadyExists = 6,
-    InvalidState = 7,
+    Unauthorized = 301,
+    InvalidTokenId = 302,
+    InvalidPercentage = 303,
+    InsufficientBalance = 304,
+    TokenNotFound = 305,
+    AlreadyExists = 306,
+    InvalidState = 307,
+    UnknownProject = 308,
+    InvalidAmount = 309,
+    Paused = 310,
+    BufferTooLow = 311,
+    Overflow = 312,
+    WithdrawCooldown = 313,
+    ReplenishCapExceeded = 314,
+    InsufficientEligibleCustody = 315,
+    RecipientNotAllowed = 316,
+    DepositCapExceeded = 317,
 }
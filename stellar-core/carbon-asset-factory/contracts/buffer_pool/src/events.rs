@@ -1,25 +1,125 @@
-use soroban_sdk::{symbol_short, Address, Env, String, Symbol};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol, Vec};
 
-pub fn emit_deposit_event(env: &Env, token_id: u32, depositor: &Address, project_id: &String) {
+/// Publish under `(name,)`, or `(name, correlation_id)` when the caller
+/// supplied a correlation id to tie the event to a multi-contract flow.
+fn publish_correlated<D>(env: &Env, name: Symbol, correlation_id: &Option<BytesN<32>>, data: D)
+where
+    D: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+{
+    #[allow(deprecated)]
+    match correlation_id {
+        Some(correlation_id) => env.events().publish((name, correlation_id.clone()), data),
+        None => env.events().publish((name,), data),
+    }
+}
+
+pub fn emit_deposit_event(
+    env: &Env,
+    token_id: u32,
+    amount: i128,
+    depositor: &Address,
+    project_id: &String,
+    correlation_id: &Option<BytesN<32>>,
+) {
+    publish_correlated(
+        env,
+        symbol_short!("deposit"),
+        correlation_id,
+        (token_id, amount, depositor.clone(), project_id.clone()),
+    );
+}
+
+pub fn emit_withdraw_event(
+    env: &Env,
+    token_id: u32,
+    target_token_id: u32,
+    amount: i128,
+    governance: &Address,
+    correlation_id: &Option<BytesN<32>>,
+) {
+    publish_correlated(
+        env,
+        symbol_short!("withdraw"),
+        correlation_id,
+        (token_id, target_token_id, amount, governance.clone()),
+    );
+}
+
+pub fn emit_auto_deposit_event(env: &Env, token_id: u32, project_id: &String) {
+    #[allow(deprecated)]
+    env.events()
+        .publish((symbol_short!("auto_dep"),), (token_id, project_id));
+}
+
+pub fn emit_replenish_event(env: &Env, project_id: &String, retired_amount: i128, needed: i128) {
     #[allow(deprecated)]
     env.events().publish(
-        (symbol_short!("deposit"),),
-        (token_id, depositor, project_id),
+        (symbol_short!("replenish"),),
+        (project_id, retired_amount, needed),
     );
 }
 
-pub fn emit_withdraw_event(env: &Env, token_id: u32, target_token_id: u32, governance: &Address) {
+pub fn emit_released_event(env: &Env, shortfall: i128, token_ids: &Vec<u32>) {
+    #[allow(deprecated)]
+    env.events()
+        .publish((symbol_short!("released"),), (shortfall, token_ids.clone()));
+}
+
+pub fn emit_reassign_event(
+    env: &Env,
+    token_id: u32,
+    old_project_id: &String,
+    new_project_id: &String,
+) {
     #[allow(deprecated)]
     env.events().publish(
-        (symbol_short!("withdraw"),),
-        (token_id, target_token_id, governance),
+        (symbol_short!("reassign"),),
+        (token_id, old_project_id.clone(), new_project_id.clone()),
     );
 }
 
-pub fn emit_auto_deposit_event(env: &Env, token_id: u32, project_id: &String) {
+pub fn emit_expired_event(env: &Env, token_ids: &Vec<u32>) {
     #[allow(deprecated)]
     env.events()
-        .publish((symbol_short!("auto_dep"),), (token_id, project_id));
+        .publish((symbol_short!("expired"),), token_ids.clone());
+}
+
+pub fn emit_withdraw_all_event(env: &Env, depositor: &Address, token_ids: &Vec<u32>) {
+    #[allow(deprecated)]
+    env.events().publish(
+        (symbol_short!("wd_all"),),
+        (depositor.clone(), token_ids.len(), token_ids.clone()),
+    );
+}
+
+pub fn emit_withdraw_to_event(env: &Env, token_id: u32, recipient: &Address, amount: i128) {
+    #[allow(deprecated)]
+    env.events().publish(
+        (symbol_short!("wd_to"), token_id),
+        (recipient.clone(), amount),
+    );
+}
+
+pub fn emit_fees_withdrawn_event(env: &Env, recipient: &Address, amount: i128) {
+    #[allow(deprecated)]
+    env.events()
+        .publish((symbol_short!("fees"),), (recipient.clone(), amount));
+}
+
+/// Publish `("pool", state)` with the caller and ledger timestamp when the
+/// pool is paused or resumed
+pub fn emit_pool_state_event(env: &Env, state: Symbol, caller: &Address) {
+    #[allow(deprecated)]
+    env.events().publish(
+        (symbol_short!("pool"), state),
+        (caller.clone(), env.ledger().timestamp()),
+    );
+}
+
+pub fn emit_reconcile_event(env: &Env, old_tvl: i128, new_tvl: i128) {
+    #[allow(deprecated)]
+    env.events()
+        .publish((symbol_short!("reconcile"),), (old_tvl, new_tvl));
 }
 
 #[allow(dead_code)]
@@ -1,15 +1,20 @@
 #![no_std]
 
+mod carbon;
 mod errors;
 mod events;
+mod registry;
 mod storage;
 #[cfg(test)]
 mod test;
 
-use errors::Error;
+use carbon::CarbonAssetClient;
+pub use errors::Error;
 use events::*;
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use shared_types::{page_bounds, HealthStatus};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env, String, Vec};
 use storage::*;
+pub use storage::{LifetimeStats, ReplenishmentStrategy, TvlSnapshot};
 
 #[contract]
 pub struct BufferPoolContract;
@@ -29,7 +34,9 @@ impl BufferPoolContract {
             return Err(Error::AlreadyExists);
         }
 
-        if !(0..=10000).contains(&initial_percentage) {
+        if !(MIN_REPLENISHMENT_PERCENTAGE..=MAX_REPLENISHMENT_PERCENTAGE)
+            .contains(&initial_percentage)
+        {
             return Err(Error::InvalidPercentage);
         }
 
@@ -42,14 +49,144 @@ impl BufferPoolContract {
         Ok(())
     }
 
-    /// Manually deposit a carbon credit token into the pool.
-    /// Only admin or carbon_asset_contract can call this.
+    /// Upgrade the contract to new Wasm. Only admin can call this.
+    /// Custody records and instance configuration are kept as-is.
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = get_admin(&env);
+
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        caller.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Pause deposits, withdrawals and retirement hooks. Only admin can call this.
+    /// Emits `("pool", "paused")` unless the pool was already paused.
+    pub fn pause(env: Env, caller: Address) -> Result<(), Error> {
+        let admin = get_admin(&env);
+
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        caller.require_auth();
+
+        if is_paused(&env) {
+            return Ok(());
+        }
+
+        set_paused(&env, true);
+        emit_pool_state_event(&env, symbol_short!("paused"), &caller);
+
+        Ok(())
+    }
+
+    /// Resume a paused pool. Only admin can call this.
+    /// Emits `("pool", "resumed")` unless the pool was not paused.
+    pub fn unpause(env: Env, caller: Address) -> Result<(), Error> {
+        let admin = get_admin(&env);
+
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        caller.require_auth();
+
+        if !is_paused(&env) {
+            return Ok(());
+        }
+
+        set_paused(&env, false);
+        emit_pool_state_event(&env, symbol_short!("resumed"), &caller);
+
+        Ok(())
+    }
+
+    /// Set the coordinator that may pause and resume the pool together with
+    /// the other contracts during an incident. Only admin can call this.
+    pub fn set_coordinator(env: Env, caller: Address, coordinator: Address) -> Result<(), Error> {
+        let admin = get_admin(&env);
+
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        caller.require_auth();
+
+        set_coordinator(&env, &coordinator);
+
+        Ok(())
+    }
+
+    /// Pause or resume on behalf of the configured coordinator, emitting the
+    /// same `("pool", ...)` events as `pause` and `unpause` on a transition.
+    pub fn set_paused_by_coordinator(
+        env: Env,
+        coordinator_caller: Address,
+        paused: bool,
+    ) -> Result<(), Error> {
+        if get_coordinator(&env) != Some(coordinator_caller.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        coordinator_caller.require_auth();
+
+        if is_paused(&env) == paused {
+            return Ok(());
+        }
+
+        set_paused(&env, paused);
+        let state = if paused {
+            symbol_short!("paused")
+        } else {
+            symbol_short!("resumed")
+        };
+        emit_pool_state_event(&env, state, &coordinator_caller);
+
+        Ok(())
+    }
+
+    /// Set the project registry used to check deposits. Only admin can call this.
+    pub fn set_registry_contract(
+        env: Env,
+        caller: Address,
+        registry: Address,
+    ) -> Result<(), Error> {
+        let admin = get_admin(&env);
+
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        caller.require_auth();
+
+        set_registry_contract(&env, &registry);
+
+        Ok(())
+    }
+
+    /// Manually deposit `amount` credits of a carbon credit token into the pool.
+    /// Only admin or carbon_asset_contract can call this. Once a registry is
+    /// set, the project must be registered there and not revoked.
+    /// A `correlation_id`, when given, is added to the event topics for tracing.
+    /// A repeat deposit with the same `idempotency_key` changes nothing and
+    /// returns the record created the first time.
     pub fn deposit(
         env: Env,
         caller: Address,
         token_id: u32,
         project_id: String,
-    ) -> Result<(), Error> {
+        amount: i128,
+        correlation_id: Option<BytesN<32>>,
+        idempotency_key: Option<BytesN<32>>,
+    ) -> Result<CustodyRecord, Error> {
+        ensure_not_paused(&env)?;
+
         let admin = get_admin(&env);
         let carbon_contract = get_carbon_asset_contract(&env);
 
@@ -59,34 +196,279 @@ impl BufferPoolContract {
 
         caller.require_auth();
 
+        if let Some(record) = idempotency_key
+            .as_ref()
+            .and_then(|key| get_processed_deposit(&env, key))
+        {
+            return Ok(record);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
         if has_custody_record(&env, token_id) {
             return Err(Error::AlreadyExists);
         }
 
+        if let Some(registry) = get_registry_contract(&env) {
+            if !registry::is_active_project(&env, &registry, &project_id) {
+                return Err(Error::UnknownProject);
+            }
+        }
+
+        let fee = amount
+            .checked_mul(get_deposit_fee(&env) as i128)
+            .ok_or(Error::Overflow)?
+            / 10000;
+        let amount = amount - fee;
+
+        if amount > deposit_headroom(&env, &caller, &project_id) {
+            return Err(Error::DepositCapExceeded);
+        }
+
+        add_accrued_fees(&env, fee)?;
+
+        let deposited_at = env.ledger().timestamp();
         let record = CustodyRecord {
             token_id,
-            deposited_at: env.ledger().timestamp(),
+            amount,
+            deposited_at,
             depositor: caller.clone(),
             project_id: project_id.clone(),
+            expires_at: custody_expiry(&env, &project_id, deposited_at),
+            frozen: false,
         };
 
         set_custody_record(&env, token_id, &record);
+        adjust_project_buffer(&env, &project_id, amount)?;
+        settle_replenishment(&env, &project_id, amount);
 
-        let tvl = get_total_value_locked(&env);
-        set_total_value_locked(&env, tvl + 1);
+        add_tvl(&env, amount)?;
+        add_lifetime_total(&env, LIFETIME_DEPOSITED, amount)?;
 
-        emit_deposit_event(&env, token_id, &caller, &project_id);
+        if let Some(key) = &idempotency_key {
+            set_processed_deposit(&env, key, &record);
+        }
 
-        Ok(())
+        emit_deposit_event(
+            &env,
+            token_id,
+            amount,
+            &caller,
+            &project_id,
+            &correlation_id,
+        );
+
+        Ok(record)
     }
 
-    /// Governance withdraws a credit from pool to replace an invalidated token.
+    /// Governance withdraws `amount` credits of a token from the pool to
+    /// replace an invalidated token. The custody record is kept with the
+    /// remaining amount and only removed once it reaches zero.
+    /// A `correlation_id`, when given, is added to the event topics for tracing.
+    /// Fails with `WithdrawCooldown` if the record's depositor was last
+    /// withdrawn from less than the withdraw cooldown ago.
     pub fn withdraw_to_replace(
         env: Env,
         governance_caller: Address,
         token_id: u32,
         target_invalidated_token: u32,
+        amount: i128,
+        correlation_id: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        let governance = get_governance(&env);
+
+        if governance_caller != governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance_caller.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        take_custody(&env, token_id, amount)?;
+
+        emit_withdraw_event(
+            &env,
+            token_id,
+            target_invalidated_token,
+            amount,
+            &governance_caller,
+            &correlation_id,
+        );
+
+        Ok(())
+    }
+
+    /// Governance sends `amount` credits of a token out of the pool to
+    /// `recipient` through the carbon asset contract. While the recipient
+    /// allowlist is enforced, only allowlisted recipients can be paid.
+    /// The same withdraw cooldown as `withdraw_to_replace` applies.
+    pub fn withdraw_to(
+        env: Env,
+        governance_caller: Address,
+        token_id: u32,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        let governance = get_governance(&env);
+
+        if governance_caller != governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance_caller.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        if is_recipient_allowlist_enforced(&env) && !is_allowed_recipient(&env, &recipient) {
+            return Err(Error::RecipientNotAllowed);
+        }
+
+        take_custody(&env, token_id, amount)?;
+
+        CarbonAssetClient::new(&env, &get_carbon_asset_contract(&env)).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        emit_withdraw_to_event(&env, token_id, &recipient, amount);
+
+        Ok(())
+    }
+
+    /// Governance allows `recipient` to be paid by `withdraw_to`
+    pub fn add_allowed_recipient(
+        env: Env,
+        governance: Address,
+        recipient: Address,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        set_allowed_recipient(&env, &recipient, true);
+
+        Ok(())
+    }
+
+    /// Governance removes `recipient` from the withdrawal allowlist
+    pub fn remove_allowed_recipient(
+        env: Env,
+        governance: Address,
+        recipient: Address,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        set_allowed_recipient(&env, &recipient, false);
+
+        Ok(())
+    }
+
+    /// Governance turns the `withdraw_to` recipient allowlist on or off.
+    /// It is off by default, letting any recipient be paid.
+    pub fn set_enforce_recipient_allowlist(
+        env: Env,
+        governance: Address,
+        enforce: bool,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        set_recipient_allowlist_enforced(&env, enforce);
+
+        Ok(())
+    }
+
+    /// A depositor takes back every custody record it holds in one call.
+    /// Records still time-locked, with an `expires_at` not yet reached, are
+    /// skipped, as are records whose withdrawal would take the
+    /// project's buffer, less the replenishment it owes, below its minimum
+    /// buffer of the outstanding credits last reported on retirement.
+    /// Frozen records are skipped too. Fails with `WithdrawCooldown` if the
+    /// depositor was last withdrawn from within the withdraw cooldown.
+    /// Returns the number of records withdrawn.
+    pub fn withdraw_all(env: Env, caller: Address) -> Result<u32, Error> {
+        ensure_not_paused(&env)?;
+
+        caller.require_auth();
+
+        let now = env.ledger().timestamp();
+        ensure_withdraw_cooldown_elapsed(&env, &caller, now)?;
+
+        let mut withdrawn = Vec::new(&env);
+        for token_id in get_depositor_tokens(&env, &caller).iter() {
+            let Some(record) = get_custody_record(&env, token_id) else {
+                continue;
+            };
+
+            if record.frozen {
+                continue;
+            }
+
+            if record.expires_at.is_some_and(|expires_at| now < expires_at) {
+                continue;
+            }
+
+            let due = get_replenishment_due(&env, &record.project_id);
+            let outstanding = get_project_outstanding(&env, &record.project_id);
+            if !meets_min_buffer(&env, &record.project_id, due, record.amount, outstanding)? {
+                continue;
+            }
+
+            adjust_project_buffer(&env, &record.project_id, -record.amount)?;
+            sub_tvl(&env, record.amount)?;
+            add_lifetime_total(&env, LIFETIME_WITHDRAWN, record.amount)?;
+            remove_custody_record(&env, token_id);
+            withdrawn.push_back(token_id);
+        }
+
+        if !withdrawn.is_empty() {
+            set_last_withdraw(&env, &caller, now);
+        }
+
+        emit_withdraw_all_event(&env, &caller, &withdrawn);
+
+        Ok(withdrawn.len())
+    }
+
+    /// Governance moves a token's custody to another project's buffer, for
+    /// example when rebalancing a portfolio. The credits leave the old
+    /// project's buffer and count towards the new one; the total value locked
+    /// is unchanged. With a registry set, the new project must be active there.
+    pub fn reassign_custody_project(
+        env: Env,
+        governance_caller: Address,
+        token_id: u32,
+        new_project_id: String,
     ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
         let governance = get_governance(&env);
 
         if governance_caller != governance {
@@ -95,22 +477,68 @@ impl BufferPoolContract {
 
         governance_caller.require_auth();
 
-        if !has_custody_record(&env, token_id) {
+        let Some(mut record) = get_custody_record(&env, token_id) else {
             return Err(Error::TokenNotFound);
+        };
+
+        if record.project_id == new_project_id {
+            return Ok(());
         }
 
-        env.storage()
-            .persistent()
-            .remove(&(storage::CUSTODY, token_id));
+        if let Some(registry) = get_registry_contract(&env) {
+            if !registry::is_active_project(&env, &registry, &new_project_id) {
+                return Err(Error::UnknownProject);
+            }
+        }
 
-        let tvl = get_total_value_locked(&env);
-        set_total_value_locked(&env, tvl - 1);
+        let old_project_id = record.project_id.clone();
+        adjust_project_buffer(&env, &old_project_id, -record.amount)?;
+        adjust_project_buffer(&env, &new_project_id, record.amount)?;
+        record.project_id = new_project_id.clone();
+        set_custody_record(&env, token_id, &record);
 
-        emit_withdraw_event(&env, token_id, target_invalidated_token, &governance_caller);
+        emit_reassign_event(&env, token_id, &old_project_id, &new_project_id);
 
         Ok(())
     }
 
+    /// Governance releases every custody record whose `expires_at` has
+    /// passed, taking it out of its project's buffer and the total value
+    /// locked. Returns the released token ids.
+    pub fn release_expired(env: Env, governance_caller: Address) -> Result<Vec<u32>, Error> {
+        ensure_not_paused(&env)?;
+
+        let governance = get_governance(&env);
+
+        if governance_caller != governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance_caller.require_auth();
+
+        let now = env.ledger().timestamp();
+        let mut released = Vec::new(&env);
+        for token_id in get_custody_ids(&env).iter() {
+            let Some(record) = get_custody_record(&env, token_id) else {
+                continue;
+            };
+            if record
+                .expires_at
+                .is_some_and(|expires_at| now >= expires_at)
+            {
+                adjust_project_buffer(&env, &record.project_id, -record.amount)?;
+                sub_tvl(&env, record.amount)?;
+                add_lifetime_total(&env, LIFETIME_WITHDRAWN, record.amount)?;
+                remove_custody_record(&env, token_id);
+                released.push_back(token_id);
+            }
+        }
+
+        emit_expired_event(&env, &released);
+
+        Ok(released)
+    }
+
     pub fn auto_deposit(
         env: Env,
         carbon_contract_caller: Address,
@@ -118,6 +546,8 @@ impl BufferPoolContract {
         project_id: String,
         _total_minted: u32,
     ) -> Result<bool, Error> {
+        ensure_not_paused(&env)?;
+
         let carbon_contract = get_carbon_asset_contract(&env);
         if carbon_contract_caller != carbon_contract {
             return Err(Error::Unauthorized);
@@ -125,21 +555,35 @@ impl BufferPoolContract {
 
         carbon_contract_caller.require_auth();
 
+        // A 0% replenishment rate sets no tokens aside
         let percentage = get_replenishment_percentage(&env);
+        if percentage == 0 {
+            return Ok(false);
+        }
         let modulo = (10000 / percentage) as u32;
 
-        if token_id % modulo == 0 {
+        if token_id.is_multiple_of(modulo) {
+            if has_custody_record(&env, token_id) {
+                return Err(Error::AlreadyExists);
+            }
+
+            let deposited_at = env.ledger().timestamp();
             let record = CustodyRecord {
                 token_id,
-                deposited_at: env.ledger().timestamp(),
+                amount: 1,
+                deposited_at,
                 depositor: carbon_contract_caller,
                 project_id: project_id.clone(),
+                expires_at: custody_expiry(&env, &project_id, deposited_at),
+                frozen: false,
             };
 
             set_custody_record(&env, token_id, &record);
+            adjust_project_buffer(&env, &project_id, 1)?;
+            settle_replenishment(&env, &project_id, 1);
 
-            let tvl = get_total_value_locked(&env);
-            set_total_value_locked(&env, tvl + 1);
+            add_tvl(&env, 1)?;
+            add_lifetime_total(&env, LIFETIME_DEPOSITED, 1)?;
 
             emit_auto_deposit_event(&env, token_id, &project_id);
 
@@ -149,29 +593,64 @@ impl BufferPoolContract {
         }
     }
 
-    pub fn set_governance_address(
+    /// Retirement hook called by the carbon asset contract. Adds the share of
+    /// `retired_amount` the pool must cover (at the replenishment percentage,
+    /// rounded up) to the project's outstanding replenishment, which later
+    /// deposits for that project pay down. Returns the amount added. The hook
+    /// only schedules the replenishment: no custody moves until deposits
+    /// arrive. Fails with `Overflow` if the share or the total owed overflows.
+    ///
+    /// `outstanding_credits` is the project's supply still in circulation
+    /// after the retirement. If the project has a minimum buffer set, the
+    /// retirement fails with `BufferTooLow` when the project's buffer, less
+    /// the replenishment it owes, would drop below that share of it.
+    pub fn on_retirement(
         env: Env,
-        current_governance: Address,
-        new_governance: Address,
-    ) -> Result<(), Error> {
-        let governance = get_governance(&env);
+        caller: Address,
+        project_id: String,
+        retired_amount: i128,
+        outstanding_credits: i128,
+    ) -> Result<i128, Error> {
+        ensure_not_paused(&env)?;
 
-        if current_governance != governance {
+        let carbon_contract = get_carbon_asset_contract(&env);
+        if caller != carbon_contract {
             return Err(Error::Unauthorized);
         }
 
-        current_governance.require_auth();
+        caller.require_auth();
 
-        set_governance(&env, &new_governance);
+        if retired_amount <= 0 || outstanding_credits < 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-        Ok(())
+        let percentage = get_replenishment_percentage(&env) as i128;
+        let needed = retired_amount
+            .checked_mul(percentage)
+            .and_then(|share| share.checked_add(9999))
+            .ok_or(Error::Overflow)?
+            / 10000;
+        let due = get_replenishment_due(&env, &project_id)
+            .checked_add(needed)
+            .ok_or(Error::Overflow)?;
+
+        if !meets_min_buffer(&env, &project_id, due, 0, outstanding_credits)? {
+            return Err(Error::BufferTooLow);
+        }
+
+        if needed > 0 {
+            set_replenishment_due(&env, &project_id, due);
+        }
+        set_project_outstanding(&env, &project_id, outstanding_credits);
+
+        emit_replenish_event(&env, &project_id, retired_amount, needed);
+
+        Ok(needed)
     }
 
-    pub fn set_replenishment_rate(
-        env: Env,
-        governance: Address,
-        new_percentage: i64,
-    ) -> Result<(), Error> {
+    /// Governance caps the custody any one depositor may hold through
+    /// `deposit`. 0 removes the cap.
+    pub fn set_depositor_cap(env: Env, governance: Address, cap: i128) -> Result<(), Error> {
         let current_governance = get_governance(&env);
 
         if governance != current_governance {
@@ -180,24 +659,778 @@ impl BufferPoolContract {
 
         governance.require_auth();
 
-        if !(0..=10000).contains(&new_percentage) {
-            return Err(Error::InvalidPercentage);
+        if cap < 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        set_replenishment_percentage(&env, new_percentage);
+        set_depositor_cap(&env, cap);
 
         Ok(())
     }
 
-    pub fn get_total_value_locked(env: Env) -> i128 {
-        get_total_value_locked(&env)
-    }
+    /// Governance caps the buffer `deposit` may build up for a project.
+    /// 0 removes the cap.
+    pub fn set_project_cap(
+        env: Env,
+        governance: Address,
+        project_id: String,
+        cap: i128,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
 
-    pub fn get_custody_record(env: Env, token_id: u32) -> Option<CustodyRecord> {
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        if cap < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        set_project_cap(&env, &project_id, cap);
+
+        Ok(())
+    }
+
+    /// Governance sets the minimum buffer a project must keep, in basis
+    /// points of its outstanding credits, checked on every retirement.
+    pub fn set_min_buffer_percentage(
+        env: Env,
+        governance: Address,
+        project_id: String,
+        percentage: i64,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        if !(0..=10000).contains(&percentage) {
+            return Err(Error::InvalidPercentage);
+        }
+
+        set_min_buffer_percentage(&env, &project_id, percentage);
+
+        Ok(())
+    }
+
+    /// Governance releases custody to cover a `shortfall` of invalidated
+    /// credits, drawing on records in the order set by the replenishment
+    /// strategy. Records are used up whole except the last, which is
+    /// decremented by whatever is left of the shortfall. Returns the token
+    /// ids drawn on, as `preview_replenishment` would. A shortfall above the
+    /// per-call cap fails with `ReplenishCapExceeded`.
+    pub fn trigger_replenishment(
+        env: Env,
+        governance_caller: Address,
+        shortfall: i128,
+    ) -> Result<Vec<u32>, Error> {
+        ensure_not_paused(&env)?;
+
+        let governance = get_governance(&env);
+
+        if governance_caller != governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance_caller.require_auth();
+
+        let selection = select_replenishment(&env, shortfall)?;
+
+        let mut token_ids = Vec::new(&env);
+        for (token_id, amount) in selection.iter() {
+            let mut record = get_custody_record(&env, token_id).unwrap();
+            record.amount -= amount;
+            adjust_project_buffer(&env, &record.project_id, -amount)?;
+            if record.amount == 0 {
+                remove_custody_record(&env, token_id);
+            } else {
+                set_custody_record(&env, token_id, &record);
+            }
+            token_ids.push_back(token_id);
+        }
+
+        sub_tvl(&env, shortfall)?;
+        add_lifetime_total(&env, LIFETIME_REPLENISHED, shortfall)?;
+
+        emit_released_event(&env, shortfall, &token_ids);
+
+        Ok(token_ids)
+    }
+
+    /// Token ids `trigger_replenishment` would draw on for `shortfall`,
+    /// without changing any state.
+    pub fn preview_replenishment(env: Env, shortfall: i128) -> Result<Vec<u32>, Error> {
+        let selection = select_replenishment(&env, shortfall)?;
+
+        let mut token_ids = Vec::new(&env);
+        for (token_id, _) in selection.iter() {
+            token_ids.push_back(token_id);
+        }
+
+        Ok(token_ids)
+    }
+
+    /// Governance sets how long `project_id` commits deposited buffer for.
+    /// Deposits made afterwards expire that many seconds after deposit; zero,
+    /// the default, means they never expire. Existing records keep theirs.
+    pub fn set_project_custody_duration(
+        env: Env,
+        governance: Address,
+        project_id: String,
+        seconds: u64,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        set_custody_duration(&env, &project_id, seconds);
+
+        Ok(())
+    }
+
+    /// Governance sets the fee, in basis points, kept from every `deposit`
+    /// into the accrued fee balance. Must be below 10000.
+    pub fn set_deposit_fee(env: Env, governance: Address, fee_bps: i64) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        if !(0..10000).contains(&fee_bps) {
+            return Err(Error::InvalidPercentage);
+        }
+
+        set_deposit_fee(&env, fee_bps);
+
+        Ok(())
+    }
+
+    /// Governance pays `amount` of the accrued deposit fees out to
+    /// `recipient`, transferring the credits through the carbon asset contract.
+    pub fn withdraw_accrued_fees(
+        env: Env,
+        governance_caller: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let governance = get_governance(&env);
+
+        if governance_caller != governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance_caller.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let accrued = get_accrued_fees(&env);
+        if amount > accrued {
+            return Err(Error::InsufficientBalance);
+        }
+
+        set_accrued_fees(&env, accrued - amount);
+
+        CarbonAssetClient::new(&env, &get_carbon_asset_contract(&env)).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        emit_fees_withdrawn_event(&env, &recipient, amount);
+
+        Ok(())
+    }
+
+    /// Governance chooses whether replenishment draws on the oldest or the
+    /// newest custody records first.
+    pub fn set_replenishment_strategy(
+        env: Env,
+        governance: Address,
+        strategy: ReplenishmentStrategy,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        set_replenishment_strategy(&env, strategy);
+
+        Ok(())
+    }
+
+    /// Governance sets the minimum seconds between two withdrawals drawn on
+    /// the same depositor's custody. Zero, the default, disables the check.
+    pub fn set_withdraw_cooldown(env: Env, governance: Address, seconds: u64) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        set_withdraw_cooldown(&env, seconds);
+
+        Ok(())
+    }
+
+    /// Governance freezes or unfreezes a custody record. Frozen custody is
+    /// skipped when selecting records for replenishment.
+    pub fn set_custody_frozen(
+        env: Env,
+        governance: Address,
+        token_id: u32,
+        frozen: bool,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        let mut record = get_custody_record(&env, token_id).ok_or(Error::TokenNotFound)?;
+        record.frozen = frozen;
+        set_custody_record(&env, token_id, &record);
+
+        Ok(())
+    }
+
+    /// Governance sets how many seconds after deposit custody stays locked
+    /// against replenishment. Zero, the default, disables the lock.
+    pub fn set_replenish_lock_period(
+        env: Env,
+        governance: Address,
+        seconds: u64,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        set_replenish_lock_period(&env, seconds);
+
+        Ok(())
+    }
+
+    /// Seconds after deposit that custody is locked against replenishment
+    pub fn get_replenish_lock_period(env: Env) -> u64 {
+        get_replenish_lock_period(&env)
+    }
+
+    /// Governance caps the shortfall a single `trigger_replenishment` may
+    /// cover, as a rail against a mistyped value. 0 removes the cap.
+    pub fn set_max_replenish_per_call(
+        env: Env,
+        governance: Address,
+        max_amount: i128,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        if max_amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        set_max_replenish_per_call(&env, max_amount);
+
+        Ok(())
+    }
+
+    pub fn set_governance_address(
+        env: Env,
+        current_governance: Address,
+        new_governance: Address,
+    ) -> Result<(), Error> {
+        let governance = get_governance(&env);
+
+        if current_governance != governance {
+            return Err(Error::Unauthorized);
+        }
+
+        current_governance.require_auth();
+
+        set_governance(&env, &new_governance);
+
+        Ok(())
+    }
+
+    pub fn set_replenishment_rate(
+        env: Env,
+        governance: Address,
+        new_percentage: i64,
+    ) -> Result<(), Error> {
+        let current_governance = get_governance(&env);
+
+        if governance != current_governance {
+            return Err(Error::Unauthorized);
+        }
+
+        governance.require_auth();
+
+        if !(MIN_REPLENISHMENT_PERCENTAGE..=MAX_REPLENISHMENT_PERCENTAGE).contains(&new_percentage)
+        {
+            return Err(Error::InvalidPercentage);
+        }
+
+        set_replenishment_percentage(&env, new_percentage);
+
+        Ok(())
+    }
+
+    /// Recompute the total value locked from the custody records and store
+    /// it, as a recovery path if the two ever drift apart. Only admin can
+    /// call this. Returns the correction applied (new total less old).
+    pub fn reconcile_tvl(env: Env, caller: Address) -> Result<i128, Error> {
+        let admin = get_admin(&env);
+
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        caller.require_auth();
+
+        let mut total: i128 = 0;
+        for token_id in get_custody_ids(&env).iter() {
+            if let Some(record) = get_custody_record(&env, token_id) {
+                total = total.checked_add(record.amount).ok_or(Error::Overflow)?;
+            }
+        }
+
+        let old_tvl = get_total_value_locked(&env);
+        set_total_value_locked(&env, total);
+
+        emit_reconcile_event(&env, old_tvl, total);
+
+        Ok(total - old_tvl)
+    }
+
+    /// Record a project's current buffer for `get_time_weighted_avg_tvl`.
+    /// Meant to be called periodically by the admin; a second snapshot in
+    /// the same ledger replaces the first.
+    pub fn snapshot_tvl(env: Env, caller: Address, project_id: String) -> Result<i128, Error> {
+        let admin = get_admin(&env);
+
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        caller.require_auth();
+
+        let value = get_project_buffer(&env, &project_id);
+        add_tvl_snapshot(
+            &env,
+            &project_id,
+            &TvlSnapshot {
+                timestamp: env.ledger().timestamp(),
+                value,
+            },
+        );
+
+        Ok(value)
+    }
+
+    /// Average of a project's buffer over `from..to`, weighting each
+    /// snapshot by how long it stood. Between snapshots the last value is
+    /// carried forward, and before the first one the buffer counts as 0.
+    /// An empty range gives the value standing at `from`.
+    pub fn get_time_weighted_avg_tvl(env: Env, project_id: String, from: u64, to: u64) -> i128 {
+        let mut value: i128 = 0;
+        let mut cursor = from;
+        let mut weighted: i128 = 0;
+        for snapshot in get_tvl_snapshots(&env, &project_id).iter() {
+            if snapshot.timestamp <= from {
+                value = snapshot.value;
+                continue;
+            }
+            if snapshot.timestamp >= to {
+                break;
+            }
+            weighted = weighted
+                .saturating_add(value.saturating_mul((snapshot.timestamp - cursor) as i128));
+            cursor = snapshot.timestamp;
+            value = snapshot.value;
+        }
+
+        if to <= from {
+            return value;
+        }
+
+        weighted = weighted.saturating_add(value.saturating_mul((to - cursor) as i128));
+        weighted / (to - from) as i128
+    }
+
+    pub fn get_tvl_snapshots(env: Env, project_id: String) -> Vec<TvlSnapshot> {
+        get_tvl_snapshots(&env, &project_id)
+    }
+
+    /// Cumulative credits deposited, withdrawn and replenished since the
+    /// pool started, with the current total value locked
+    pub fn get_lifetime_stats(env: Env) -> LifetimeStats {
+        LifetimeStats {
+            total_deposited: get_lifetime_total(&env, LIFETIME_DEPOSITED),
+            total_withdrawn: get_lifetime_total(&env, LIFETIME_WITHDRAWN),
+            total_replenished: get_lifetime_total(&env, LIFETIME_REPLENISHED),
+            current_tvl: get_total_value_locked(&env),
+        }
+    }
+
+    pub fn get_total_value_locked(env: Env) -> i128 {
+        get_total_value_locked(&env)
+    }
+
+    pub fn get_custody_record(env: Env, token_id: u32) -> Option<CustodyRecord> {
         get_custody_record(&env, token_id)
     }
 
+    /// List up to `limit` custody records starting at `cursor`, in deposit order.
+    pub fn list_custody(env: Env, cursor: u32, limit: u32) -> CustodyPage {
+        let token_ids = get_custody_ids(&env);
+        let (start, end, next_cursor) = page_bounds(token_ids.len(), cursor, limit);
+
+        let mut items = Vec::new(&env);
+        for token_id in token_ids.slice(start..end).iter() {
+            if let Some(record) = get_custody_record(&env, token_id) {
+                items.push_back(record);
+            }
+        }
+
+        CustodyPage {
+            items,
+            next_cursor,
+            total: token_ids.len(),
+        }
+    }
+
+    /// Token ids among up to `limit` custody records from `start`, in deposit
+    /// order, whose project the registry does not know or has revoked. For
+    /// cleanup of records deposited before the registry was set; empty when
+    /// no registry is configured.
+    pub fn find_orphan_custody(env: Env, start: u32, limit: u32) -> Vec<u32> {
+        let mut orphans = Vec::new(&env);
+        let Some(registry) = get_registry_contract(&env) else {
+            return orphans;
+        };
+
+        let token_ids = get_custody_ids(&env);
+        let (start, end, _) = page_bounds(token_ids.len(), start, limit);
+        for token_id in token_ids.slice(start..end).iter() {
+            if let Some(record) = get_custody_record(&env, token_id) {
+                if !registry::is_active_project(&env, &registry, &record.project_id) {
+                    orphans.push_back(token_id);
+                }
+            }
+        }
+
+        orphans
+    }
+
+    /// Credits deposited for a project between `from` and `to` inclusive,
+    /// less those withdrawn, replenished or released in that time. Custody
+    /// reassigned between projects counts as an outflow from one and an
+    /// inflow to the other. An empty range gives 0.
+    pub fn get_net_flow(env: Env, project_id: String, from: u64, to: u64) -> i128 {
+        if from > to {
+            return 0;
+        }
+
+        get_flow_log(&env, &project_id)
+            .iter()
+            .filter(|entry| (from..=to).contains(&entry.timestamp))
+            .fold(0i128, |net, entry| net.saturating_add(entry.amount))
+    }
+
+    /// Custody records deposited between `from` and `to` inclusive, in
+    /// deposit order. Empty when `from` is after `to`.
+    pub fn get_custody_in_range(env: Env, from: u64, to: u64) -> Vec<CustodyRecord> {
+        let mut records = Vec::new(&env);
+        if from > to {
+            return records;
+        }
+
+        for token_id in get_custody_ids(&env).iter() {
+            if let Some(record) = get_custody_record(&env, token_id) {
+                if (from..=to).contains(&record.deposited_at) {
+                    records.push_back(record);
+                }
+            }
+        }
+
+        records
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        is_paused(&env)
+    }
+
+    /// Report initialization, pause state and the number of custody records.
+    pub fn health_check(env: Env) -> HealthStatus {
+        HealthStatus {
+            initialized: env.storage().instance().has(&storage::ADMIN),
+            paused: is_paused(&env),
+            entity_count: get_custody_ids(&env).len(),
+        }
+    }
+
+    pub fn get_replenishment_due(env: Env, project_id: String) -> i128 {
+        get_replenishment_due(&env, &project_id)
+    }
+
+    pub fn get_project_buffer(env: Env, project_id: String) -> i128 {
+        get_project_buffer(&env, &project_id)
+    }
+
+    pub fn get_depositor_cap(env: Env) -> i128 {
+        get_depositor_cap(&env)
+    }
+
+    pub fn get_project_cap(env: Env, project_id: String) -> i128 {
+        get_project_cap(&env, &project_id)
+    }
+
+    /// Credits `depositor` can still place into custody for `project_id`
+    /// before a cap is hit, after any deposit fee. `i128::MAX` when neither
+    /// the depositor nor the project is capped.
+    pub fn get_deposit_headroom(env: Env, depositor: Address, project_id: String) -> i128 {
+        deposit_headroom(&env, &depositor, &project_id)
+    }
+
+    pub fn get_min_buffer_percentage(env: Env, project_id: String) -> i64 {
+        get_min_buffer_percentage(&env, &project_id)
+    }
+
+    pub fn get_project_custody_duration(env: Env, project_id: String) -> u64 {
+        get_custody_duration(&env, &project_id)
+    }
+
+    pub fn get_deposit_fee(env: Env) -> i64 {
+        get_deposit_fee(&env)
+    }
+
+    /// Deposit fees collected and not yet withdrawn
+    pub fn get_accrued_fees(env: Env) -> i128 {
+        get_accrued_fees(&env)
+    }
+
+    pub fn get_max_replenish_per_call(env: Env) -> i128 {
+        get_max_replenish_per_call(&env)
+    }
+
+    pub fn get_withdraw_cooldown(env: Env) -> u64 {
+        get_withdraw_cooldown(&env)
+    }
+
+    /// Token ids held in custody for a depositor
+    pub fn get_depositor_tokens(env: Env, depositor: Address) -> Vec<u32> {
+        get_depositor_tokens(&env, &depositor)
+    }
+
+    /// Ledger timestamp of the last withdrawal drawn on `depositor`'s custody
+    pub fn get_last_withdraw(env: Env, depositor: Address) -> Option<u64> {
+        get_last_withdraw(&env, &depositor)
+    }
+
+    /// Replenishment percentage in basis points, as set by `set_replenishment_rate`
+    pub fn get_replenishment_percentage(env: Env) -> i64 {
+        get_replenishment_percentage(&env)
+    }
+
+    /// Inclusive minimum and maximum accepted for the replenishment percentage
+    pub fn get_replenishment_bounds(_env: Env) -> (i64, i64) {
+        (MIN_REPLENISHMENT_PERCENTAGE, MAX_REPLENISHMENT_PERCENTAGE)
+    }
+
+    pub fn get_replenishment_strategy(env: Env) -> ReplenishmentStrategy {
+        get_replenishment_strategy(&env)
+    }
+
+    pub fn get_coordinator(env: Env) -> Option<Address> {
+        get_coordinator(&env)
+    }
+
+    pub fn is_allowed_recipient(env: Env, recipient: Address) -> bool {
+        is_allowed_recipient(&env, &recipient)
+    }
+
+    pub fn is_recipient_allowlist_enforced(env: Env) -> bool {
+        is_recipient_allowlist_enforced(&env)
+    }
+
+    pub fn get_registry_contract(env: Env) -> Option<Address> {
+        get_registry_contract(&env)
+    }
+
     pub fn is_token_in_pool(env: Env, token_id: u32) -> bool {
         has_custody_record(&env, token_id)
     }
 }
+
+fn ensure_not_paused(env: &Env) -> Result<(), Error> {
+    if is_paused(env) {
+        return Err(Error::Paused);
+    }
+
+    Ok(())
+}
+
+/// Smallest room left under the depositor cap and the project cap
+fn deposit_headroom(env: &Env, depositor: &Address, project_id: &String) -> i128 {
+    let mut headroom = i128::MAX;
+
+    let depositor_cap = get_depositor_cap(env);
+    if depositor_cap > 0 {
+        let held = get_depositor_tokens(env, depositor)
+            .iter()
+            .filter_map(|token_id| get_custody_record(env, token_id))
+            .fold(0i128, |held, record| held.saturating_add(record.amount));
+        headroom = headroom.min(depositor_cap.saturating_sub(held).max(0));
+    }
+
+    let project_cap = get_project_cap(env, project_id);
+    if project_cap > 0 {
+        let buffer = get_project_buffer(env, project_id);
+        headroom = headroom.min(project_cap.saturating_sub(buffer).max(0));
+    }
+
+    headroom
+}
+
+/// Fail with `WithdrawCooldown` if `depositor` was last withdrawn from less
+/// than the withdraw cooldown before `now`
+fn ensure_withdraw_cooldown_elapsed(env: &Env, depositor: &Address, now: u64) -> Result<(), Error> {
+    if let Some(last) = get_last_withdraw(env, depositor) {
+        if now < last.saturating_add(get_withdraw_cooldown(env)) {
+            return Err(Error::WithdrawCooldown);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a project's buffer, less the replenishment `due` and `withdrawn`
+/// credits, still covers its minimum buffer of `outstanding` credits. Always
+/// true when the project has no minimum set.
+fn meets_min_buffer(
+    env: &Env,
+    project_id: &String,
+    due: i128,
+    withdrawn: i128,
+    outstanding: i128,
+) -> Result<bool, Error> {
+    let min_percentage = get_min_buffer_percentage(env, project_id) as i128;
+    if min_percentage <= 0 {
+        return Ok(true);
+    }
+
+    let available = get_project_buffer(env, project_id)
+        .checked_sub(due)
+        .and_then(|available| available.checked_sub(withdrawn))
+        .and_then(|available| available.checked_mul(10000))
+        .ok_or(Error::Overflow)?;
+    let floor = outstanding
+        .checked_mul(min_percentage)
+        .ok_or(Error::Overflow)?;
+
+    Ok(available >= floor)
+}
+
+/// Draw `amount` credits out of a token's custody record, subject to the
+/// depositor's withdraw cooldown. The record is removed once it reaches zero.
+fn take_custody(env: &Env, token_id: u32, amount: i128) -> Result<(), Error> {
+    let Some(mut record) = get_custody_record(env, token_id) else {
+        return Err(Error::TokenNotFound);
+    };
+
+    if amount > record.amount {
+        return Err(Error::InsufficientBalance);
+    }
+
+    let now = env.ledger().timestamp();
+    ensure_withdraw_cooldown_elapsed(env, &record.depositor, now)?;
+    set_last_withdraw(env, &record.depositor, now);
+
+    record.amount -= amount;
+    adjust_project_buffer(env, &record.project_id, -amount)?;
+    if record.amount == 0 {
+        remove_custody_record(env, token_id);
+    } else {
+        set_custody_record(env, token_id, &record);
+    }
+
+    sub_tvl(env, amount)?;
+    add_lifetime_total(env, LIFETIME_WITHDRAWN, amount)
+}
+
+/// Pick the custody records covering `shortfall` in strategy order, paired
+/// with the amount taken from each.
+fn select_replenishment(env: &Env, shortfall: i128) -> Result<Vec<(u32, i128)>, Error> {
+    if shortfall <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let cap = get_max_replenish_per_call(env);
+    if cap > 0 && shortfall > cap {
+        return Err(Error::ReplenishCapExceeded);
+    }
+
+    let token_ids = get_custody_ids(env);
+    let lifo = get_replenishment_strategy(env) == ReplenishmentStrategy::Lifo;
+    let now = env.ledger().timestamp();
+    let lock_period = get_replenish_lock_period(env);
+
+    let mut selection = Vec::new(env);
+    let mut remaining = shortfall;
+    let mut ineligible: i128 = 0;
+    for i in 0..token_ids.len() {
+        if remaining == 0 {
+            break;
+        }
+        let index = if lifo { token_ids.len() - 1 - i } else { i };
+        let token_id = token_ids.get(index).unwrap();
+        if let Some(record) = get_custody_record(env, token_id) {
+            // Frozen and still-locked custody is left in place
+            if record.frozen || now < record.deposited_at.saturating_add(lock_period) {
+                ineligible = ineligible.saturating_add(record.amount);
+                continue;
+            }
+            let amount = record.amount.min(remaining);
+            selection.push_back((token_id, amount));
+            remaining -= amount;
+        }
+    }
+
+    if remaining > 0 {
+        if ineligible >= remaining {
+            return Err(Error::InsufficientEligibleCustody);
+        }
+        return Err(Error::InsufficientBalance);
+    }
+
+    Ok(selection)
+}
@@ -0,0 +1,17 @@
+use shared_types::{Project, RevocationStatus};
+use soroban_sdk::{contractclient, Address, Env, String};
+
+/// Subset of the project registry interface the pool calls into
+#[allow(dead_code)]
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn get_project(env: Env, project_id: String) -> Project;
+}
+
+/// Whether the registry knows `project_id` and has not revoked it
+pub fn is_active_project(env: &Env, registry: &Address, project_id: &String) -> bool {
+    match RegistryClient::new(env, registry).try_get_project(project_id) {
+        Ok(Ok(project)) => project.revocation == RevocationStatus::NotRevoked,
+        _ => false,
+    }
+}
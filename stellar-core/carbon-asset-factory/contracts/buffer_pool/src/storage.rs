@@ -1,20 +1,107 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+use crate::errors::Error;
+use shared_types::page_type;
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CustodyRecord {
     pub token_id: u32,
+    /// Credits held for the token, in its smallest unit
+    pub amount: i128,
     pub deposited_at: u64,
     pub depositor: Address,
     pub project_id: String,
+    /// When the project's custody duration runs out; `None` never expires
+    pub expires_at: Option<u64>,
+    /// Held back from replenishment by governance, see `set_custody_frozen`
+    pub frozen: bool,
 }
 
+/// A project's buffer as recorded by `snapshot_tvl`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TvlSnapshot {
+    pub timestamp: u64,
+    pub value: i128,
+}
+
+/// Net change to a project's buffer at one ledger timestamp, from `get_net_flow`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlowEntry {
+    pub timestamp: u64,
+    /// Deposits less withdrawals, replenishments and releases
+    pub amount: i128,
+}
+
+/// Pool-wide totals from `get_lifetime_stats`. Only `current_tvl` ever
+/// goes down.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LifetimeStats {
+    /// Credits taken into custody, after deposit fees
+    pub total_deposited: i128,
+    /// Credits withdrawn to replace tokens, by depositors or on expiry
+    pub total_withdrawn: i128,
+    /// Credits released by `trigger_replenishment`
+    pub total_replenished: i128,
+    pub current_tvl: i128,
+}
+
+/// Order in which `trigger_replenishment` draws on custody records
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReplenishmentStrategy {
+    /// Oldest deposits first
+    Fifo,
+    /// Newest deposits first
+    Lifo,
+}
+
+page_type!(
+    /// Page of custody records returned by `list_custody`
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    CustodyPage,
+    CustodyRecord
+);
+
 pub const ADMIN: Symbol = symbol_short!("admin");
 pub const GOVERNANCE: Symbol = symbol_short!("gov");
 pub const CARBON_CONTRACT: Symbol = symbol_short!("carbon");
 pub const REPLENISH_PCT: Symbol = symbol_short!("rep_pct");
 pub const TVL: Symbol = symbol_short!("tvl");
 pub const CUSTODY: Symbol = symbol_short!("custody");
+pub const CUSTODY_IDS: Symbol = symbol_short!("cust_ids");
+pub const REGISTRY: Symbol = symbol_short!("registry");
+pub const REPLENISH_DUE: Symbol = symbol_short!("rep_due");
+pub const PAUSED: Symbol = symbol_short!("paused");
+pub const PROJECT_BUFFER: Symbol = symbol_short!("proj_buf");
+pub const MIN_BUFFER_PCT: Symbol = symbol_short!("min_buf");
+pub const REPLENISH_STRATEGY: Symbol = symbol_short!("rep_strat");
+pub const PROCESSED_DEPOSIT: Symbol = symbol_short!("dep_key");
+pub const WITHDRAW_COOLDOWN: Symbol = symbol_short!("wd_cool");
+pub const LAST_WITHDRAW: Symbol = symbol_short!("last_wd");
+pub const CUSTODY_DURATION: Symbol = symbol_short!("cust_dur");
+pub const DEPOSIT_FEE: Symbol = symbol_short!("dep_fee");
+pub const ACCRUED_FEES: Symbol = symbol_short!("fees");
+pub const DEPOSITOR_TOKENS: Symbol = symbol_short!("dep_toks");
+pub const PROJECT_OUTSTANDING: Symbol = symbol_short!("proj_out");
+pub const MAX_REPLENISH: Symbol = symbol_short!("max_rep");
+pub const TVL_SNAPSHOTS: Symbol = symbol_short!("tvl_snap");
+pub const COORDINATOR: Symbol = symbol_short!("coord");
+pub const FLOW_LOG: Symbol = symbol_short!("flow_log");
+pub const LIFETIME_DEPOSITED: Symbol = symbol_short!("life_dep");
+pub const LIFETIME_WITHDRAWN: Symbol = symbol_short!("life_wd");
+pub const LIFETIME_REPLENISHED: Symbol = symbol_short!("life_rep");
+pub const REPLENISH_LOCK: Symbol = symbol_short!("rep_lock");
+pub const ALLOWED_RECIPIENT: Symbol = symbol_short!("allow_rcp");
+pub const ENFORCE_RECIPIENTS: Symbol = symbol_short!("rcp_enf");
+pub const DEPOSITOR_CAP: Symbol = symbol_short!("dep_cap");
+pub const PROJECT_CAP: Symbol = symbol_short!("proj_cap");
+
+/// Range, in basis points, enforced on the replenishment percentage
+pub const MIN_REPLENISHMENT_PERCENTAGE: i64 = 0;
+pub const MAX_REPLENISHMENT_PERCENTAGE: i64 = 10000;
 
 pub fn get_admin(env: &Env) -> Address {
     env.storage().instance().get(&ADMIN).unwrap()
@@ -40,6 +127,30 @@ pub fn set_carbon_asset_contract(env: &Env, contract: &Address) {
     env.storage().instance().set(&CARBON_CONTRACT, contract);
 }
 
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance().get(&PAUSED).unwrap_or(false)
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&PAUSED, &paused);
+}
+
+pub fn get_coordinator(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&COORDINATOR)
+}
+
+pub fn set_coordinator(env: &Env, coordinator: &Address) {
+    env.storage().instance().set(&COORDINATOR, coordinator);
+}
+
+pub fn get_registry_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&REGISTRY)
+}
+
+pub fn set_registry_contract(env: &Env, contract: &Address) {
+    env.storage().instance().set(&REGISTRY, contract);
+}
+
 pub fn get_replenishment_percentage(env: &Env) -> i64 {
     env.storage().instance().get(&REPLENISH_PCT).unwrap_or(500)
 }
@@ -48,6 +159,111 @@ pub fn set_replenishment_percentage(env: &Env, percentage: i64) {
     env.storage().instance().set(&REPLENISH_PCT, &percentage);
 }
 
+pub fn get_replenishment_strategy(env: &Env) -> ReplenishmentStrategy {
+    env.storage()
+        .instance()
+        .get(&REPLENISH_STRATEGY)
+        .unwrap_or(ReplenishmentStrategy::Fifo)
+}
+
+pub fn set_replenishment_strategy(env: &Env, strategy: ReplenishmentStrategy) {
+    env.storage().instance().set(&REPLENISH_STRATEGY, &strategy);
+}
+
+/// Largest shortfall one replenishment may cover, 0 for no cap
+pub fn get_max_replenish_per_call(env: &Env) -> i128 {
+    env.storage().instance().get(&MAX_REPLENISH).unwrap_or(0)
+}
+
+pub fn set_max_replenish_per_call(env: &Env, max_amount: i128) {
+    env.storage().instance().set(&MAX_REPLENISH, &max_amount);
+}
+
+/// Seconds after deposit during which custody cannot be drawn on by
+/// replenishment, 0 for none
+pub fn get_replenish_lock_period(env: &Env) -> u64 {
+    env.storage().instance().get(&REPLENISH_LOCK).unwrap_or(0)
+}
+
+pub fn set_replenish_lock_period(env: &Env, seconds: u64) {
+    env.storage().instance().set(&REPLENISH_LOCK, &seconds);
+}
+
+pub fn get_withdraw_cooldown(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&WITHDRAW_COOLDOWN)
+        .unwrap_or(0)
+}
+
+pub fn set_withdraw_cooldown(env: &Env, seconds: u64) {
+    env.storage().instance().set(&WITHDRAW_COOLDOWN, &seconds);
+}
+
+pub fn is_allowed_recipient(env: &Env, recipient: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(ALLOWED_RECIPIENT, recipient.clone()))
+}
+
+pub fn set_allowed_recipient(env: &Env, recipient: &Address, allowed: bool) {
+    let key = (ALLOWED_RECIPIENT, recipient.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Whether `withdraw_to` only pays out to allowlisted recipients
+pub fn is_recipient_allowlist_enforced(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&ENFORCE_RECIPIENTS)
+        .unwrap_or(false)
+}
+
+pub fn set_recipient_allowlist_enforced(env: &Env, enforced: bool) {
+    env.storage().instance().set(&ENFORCE_RECIPIENTS, &enforced);
+}
+
+pub fn get_last_withdraw(env: &Env, depositor: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&(LAST_WITHDRAW, depositor.clone()))
+}
+
+pub fn set_last_withdraw(env: &Env, depositor: &Address, timestamp: u64) {
+    env.storage()
+        .persistent()
+        .set(&(LAST_WITHDRAW, depositor.clone()), &timestamp);
+}
+
+pub fn get_deposit_fee(env: &Env) -> i64 {
+    env.storage().instance().get(&DEPOSIT_FEE).unwrap_or(0)
+}
+
+pub fn set_deposit_fee(env: &Env, fee_bps: i64) {
+    env.storage().instance().set(&DEPOSIT_FEE, &fee_bps);
+}
+
+pub fn get_accrued_fees(env: &Env) -> i128 {
+    env.storage().instance().get(&ACCRUED_FEES).unwrap_or(0)
+}
+
+pub fn set_accrued_fees(env: &Env, fees: i128) {
+    env.storage().instance().set(&ACCRUED_FEES, &fees);
+}
+
+/// Add `fee` to the accrued fees, failing rather than wrapping
+pub fn add_accrued_fees(env: &Env, fee: i128) -> Result<(), Error> {
+    let fees = get_accrued_fees(env)
+        .checked_add(fee)
+        .ok_or(Error::Overflow)?;
+    set_accrued_fees(env, fees);
+    Ok(())
+}
+
 pub fn get_total_value_locked(env: &Env) -> i128 {
     env.storage().instance().get(&TVL).unwrap_or(0)
 }
@@ -56,14 +272,277 @@ pub fn set_total_value_locked(env: &Env, tvl: i128) {
     env.storage().instance().set(&TVL, &tvl);
 }
 
+/// Add `amount` to the total value locked, failing rather than wrapping
+pub fn add_tvl(env: &Env, amount: i128) -> Result<(), Error> {
+    let tvl = get_total_value_locked(env)
+        .checked_add(amount)
+        .ok_or(Error::Overflow)?;
+    set_total_value_locked(env, tvl);
+    Ok(())
+}
+
+/// Subtract `amount` from the total value locked, which may never go negative
+pub fn sub_tvl(env: &Env, amount: i128) -> Result<(), Error> {
+    let tvl = get_total_value_locked(env)
+        .checked_sub(amount)
+        .filter(|tvl| *tvl >= 0)
+        .ok_or(Error::Overflow)?;
+    set_total_value_locked(env, tvl);
+    Ok(())
+}
+
+/// One of the `LIFETIME_*` totals
+pub fn get_lifetime_total(env: &Env, key: Symbol) -> i128 {
+    env.storage().instance().get(&key).unwrap_or(0)
+}
+
+/// Add `amount` to one of the `LIFETIME_*` totals, failing rather than wrapping
+pub fn add_lifetime_total(env: &Env, key: Symbol, amount: i128) -> Result<(), Error> {
+    let total = get_lifetime_total(env, key.clone())
+        .checked_add(amount)
+        .ok_or(Error::Overflow)?;
+    env.storage().instance().set(&key, &total);
+    Ok(())
+}
+
 pub fn get_custody_record(env: &Env, token_id: u32) -> Option<CustodyRecord> {
     env.storage().persistent().get(&(CUSTODY, token_id))
 }
 
 pub fn set_custody_record(env: &Env, token_id: u32, record: &CustodyRecord) {
     env.storage().persistent().set(&(CUSTODY, token_id), record);
+
+    let mut ids = get_custody_ids(env);
+    if !ids.contains(token_id) {
+        ids.push_back(token_id);
+        env.storage().persistent().set(&CUSTODY_IDS, &ids);
+    }
+
+    let mut depositor_ids = get_depositor_tokens(env, &record.depositor);
+    if !depositor_ids.contains(token_id) {
+        depositor_ids.push_back(token_id);
+        env.storage().persistent().set(
+            &(DEPOSITOR_TOKENS, record.depositor.clone()),
+            &depositor_ids,
+        );
+    }
+}
+
+pub fn remove_custody_record(env: &Env, token_id: u32) {
+    let Some(record) = get_custody_record(env, token_id) else {
+        return;
+    };
+    env.storage().persistent().remove(&(CUSTODY, token_id));
+
+    let mut ids = get_custody_ids(env);
+    if let Some(index) = ids.first_index_of(token_id) {
+        ids.remove(index);
+        env.storage().persistent().set(&CUSTODY_IDS, &ids);
+    }
+
+    let mut depositor_ids = get_depositor_tokens(env, &record.depositor);
+    if let Some(index) = depositor_ids.first_index_of(token_id) {
+        depositor_ids.remove(index);
+        env.storage()
+            .persistent()
+            .set(&(DEPOSITOR_TOKENS, record.depositor), &depositor_ids);
+    }
+}
+
+/// Token ids held in custody for `depositor`, in deposit order
+pub fn get_depositor_tokens(env: &Env, depositor: &Address) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&(DEPOSITOR_TOKENS, depositor.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Token ids held in custody, in deposit order
+pub fn get_custody_ids(env: &Env) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&CUSTODY_IDS)
+        .unwrap_or(Vec::new(env))
 }
 
 pub fn has_custody_record(env: &Env, token_id: u32) -> bool {
     env.storage().persistent().has(&(CUSTODY, token_id))
 }
+
+/// Custody record created by the deposit that used `idempotency_key`
+pub fn get_processed_deposit(env: &Env, idempotency_key: &BytesN<32>) -> Option<CustodyRecord> {
+    env.storage()
+        .persistent()
+        .get(&(PROCESSED_DEPOSIT, idempotency_key.clone()))
+}
+
+pub fn set_processed_deposit(env: &Env, idempotency_key: &BytesN<32>, record: &CustodyRecord) {
+    env.storage()
+        .persistent()
+        .set(&(PROCESSED_DEPOSIT, idempotency_key.clone()), record);
+}
+
+pub fn get_replenishment_due(env: &Env, project_id: &String) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(REPLENISH_DUE, project_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_replenishment_due(env: &Env, project_id: &String, due: i128) {
+    env.storage()
+        .persistent()
+        .set(&(REPLENISH_DUE, project_id.clone()), &due);
+}
+
+/// Credits held in custody for `project_id`
+pub fn get_project_buffer(env: &Env, project_id: &String) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(PROJECT_BUFFER, project_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn adjust_project_buffer(env: &Env, project_id: &String, delta: i128) -> Result<(), Error> {
+    let balance = get_project_buffer(env, project_id)
+        .checked_add(delta)
+        .ok_or(Error::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&(PROJECT_BUFFER, project_id.clone()), &balance);
+    record_flow(env, project_id, delta);
+    Ok(())
+}
+
+/// Changes to a project's buffer, oldest first
+pub fn get_flow_log(env: &Env, project_id: &String) -> Vec<FlowEntry> {
+    env.storage()
+        .persistent()
+        .get(&(FLOW_LOG, project_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Log a buffer change, folding changes in the same ledger into one entry
+fn record_flow(env: &Env, project_id: &String, delta: i128) {
+    let now = env.ledger().timestamp();
+    let mut log = get_flow_log(env, project_id);
+    let amount = match log.last() {
+        Some(last) if last.timestamp == now => {
+            log.pop_back();
+            last.amount.saturating_add(delta)
+        }
+        _ => delta,
+    };
+    log.push_back(FlowEntry {
+        timestamp: now,
+        amount,
+    });
+    env.storage()
+        .persistent()
+        .set(&(FLOW_LOG, project_id.clone()), &log);
+}
+
+/// Credits of `project_id` still in circulation, as last reported on retirement
+pub fn get_project_outstanding(env: &Env, project_id: &String) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(PROJECT_OUTSTANDING, project_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_project_outstanding(env: &Env, project_id: &String, outstanding: i128) {
+    env.storage()
+        .persistent()
+        .set(&(PROJECT_OUTSTANDING, project_id.clone()), &outstanding);
+}
+
+/// Seconds custody deposited for `project_id` is held, 0 for no limit
+pub fn get_custody_duration(env: &Env, project_id: &String) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(CUSTODY_DURATION, project_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_custody_duration(env: &Env, project_id: &String, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&(CUSTODY_DURATION, project_id.clone()), &seconds);
+}
+
+/// Expiry of custody deposited for `project_id` at `deposited_at`, from the
+/// project's custody duration. A zero duration never expires.
+pub fn custody_expiry(env: &Env, project_id: &String, deposited_at: u64) -> Option<u64> {
+    match get_custody_duration(env, project_id) {
+        0 => None,
+        duration => Some(deposited_at.saturating_add(duration)),
+    }
+}
+
+/// Snapshots of a project's buffer, oldest first
+pub fn get_tvl_snapshots(env: &Env, project_id: &String) -> Vec<TvlSnapshot> {
+    env.storage()
+        .persistent()
+        .get(&(TVL_SNAPSHOTS, project_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Append `snapshot`, replacing the latest one if taken at the same time
+pub fn add_tvl_snapshot(env: &Env, project_id: &String, snapshot: &TvlSnapshot) {
+    let mut snapshots = get_tvl_snapshots(env, project_id);
+    if snapshots
+        .last()
+        .is_some_and(|last| last.timestamp == snapshot.timestamp)
+    {
+        snapshots.pop_back();
+    }
+    snapshots.push_back(snapshot.clone());
+    env.storage()
+        .persistent()
+        .set(&(TVL_SNAPSHOTS, project_id.clone()), &snapshots);
+}
+
+/// Most custody any one depositor may hold, 0 for no cap
+pub fn get_depositor_cap(env: &Env) -> i128 {
+    env.storage().instance().get(&DEPOSITOR_CAP).unwrap_or(0)
+}
+
+pub fn set_depositor_cap(env: &Env, cap: i128) {
+    env.storage().instance().set(&DEPOSITOR_CAP, &cap);
+}
+
+/// Largest buffer a project may hold, 0 for no cap
+pub fn get_project_cap(env: &Env, project_id: &String) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(PROJECT_CAP, project_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_project_cap(env: &Env, project_id: &String, cap: i128) {
+    env.storage()
+        .persistent()
+        .set(&(PROJECT_CAP, project_id.clone()), &cap);
+}
+
+/// Minimum buffer per project, in basis points of its outstanding credits
+pub fn get_min_buffer_percentage(env: &Env, project_id: &String) -> i64 {
+    env.storage()
+        .persistent()
+        .get(&(MIN_BUFFER_PCT, project_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_min_buffer_percentage(env: &Env, project_id: &String, percentage: i64) {
+    env.storage()
+        .persistent()
+        .set(&(MIN_BUFFER_PCT, project_id.clone()), &percentage);
+}
+
+/// Count `amount` deposited for `project_id` against its outstanding replenishment.
+pub fn settle_replenishment(env: &Env, project_id: &String, amount: i128) {
+    let due = get_replenishment_due(env, project_id);
+    if due > 0 {
+        set_replenishment_due(env, project_id, due - amount.min(due));
+    }
+}
@@ -1,7 +1,18 @@
 #![cfg(test)]
 
-use crate::{BufferPoolContract, BufferPoolContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use crate::errors::Error;
+use crate::storage::{self, CustodyRecord};
+use crate::{BufferPoolContract, BufferPoolContractClient, ReplenishmentStrategy};
+use shared_types::{error_source, ErrorSource};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, BytesN, Env, IntoVal, String,
+};
+
+mod upgrade_target {
+    soroban_sdk::contractimport!(file = "test_wasms/upgrade_target.wasm");
+}
 
 fn setup_test_env<'a>() -> (Env, Address, Address, Address, BufferPoolContractClient<'a>) {
     let env = Env::default();
@@ -43,7 +54,7 @@ fn test_deposit_as_admin() {
     client.initialize(&admin, &governance, &carbon_contract, &500);
 
     let project_id = String::from_str(&env, "PROJECT-001");
-    client.deposit(&admin, &1, &project_id);
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
 
     let tvl = client.get_total_value_locked();
     assert_eq!(tvl, 1);
@@ -59,9 +70,9 @@ fn test_deposit_duplicate_fails() {
     client.initialize(&admin, &governance, &carbon_contract, &500);
 
     let project_id = String::from_str(&env, "PROJECT-001");
-    client.deposit(&admin, &1, &project_id);
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
 
-    let result = client.try_deposit(&admin, &1, &project_id);
+    let result = client.try_deposit(&admin, &1, &project_id, &1, &None, &None);
     assert!(result.is_err());
 }
 
@@ -72,9 +83,9 @@ fn test_withdraw_by_governance() {
     client.initialize(&admin, &governance, &carbon_contract, &500);
 
     let project_id = String::from_str(&env, "PROJECT-001");
-    client.deposit(&admin, &1, &project_id);
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
 
-    client.withdraw_to_replace(&governance, &1, &999);
+    client.withdraw_to_replace(&governance, &1, &999, &1, &None);
 
     let tvl = client.get_total_value_locked();
     assert_eq!(tvl, 0);
@@ -90,10 +101,41 @@ fn test_auto_deposit_calculation() {
 
     // With 5% (500 bp), every 20th token should be deposited
     let deposited = client.auto_deposit(&carbon_contract, &20, &project_id, &20);
-    assert_eq!(deposited, true);
+    assert!(deposited);
 
     let not_deposited = client.auto_deposit(&carbon_contract, &21, &project_id, &21);
-    assert_eq!(not_deposited, false);
+    assert!(!not_deposited);
+}
+
+#[test]
+fn test_auto_deposit_at_zero_percentage() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &0);
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    for token_id in [1, 20, 10_000] {
+        assert!(!client.auto_deposit(&carbon_contract, &token_id, &project_id, &token_id));
+    }
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_auto_deposit_rejects_token_in_custody() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    let other_project = String::from_str(&env, "PROJECT-002");
+
+    client.deposit(&admin, &20, &project_id, &5, &None, &None);
+
+    // The existing record is left as it was
+    let result = client.try_auto_deposit(&carbon_contract, &20, &other_project, &20);
+    assert_eq!(result, Err(Ok(Error::AlreadyExists)));
+    let record = client.get_custody_record(&20).unwrap();
+    assert_eq!(record.amount, 5);
+    assert_eq!(record.depositor, admin);
+    assert_eq!(client.get_total_value_locked(), 5);
+    assert_eq!(client.get_project_buffer(&other_project), 0);
 }
 
 #[test]
@@ -110,7 +152,7 @@ fn test_withdraw_nonexistent_token() {
 
     client.initialize(&admin, &governance, &carbon_contract, &500);
 
-    let result = client.try_withdraw_to_replace(&governance, &999, &1);
+    let result = client.try_withdraw_to_replace(&governance, &999, &1, &1, &None);
     assert!(result.is_err());
 }
 
@@ -121,7 +163,7 @@ fn test_get_custody_record() {
     client.initialize(&admin, &governance, &carbon_contract, &500);
 
     let project_id = String::from_str(&env, "PROJECT-001");
-    client.deposit(&admin, &1, &project_id);
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
 
     let record = client.get_custody_record(&1);
     assert!(record.is_some());
@@ -136,6 +178,1031 @@ fn test_set_replenishment_rate() {
     let (_, admin, governance, carbon_contract, client) = setup_test_env();
 
     client.initialize(&admin, &governance, &carbon_contract, &500);
+    assert_eq!(client.get_replenishment_percentage(), 500);
 
     client.set_replenishment_rate(&governance, &1000);
+    assert_eq!(client.get_replenishment_percentage(), 1000);
+}
+
+#[test]
+fn test_replenishment_bounds_match_enforced_range() {
+    let (_, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    let (min, max) = client.get_replenishment_bounds();
+    assert_eq!((min, max), (0, 10000));
+
+    client.set_replenishment_rate(&governance, &min);
+    assert_eq!(client.get_replenishment_percentage(), min);
+    client.set_replenishment_rate(&governance, &max);
+    assert_eq!(client.get_replenishment_percentage(), max);
+
+    let result = client.try_set_replenishment_rate(&governance, &(max + 1));
+    assert_eq!(result, Err(Ok(Error::InvalidPercentage)));
+    let result = client.try_set_replenishment_rate(&governance, &(min - 1));
+    assert_eq!(result, Err(Ok(Error::InvalidPercentage)));
+    assert_eq!(client.get_replenishment_percentage(), max);
+}
+
+#[test]
+fn test_upgrade_preserves_custody_records() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgrade_target::WASM);
+    client.upgrade(&admin, &new_wasm_hash);
+
+    let upgraded = upgrade_target::Client::new(&env, &client.address);
+    assert_eq!(upgraded.add(&2, &3), 5);
+
+    env.as_contract(&client.address, || {
+        let record: CustodyRecord = env
+            .storage()
+            .persistent()
+            .get(&(storage::CUSTODY, 1u32))
+            .unwrap();
+        assert_eq!(record.project_id, project_id);
+        assert_eq!(record.depositor, admin);
+
+        let tvl: i128 = env.storage().instance().get(&storage::TVL).unwrap();
+        assert_eq!(tvl, 1);
+
+        let stored_admin: Address = env.storage().instance().get(&storage::ADMIN).unwrap();
+        assert_eq!(stored_admin, admin);
+    });
+}
+
+#[test]
+fn test_upgrade_non_admin_fails() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgrade_target::WASM);
+    let result = client.try_upgrade(&governance, &new_wasm_hash);
+
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_error_codes_in_buffer_pool_range() {
+    let errors = [
+        Error::Unauthorized,
+        Error::InvalidTokenId,
+        Error::InvalidPercentage,
+        Error::InsufficientBalance,
+        Error::TokenNotFound,
+        Error::AlreadyExists,
+        Error::InvalidState,
+        Error::UnknownProject,
+        Error::InvalidAmount,
+        Error::Paused,
+        Error::BufferTooLow,
+        Error::Overflow,
+        Error::WithdrawCooldown,
+        Error::ReplenishCapExceeded,
+        Error::InsufficientEligibleCustody,
+        Error::RecipientNotAllowed,
+        Error::DepositCapExceeded,
+    ];
+
+    for error in errors {
+        assert_eq!(error_source(error as u32), Some(ErrorSource::BufferPool));
+    }
+}
+
+#[test]
+fn test_health_check() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+
+    let status = client.health_check();
+    assert!(!status.initialized);
+    assert!(!status.paused);
+    assert_eq!(status.entity_count, 0);
+
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
+    client.deposit(&admin, &2, &project_id, &1, &None, &None);
+
+    let status = client.health_check();
+    assert!(status.initialized);
+    assert!(!status.paused);
+    assert_eq!(status.entity_count, 2);
+
+    client.pause(&admin);
+    assert!(client.health_check().paused);
+
+    client.unpause(&admin);
+    assert!(!client.health_check().paused);
+}
+
+#[test]
+fn test_pause_blocks_deposits_and_withdrawals() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
+
+    let result = client.try_pause(&governance);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    client.pause(&admin);
+    assert!(client.is_paused());
+
+    let result = client.try_deposit(&admin, &2, &project_id, &1, &None, &None);
+    assert_eq!(result, Err(Ok(Error::Paused)));
+
+    let result = client.try_withdraw_to_replace(&governance, &1, &99, &1, &None);
+    assert_eq!(result, Err(Ok(Error::Paused)));
+
+    client.unpause(&admin);
+    client.withdraw_to_replace(&governance, &1, &99, &1, &None);
+    assert!(!client.is_token_in_pool(&1));
+}
+
+#[test]
+fn test_set_paused_by_coordinator() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let coordinator = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_paused_by_coordinator(&coordinator, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_coordinator(&governance, &coordinator),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.set_coordinator(&admin, &coordinator);
+    assert_eq!(client.get_coordinator(), Some(coordinator.clone()));
+
+    client.set_paused_by_coordinator(&coordinator, &true);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("pool"), symbol_short!("paused")).into_val(&env),
+                (coordinator.clone(), 0u64).into_val(&env),
+            )
+        ]
+    );
+    assert!(client.is_paused());
+
+    // The admin can still resume a pool the coordinator paused
+    client.unpause(&admin);
+    assert!(!client.is_paused());
+    client.set_paused_by_coordinator(&coordinator, &false);
+    assert_eq!(env.events().all().len(), 0);
+}
+
+#[test]
+fn test_pause_and_unpause_emit_pool_events() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    env.ledger().set_timestamp(1_234);
+
+    client.pause(&admin);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("pool"), symbol_short!("paused")).into_val(&env),
+                (admin.clone(), 1_234u64).into_val(&env),
+            )
+        ]
+    );
+
+    // Repeating a transition writes nothing and stays silent
+    client.pause(&admin);
+    assert_eq!(env.events().all().len(), 0);
+
+    env.ledger().set_timestamp(2_000);
+    client.unpause(&admin);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("pool"), symbol_short!("resumed")).into_val(&env),
+                (admin.clone(), 2_000u64).into_val(&env),
+            )
+        ]
+    );
+
+    client.unpause(&admin);
+    assert_eq!(env.events().all().len(), 0);
+}
+
+#[test]
+fn test_correlation_id_in_event_topics() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    let project_id = String::from_str(&env, "PROJECT-001");
+    let correlation_id = BytesN::from_array(&env, &[7u8; 32]);
+
+    client.deposit(
+        &admin,
+        &1,
+        &project_id,
+        &1,
+        &Some(correlation_id.clone()),
+        &None,
+    );
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("deposit"), correlation_id.clone()).into_val(&env),
+                (1u32, 1i128, admin.clone(), project_id.clone()).into_val(&env),
+            )
+        ]
+    );
+
+    client.withdraw_to_replace(&governance, &1, &99, &1, &Some(correlation_id.clone()));
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("withdraw"), correlation_id).into_val(&env),
+                (1u32, 99u32, 1i128, governance.clone()).into_val(&env),
+            )
+        ]
+    );
+
+    // Without a correlation id the topics are unchanged
+    client.deposit(&admin, &2, &project_id, &1, &None, &None);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("deposit"),).into_val(&env),
+                (2u32, 1i128, admin, project_id).into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_list_custody_pages() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    for token_id in 1..=5 {
+        client.deposit(&admin, &token_id, &project_id, &1, &None, &None);
+    }
+    client.withdraw_to_replace(&governance, &2, &99, &1, &None);
+
+    let first = client.list_custody(&0, &3);
+    assert_eq!(first.total, 4);
+    assert_eq!(first.items.len(), 3);
+    assert_eq!(first.items.get(1).unwrap().token_id, 3);
+    assert_eq!(first.next_cursor, Some(3));
+
+    let last = client.list_custody(&3, &3);
+    assert_eq!(last.items.len(), 1);
+    assert_eq!(last.items.get(0).unwrap().token_id, 5);
+    assert_eq!(last.next_cursor, None);
+}
+
+#[test]
+fn test_deposit_tracks_amounts() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    client.deposit(&admin, &1, &project_id, &2_500, &None, &None);
+    client.deposit(&admin, &2, &project_id, &750, &None, &None);
+
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 2_500);
+    assert_eq!(client.get_total_value_locked(), 3_250);
+    assert_eq!(client.health_check().entity_count, 2);
+
+    let result = client.try_deposit(&admin, &3, &project_id, &0, &None, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+}
+
+#[test]
+fn test_partial_withdrawal_keeps_record() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &1_000, &None, &None);
+
+    client.withdraw_to_replace(&governance, &1, &99, &400, &None);
+
+    let record = client.get_custody_record(&1).unwrap();
+    assert_eq!(record.amount, 600);
+    assert!(client.is_token_in_pool(&1));
+    assert_eq!(client.get_total_value_locked(), 600);
+
+    let result = client.try_withdraw_to_replace(&governance, &1, &99, &601, &None);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+    let result = client.try_withdraw_to_replace(&governance, &1, &99, &0, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 600);
+}
+
+#[test]
+fn test_withdraw_cooldown_per_depositor() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &1_000, &None, &None);
+    client.deposit(&admin, &2, &project_id, &1_000, &None, &None);
+
+    let result = client.try_set_withdraw_cooldown(&admin, &3_600);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    client.set_withdraw_cooldown(&governance, &3_600);
+    assert_eq!(client.get_withdraw_cooldown(), 3_600);
+
+    env.ledger().set_timestamp(1_000);
+    client.withdraw_to_replace(&governance, &1, &99, &100, &None);
+    assert_eq!(client.get_last_withdraw(&admin), Some(1_000));
+
+    // Any record of the same depositor is locked until the cooldown passes
+    env.ledger().set_timestamp(1_000 + 3_599);
+    let result = client.try_withdraw_to_replace(&governance, &2, &99, &100, &None);
+    assert_eq!(result, Err(Ok(Error::WithdrawCooldown)));
+    assert_eq!(client.get_custody_record(&2).unwrap().amount, 1_000);
+
+    env.ledger().set_timestamp(1_000 + 3_600);
+    client.withdraw_to_replace(&governance, &2, &99, &100, &None);
+    assert_eq!(client.get_custody_record(&2).unwrap().amount, 900);
+    assert_eq!(client.get_last_withdraw(&admin), Some(4_600));
+    assert_eq!(client.get_total_value_locked(), 1_800);
+}
+
+#[test]
+fn test_custody_expiry_follows_project_duration() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let short = String::from_str(&env, "PROJECT-SHORT");
+    let long = String::from_str(&env, "PROJECT-LONG");
+    let open = String::from_str(&env, "PROJECT-OPEN");
+
+    let result = client.try_set_project_custody_duration(&admin, &short, &100);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    client.set_project_custody_duration(&governance, &short, &100);
+    client.set_project_custody_duration(&governance, &long, &1_000);
+    assert_eq!(client.get_project_custody_duration(&long), 1_000);
+    assert_eq!(client.get_project_custody_duration(&open), 0);
+
+    env.ledger().set_timestamp(500);
+    assert_eq!(
+        client
+            .deposit(&admin, &1, &short, &10, &None, &None)
+            .expires_at,
+        Some(600)
+    );
+    assert_eq!(
+        client
+            .deposit(&admin, &2, &long, &20, &None, &None)
+            .expires_at,
+        Some(1_500)
+    );
+    assert_eq!(
+        client
+            .deposit(&admin, &3, &open, &30, &None, &None)
+            .expires_at,
+        None
+    );
+
+    let result = client.try_release_expired(&admin);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.release_expired(&governance).len(), 0);
+
+    env.ledger().set_timestamp(600);
+    assert_eq!(client.release_expired(&governance), vec![&env, 1]);
+    assert!(!client.is_token_in_pool(&1));
+    assert_eq!(client.get_project_buffer(&short), 0);
+    assert_eq!(client.get_total_value_locked(), 50);
+
+    // Custody without a duration is never released
+    env.ledger().set_timestamp(1_000_000);
+    assert_eq!(client.release_expired(&governance), vec![&env, 2]);
+    assert_eq!(client.get_project_buffer(&long), 0);
+    assert!(client.is_token_in_pool(&3));
+    assert_eq!(client.get_total_value_locked(), 30);
+}
+
+#[test]
+fn test_withdraw_all_skips_locked_records() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let timed = String::from_str(&env, "PROJECT-TIMED");
+    let floored = String::from_str(&env, "PROJECT-FLOORED");
+    let open = String::from_str(&env, "PROJECT-OPEN");
+    client.set_project_custody_duration(&governance, &timed, &100);
+    client.set_project_custody_duration(&governance, &floored, &100);
+    client.set_min_buffer_percentage(&governance, &floored, &5000);
+
+    client.deposit(&admin, &1, &timed, &10, &None, &None);
+    client.deposit(&admin, &2, &open, &10, &None, &None);
+    client.deposit(&admin, &3, &floored, &10, &None, &None);
+    client.deposit(&carbon_contract, &5, &timed, &10, &None, &None);
+    env.ledger().set_timestamp(50);
+    client.deposit(&admin, &4, &timed, &10, &None, &None);
+    client.on_retirement(&carbon_contract, &floored, &1, &10);
+    assert_eq!(client.get_depositor_tokens(&admin), vec![&env, 1, 2, 3, 4]);
+
+    // Token 4 is still time-locked and token 3 would take its project below
+    // the minimum buffer. Token 2 has no expiry, so nothing locks it.
+    env.ledger().set_timestamp(100);
+    assert_eq!(client.withdraw_all(&admin), 2);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("wd_all"),).into_val(&env),
+                (admin.clone(), 2u32, vec![&env, 1u32, 2u32]).into_val(&env),
+            )
+        ]
+    );
+    assert!(!client.is_token_in_pool(&1));
+    assert!(!client.is_token_in_pool(&2));
+    assert_eq!(client.get_project_buffer(&timed), 20);
+    assert_eq!(client.get_project_buffer(&open), 0);
+
+    env.ledger().set_timestamp(150);
+    assert_eq!(client.withdraw_all(&admin), 1);
+    assert_eq!(client.get_depositor_tokens(&admin), vec![&env, 3]);
+    assert!(client.is_token_in_pool(&5));
+    assert_eq!(client.get_total_value_locked(), 20);
+
+    assert_eq!(client.withdraw_all(&admin), 0);
+}
+
+#[test]
+fn test_on_retirement_rejects_overflowing_amounts() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    let result = client.try_on_retirement(&carbon_contract, &project_id, &i128::MAX, &0);
+    assert_eq!(result, Err(Ok(Error::Overflow)));
+    assert_eq!(client.get_replenishment_due(&project_id), 0);
+
+    // The largest share that fits is still scheduled
+    let large = (i128::MAX - 9999) / 500;
+    let needed = client.on_retirement(&carbon_contract, &project_id, &large, &0);
+    assert_eq!(client.get_replenishment_due(&project_id), needed);
+}
+
+#[test]
+fn test_min_buffer_check_rejects_overflowing_outstanding_credits() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.set_min_buffer_percentage(&governance, &project_id, &5000);
+    client.deposit(&admin, &1, &project_id, &10, &None, &None);
+
+    let result = client.try_on_retirement(&carbon_contract, &project_id, &1, &i128::MAX);
+    assert_eq!(result, Err(Ok(Error::Overflow)));
+    assert_eq!(client.get_replenishment_due(&project_id), 0);
+}
+
+#[test]
+fn test_withdraw_all_honours_cooldown_and_freeze() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.set_project_custody_duration(&governance, &project_id, &100);
+    client.set_withdraw_cooldown(&governance, &500);
+
+    client.deposit(&admin, &1, &project_id, &10, &None, &None);
+    client.deposit(&admin, &2, &project_id, &10, &None, &None);
+    client.deposit(&admin, &3, &project_id, &10, &None, &None);
+    client.set_custody_frozen(&governance, &2, &true);
+
+    // A governance withdrawal against the depositor starts its cooldown
+    env.ledger().set_timestamp(100);
+    client.withdraw_to_replace(&governance, &3, &99, &5, &None);
+    assert_eq!(
+        client.try_withdraw_all(&admin),
+        Err(Ok(Error::WithdrawCooldown))
+    );
+
+    // Once it has passed, the frozen record stays behind
+    env.ledger().set_timestamp(600);
+    assert_eq!(client.withdraw_all(&admin), 2);
+    assert_eq!(client.get_depositor_tokens(&admin), vec![&env, 2]);
+    assert_eq!(client.get_last_withdraw(&admin), Some(600));
+
+    // The withdrawal restarts the cooldown for the next call
+    client.set_custody_frozen(&governance, &2, &false);
+    env.ledger().set_timestamp(700);
+    assert_eq!(
+        client.try_withdraw_all(&admin),
+        Err(Ok(Error::WithdrawCooldown))
+    );
+    env.ledger().set_timestamp(1_100);
+    assert_eq!(client.withdraw_all(&admin), 1);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_withdrawal_to_zero_removes_record() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &1_000, &None, &None);
+
+    client.withdraw_to_replace(&governance, &1, &99, &400, &None);
+    client.withdraw_to_replace(&governance, &1, &99, &600, &None);
+
+    assert!(client.get_custody_record(&1).is_none());
+    assert!(!client.is_token_in_pool(&1));
+    assert_eq!(client.get_total_value_locked(), 0);
+    assert_eq!(client.list_custody(&0, &10).total, 0);
+
+    // The token id is free for a fresh deposit again
+    client.deposit(&admin, &1, &project_id, &50, &None, &None);
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 50);
+}
+
+#[test]
+fn test_tvl_overflow_is_rejected() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    client.deposit(&admin, &1, &project_id, &(i128::MAX - 1), &None, &None);
+    client.deposit(&admin, &2, &project_id, &1, &None, &None);
+    assert_eq!(client.get_total_value_locked(), i128::MAX);
+
+    let result = client.try_deposit(&admin, &3, &project_id, &1, &None, &None);
+    assert_eq!(result, Err(Ok(Error::Overflow)));
+    assert_eq!(client.get_total_value_locked(), i128::MAX);
+    assert!(!client.is_token_in_pool(&3));
+}
+
+#[test]
+fn test_tvl_underflow_is_rejected() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &10, &None, &None);
+
+    // Simulate accounting drift where the TVL lags the custody records
+    env.as_contract(&client.address, || {
+        storage::set_total_value_locked(&env, 4);
+    });
+
+    let result = client.try_withdraw_to_replace(&governance, &1, &99, &5, &None);
+    assert_eq!(result, Err(Ok(Error::Overflow)));
+    assert_eq!(client.get_total_value_locked(), 4);
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 10);
+
+    client.withdraw_to_replace(&governance, &1, &99, &4, &None);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_preview_matches_triggered_replenishment() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    client.deposit(&admin, &2, &project_id, &200, &None, &None);
+    client.deposit(&admin, &3, &project_id, &300, &None, &None);
+
+    assert_eq!(
+        client.get_replenishment_strategy(),
+        ReplenishmentStrategy::Fifo
+    );
+    let preview = client.preview_replenishment(&250);
+    assert_eq!(preview, vec![&env, 1, 2]);
+
+    // Previewing leaves the pool untouched
+    assert_eq!(client.get_total_value_locked(), 600);
+    assert_eq!(client.get_custody_record(&2).unwrap().amount, 200);
+
+    let released = client.trigger_replenishment(&governance, &250);
+    assert_eq!(released, preview);
+    assert!(!client.is_token_in_pool(&1));
+    assert_eq!(client.get_custody_record(&2).unwrap().amount, 50);
+    assert_eq!(client.get_total_value_locked(), 350);
+    assert_eq!(client.get_project_buffer(&project_id), 350);
+}
+
+#[test]
+fn test_lifo_replenishment_draws_newest_first() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    client.deposit(&admin, &2, &project_id, &200, &None, &None);
+    client.deposit(&admin, &3, &project_id, &300, &None, &None);
+
+    client.set_replenishment_strategy(&governance, &ReplenishmentStrategy::Lifo);
+    let preview = client.preview_replenishment(&400);
+    assert_eq!(preview, vec![&env, 3, 2]);
+
+    assert_eq!(client.trigger_replenishment(&governance, &400), preview);
+    assert!(!client.is_token_in_pool(&3));
+    assert_eq!(client.get_custody_record(&2).unwrap().amount, 100);
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 100);
+    assert_eq!(client.get_total_value_locked(), 200);
+}
+
+#[test]
+fn test_replenishment_rejects_invalid_requests() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+
+    assert_eq!(
+        client.try_preview_replenishment(&101),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_trigger_replenishment(&governance, &101),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_preview_replenishment(&0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_trigger_replenishment(&admin, &50),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_replenishment_strategy(&admin, &ReplenishmentStrategy::Lifo),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 100);
+}
+
+#[test]
+fn test_replenishment_cap_per_call() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+
+    assert_eq!(
+        client.try_set_max_replenish_per_call(&admin, &40),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_max_replenish_per_call(&governance, &-1),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.set_max_replenish_per_call(&governance, &40);
+    assert_eq!(client.get_max_replenish_per_call(), 40);
+
+    assert_eq!(
+        client.try_preview_replenishment(&41),
+        Err(Ok(Error::ReplenishCapExceeded))
+    );
+    assert_eq!(
+        client.try_trigger_replenishment(&governance, &41),
+        Err(Ok(Error::ReplenishCapExceeded))
+    );
+    assert_eq!(client.get_total_value_locked(), 100);
+
+    assert_eq!(
+        client.trigger_replenishment(&governance, &40),
+        vec![&env, 1]
+    );
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 60);
+
+    // Removing the cap allows the rest to be drawn at once
+    client.set_max_replenish_per_call(&governance, &0);
+    client.trigger_replenishment(&governance, &60);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_replenishment_skips_frozen_and_locked_custody() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    env.ledger().set_timestamp(1_000);
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    client.deposit(&admin, &2, &project_id, &100, &None, &None);
+
+    assert_eq!(
+        client.try_set_custody_frozen(&admin, &1, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_custody_frozen(&governance, &9, &true),
+        Err(Ok(Error::TokenNotFound))
+    );
+    client.set_custody_frozen(&governance, &1, &true);
+    assert!(client.get_custody_record(&1).unwrap().frozen);
+
+    // Frozen custody is passed over in favour of the next record
+    assert_eq!(client.preview_replenishment(&50), vec![&env, 2]);
+    assert_eq!(
+        client.try_preview_replenishment(&150),
+        Err(Ok(Error::InsufficientEligibleCustody))
+    );
+
+    // A lock period holds back recent deposits until it elapses
+    client.set_custody_frozen(&governance, &1, &false);
+    client.deposit(&admin, &3, &project_id, &100, &None, &None);
+    client.set_replenish_lock_period(&governance, &500);
+    assert_eq!(client.get_replenish_lock_period(), 500);
+    assert_eq!(
+        client.try_trigger_replenishment(&governance, &50),
+        Err(Ok(Error::InsufficientEligibleCustody))
+    );
+    assert_eq!(client.get_total_value_locked(), 300);
+
+    env.ledger().set_timestamp(1_500);
+    assert_eq!(client.preview_replenishment(&50), vec![&env, 1]);
+
+    // Shortfalls beyond all custody still report a plain shortage
+    assert_eq!(
+        client.try_preview_replenishment(&400),
+        Err(Ok(Error::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_deposit_headroom_under_depositor_and_project_caps() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_a = String::from_str(&env, "PROJECT-A");
+    let project_b = String::from_str(&env, "PROJECT-B");
+
+    // Without caps there is no practical limit
+    assert_eq!(client.get_deposit_headroom(&admin, &project_a), i128::MAX);
+
+    assert_eq!(
+        client.try_set_depositor_cap(&admin, &100),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_project_cap(&governance, &project_a, &-1),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.set_depositor_cap(&governance, &100);
+    client.set_project_cap(&governance, &project_a, &60);
+    assert_eq!(client.get_depositor_cap(), 100);
+    assert_eq!(client.get_project_cap(&project_a), 60);
+
+    // The tighter of the two caps applies
+    assert_eq!(client.get_deposit_headroom(&admin, &project_a), 60);
+    assert_eq!(client.get_deposit_headroom(&admin, &project_b), 100);
+
+    client.deposit(&admin, &1, &project_a, &50, &None, &None);
+    assert_eq!(client.get_deposit_headroom(&admin, &project_a), 10);
+    assert_eq!(client.get_deposit_headroom(&admin, &project_b), 50);
+    assert_eq!(
+        client.try_deposit(&admin, &2, &project_a, &11, &None, &None),
+        Err(Ok(Error::DepositCapExceeded))
+    );
+
+    // Filling the depositor cap elsewhere leaves no room in either project
+    client.deposit(&admin, &2, &project_b, &50, &None, &None);
+    assert_eq!(client.get_deposit_headroom(&admin, &project_a), 0);
+    assert_eq!(
+        client.try_deposit(&admin, &3, &project_a, &1, &None, &None),
+        Err(Ok(Error::DepositCapExceeded))
+    );
+
+    // Another depositor is bound only by the project cap
+    assert_eq!(
+        client.get_deposit_headroom(&carbon_contract, &project_a),
+        10
+    );
+    client.deposit(&carbon_contract, &3, &project_a, &10, &None, &None);
+    assert_eq!(client.get_deposit_headroom(&carbon_contract, &project_a), 0);
+
+    // Withdrawals free up headroom again
+    client.withdraw_to_replace(&governance, &2, &99, &20, &None);
+    assert_eq!(client.get_deposit_headroom(&admin, &project_b), 20);
+
+    client.set_depositor_cap(&governance, &0);
+    client.set_project_cap(&governance, &project_a, &0);
+    assert_eq!(client.get_deposit_headroom(&admin, &project_a), i128::MAX);
+}
+
+#[test]
+fn test_repeated_deposit_with_idempotency_key_is_noop() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    let key = BytesN::from_array(&env, &[3u8; 32]);
+
+    let record = client.deposit(&admin, &1, &project_id, &100, &None, &Some(key.clone()));
+    assert_eq!(client.get_total_value_locked(), 100);
+
+    // A retry returns the original record without counting the credits again
+    let retried = client.deposit(&admin, &1, &project_id, &100, &None, &Some(key.clone()));
+    assert_eq!(retried, record);
+    assert_eq!(client.get_total_value_locked(), 100);
+    assert_eq!(client.get_project_buffer(&project_id), 100);
+    assert_eq!(client.list_custody(&0, &10).total, 1);
+
+    // Without the key the same deposit is still rejected as a duplicate
+    let result = client.try_deposit(&admin, &1, &project_id, &100, &None, &None);
+    assert_eq!(result, Err(Ok(Error::AlreadyExists)));
+
+    // A different key is a new deposit
+    let other_key = BytesN::from_array(&env, &[4u8; 32]);
+    client.deposit(&admin, &2, &project_id, &50, &None, &Some(other_key));
+    assert_eq!(client.get_total_value_locked(), 150);
+}
+
+#[test]
+fn test_reconcile_tvl_repairs_drift() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    client.deposit(&admin, &2, &project_id, &250, &None, &None);
+    client.withdraw_to_replace(&governance, &2, &99, &50, &None);
+
+    // Nothing to correct while the books agree
+    assert_eq!(client.reconcile_tvl(&admin), 0);
+    assert_eq!(client.get_total_value_locked(), 300);
+
+    env.as_contract(&client.address, || {
+        storage::set_total_value_locked(&env, 1_000);
+    });
+    assert_eq!(client.reconcile_tvl(&admin), -700);
+    assert_eq!(client.get_total_value_locked(), 300);
+
+    env.as_contract(&client.address, || {
+        storage::set_total_value_locked(&env, 20);
+    });
+    assert_eq!(client.reconcile_tvl(&admin), 280);
+    assert_eq!(client.get_total_value_locked(), 300);
+
+    let result = client.try_reconcile_tvl(&governance);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_time_weighted_avg_tvl() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    let result = client.try_snapshot_tvl(&governance, &project_id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_time_weighted_avg_tvl(&project_id, &0, &100), 0);
+
+    // 100 from t=100, 300 from t=200, 0 from t=400
+    env.ledger().set_timestamp(100);
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    assert_eq!(client.snapshot_tvl(&admin, &project_id), 100);
+    env.ledger().set_timestamp(200);
+    client.deposit(&admin, &2, &project_id, &200, &None, &None);
+    client.snapshot_tvl(&admin, &project_id);
+    env.ledger().set_timestamp(400);
+    client.trigger_replenishment(&governance, &300);
+    client.snapshot_tvl(&admin, &project_id);
+    client.snapshot_tvl(&admin, &project_id);
+    assert_eq!(client.get_tvl_snapshots(&project_id).len(), 3);
+
+    assert_eq!(
+        client.get_time_weighted_avg_tvl(&project_id, &100, &200),
+        100
+    );
+    // (100 * 100 + 300 * 200) / 300
+    assert_eq!(
+        client.get_time_weighted_avg_tvl(&project_id, &100, &400),
+        233
+    );
+    // Nothing recorded before t=100, and the last value carries past t=400
+    assert_eq!(client.get_time_weighted_avg_tvl(&project_id, &0, &200), 50);
+    assert_eq!(
+        client.get_time_weighted_avg_tvl(&project_id, &300, &500),
+        150
+    );
+    assert_eq!(
+        client.get_time_weighted_avg_tvl(&project_id, &250, &250),
+        300
+    );
+}
+
+#[test]
+fn test_get_net_flow() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    let other_id = String::from_str(&env, "PROJECT-002");
+
+    // +100 at t=100, +50 and -20 at t=200, -30 at t=300, -40 at t=400
+    env.ledger().set_timestamp(100);
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    env.ledger().set_timestamp(200);
+    client.deposit(&admin, &2, &project_id, &50, &None, &None);
+    client.withdraw_to_replace(&governance, &1, &99, &20, &None);
+    env.ledger().set_timestamp(300);
+    client.withdraw_to_replace(&governance, &1, &99, &30, &None);
+    client.deposit(&admin, &3, &other_id, &500, &None, &None);
+    env.ledger().set_timestamp(400);
+    client.trigger_replenishment(&governance, &40);
+
+    assert_eq!(client.get_net_flow(&project_id, &0, &1_000), 60);
+    assert_eq!(client.get_net_flow(&project_id, &100, &200), 130);
+    // Bounds are inclusive, so a window starting on an entry counts it
+    assert_eq!(client.get_net_flow(&project_id, &200, &300), 0);
+    assert_eq!(client.get_net_flow(&project_id, &201, &400), -70);
+    assert_eq!(client.get_net_flow(&project_id, &150, &199), 0);
+    assert_eq!(client.get_net_flow(&other_id, &0, &1_000), 500);
+
+    // Empty range
+    assert_eq!(client.get_net_flow(&project_id, &300, &200), 0);
+}
+
+#[test]
+fn test_lifetime_stats_accumulate() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    let expiring_id = String::from_str(&env, "PROJECT-002");
+    client.set_project_custody_duration(&governance, &expiring_id, &100);
+
+    let empty = client.get_lifetime_stats();
+    assert_eq!(
+        (
+            empty.total_deposited,
+            empty.total_withdrawn,
+            empty.total_replenished,
+            empty.current_tvl
+        ),
+        (0, 0, 0, 0)
+    );
+
+    env.ledger().set_timestamp(1_000);
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    client.deposit(&admin, &2, &expiring_id, &40, &None, &None);
+    client.withdraw_to_replace(&governance, &1, &99, &20, &None);
+    client.trigger_replenishment(&governance, &30);
+    let stats = client.get_lifetime_stats();
+    assert_eq!(stats.total_deposited, 140);
+    assert_eq!(stats.total_withdrawn, 20);
+    assert_eq!(stats.total_replenished, 30);
+    assert_eq!(stats.current_tvl, 90);
+
+    // Totals only grow, while the TVL follows the pool back down
+    env.ledger().set_timestamp(1_100);
+    client.release_expired(&governance);
+    client.deposit(&admin, &3, &project_id, &10, &None, &None);
+    client.trigger_replenishment(&governance, &60);
+    let stats = client.get_lifetime_stats();
+    assert_eq!(stats.total_deposited, 150);
+    assert_eq!(stats.total_withdrawn, 60);
+    assert_eq!(stats.total_replenished, 90);
+    assert_eq!(stats.current_tvl, 0);
+    assert_eq!(stats.current_tvl, client.get_total_value_locked());
+}
+
+#[test]
+fn test_get_custody_in_range() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    for (token_id, timestamp) in [(1, 100), (2, 200), (3, 300), (4, 400)] {
+        env.ledger().set_timestamp(timestamp);
+        client.deposit(&admin, &token_id, &project_id, &10, &None, &None);
+    }
+
+    let token_ids = |from: u64, to: u64| {
+        let mut ids = vec![&env];
+        for record in client.get_custody_in_range(&from, &to).iter() {
+            ids.push_back(record.token_id);
+        }
+        ids
+    };
+
+    assert_eq!(token_ids(0, 1_000), vec![&env, 1, 2, 3, 4]);
+    assert_eq!(token_ids(200, 300), vec![&env, 2, 3]);
+    assert_eq!(token_ids(150, 250), vec![&env, 2]);
+    assert_eq!(token_ids(400, 400), vec![&env, 4]);
+    assert_eq!(token_ids(401, 1_000).len(), 0);
+    assert_eq!(token_ids(300, 200).len(), 0);
 }
@@ -1,7 +1,82 @@
 #![cfg(test)]
 
 use buffer_pool::{BufferPoolContract, BufferPoolContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use shared_types::{Project, ProjectStatus, Revocation, RevocationReason, RevocationStatus};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, testutils::Address as _, vec, Address, Env, String,
+};
+
+/// Minimal stand-in for the project registry exposing `get_project`
+#[contract]
+pub struct MockRegistry;
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MockRegistryError {
+    ProjectNotFound = 3,
+}
+
+#[contractimpl]
+impl MockRegistry {
+    pub fn set_project(env: Env, project_id: String, project: Project) {
+        env.storage().persistent().set(&project_id, &project);
+    }
+
+    pub fn get_project(env: Env, project_id: String) -> Result<Project, MockRegistryError> {
+        env.storage()
+            .persistent()
+            .get(&project_id)
+            .ok_or(MockRegistryError::ProjectNotFound)
+    }
+}
+
+/// Stand-in for the carbon asset contract that reports retirements to the pool
+#[contract]
+pub struct MockCarbonAsset;
+
+#[contractimpl]
+impl MockCarbonAsset {
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, owner: Address) -> i128 {
+        env.storage().persistent().get(&owner).unwrap_or(0)
+    }
+
+    pub fn retire(
+        env: Env,
+        pool: Address,
+        project_id: String,
+        amount: i128,
+        outstanding: i128,
+    ) -> Result<i128, buffer_pool::Error> {
+        match BufferPoolContractClient::new(&env, &pool).try_on_retirement(
+            &env.current_contract_address(),
+            &project_id,
+            &amount,
+            &outstanding,
+        ) {
+            Ok(Ok(needed)) => Ok(needed),
+            Err(Ok(error)) => Err(error),
+            _ => panic!("unexpected on_retirement failure"),
+        }
+    }
+}
+
+fn registry_project(env: &Env, revocation: RevocationStatus) -> Project {
+    Project {
+        owner: Address::generate(env),
+        status: ProjectStatus::Verified,
+        registered_at: 0,
+        expires_at: 1_000_000,
+        expired: false,
+        revocation,
+        total_retired: 0,
+    }
+}
 
 #[test]
 fn test_full_lifecycle() {
@@ -27,9 +102,9 @@ fn test_full_lifecycle() {
         let should_deposit = client.auto_deposit(&carbon_contract, &i, &project_id, &i);
 
         if i % 20 == 0 {
-            assert_eq!(should_deposit, true);
+            assert!(should_deposit);
         } else {
-            assert_eq!(should_deposit, false);
+            assert!(!should_deposit);
         }
     }
 
@@ -43,7 +118,7 @@ fn test_full_lifecycle() {
     assert!(!client.is_token_in_pool(&21));
 
     // Governance withdraws for replacement
-    client.withdraw_to_replace(&governance, &20, &1000);
+    client.withdraw_to_replace(&governance, &20, &1000, &1, &None);
 
     let tvl = client.get_total_value_locked();
     assert_eq!(tvl, 4);
@@ -52,7 +127,7 @@ fn test_full_lifecycle() {
 
     // Admin manually deposits
     let project_id_2 = String::from_str(&env, "PROJECT-002");
-    client.deposit(&admin, &999, &project_id_2);
+    client.deposit(&admin, &999, &project_id_2, &1, &None, &None);
 
     let tvl = client.get_total_value_locked();
     assert_eq!(tvl, 5);
@@ -91,10 +166,10 @@ fn test_governance_updates() {
 
     // With 10% (1000 bp), every 10th token should be deposited
     let should_deposit = client.auto_deposit(&carbon_contract, &10, &project_id, &10);
-    assert_eq!(should_deposit, true);
+    assert!(should_deposit);
 
     let should_not_deposit = client.auto_deposit(&carbon_contract, &11, &project_id, &11);
-    assert_eq!(should_not_deposit, false);
+    assert!(!should_not_deposit);
 }
 
 #[test]
@@ -111,14 +186,14 @@ fn test_multiple_projects() {
     client.initialize(&admin, &governance, &carbon_contract, &500);
 
     // Deposit from multiple projects
-    let projects = vec![
+    let projects = [
         String::from_str(&env, "PROJECT-A"),
         String::from_str(&env, "PROJECT-B"),
         String::from_str(&env, "PROJECT-C"),
     ];
 
     for (i, project) in projects.iter().enumerate() {
-        client.deposit(&admin, &((i as u32) + 1), project);
+        client.deposit(&admin, &((i as u32) + 1), project, &1, &None, &None);
     }
 
     let tvl = client.get_total_value_locked();
@@ -134,3 +209,385 @@ fn test_multiple_projects() {
     let record_c = client.get_custody_record(&3).unwrap();
     assert_eq!(record_c.project_id, projects[2]);
 }
+
+#[test]
+fn test_deposit_requires_registered_project() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    let registry_id = env.register(MockRegistry, ());
+    let registry = MockRegistryClient::new(&env, &registry_id);
+    client.set_registry_contract(&admin, &registry_id);
+    assert_eq!(client.get_registry_contract(), Some(registry_id));
+
+    let active = String::from_str(&env, "PROJECT-ACTIVE");
+    registry.set_project(
+        &active,
+        &registry_project(&env, RevocationStatus::NotRevoked),
+    );
+
+    let revoked = String::from_str(&env, "PROJECT-REVOKED");
+    registry.set_project(
+        &revoked,
+        &registry_project(
+            &env,
+            RevocationStatus::Revoked(Revocation {
+                code: RevocationReason::Fraud,
+                reason: String::from_str(&env, "Fabricated monitoring data"),
+                revoked_at: 0,
+            }),
+        ),
+    );
+
+    client.deposit(&admin, &1, &active, &1, &None, &None);
+    assert!(client.is_token_in_pool(&1));
+
+    let result = client.try_deposit(&admin, &2, &revoked, &1, &None, &None);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::UnknownProject)));
+
+    let phantom = String::from_str(&env, "PROJECT-PHANTOM");
+    let result = client.try_deposit(&admin, &3, &phantom, &1, &None, &None);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::UnknownProject)));
+
+    assert_eq!(client.get_total_value_locked(), 1);
+}
+
+#[test]
+fn test_find_orphan_custody_reports_unknown_and_revoked_projects() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    // Legacy custody deposited before the registry was wired in
+    let active = String::from_str(&env, "PROJECT-ACTIVE");
+    let missing = String::from_str(&env, "PROJECT-MISSING");
+    let revoked = String::from_str(&env, "PROJECT-REVOKED");
+    client.deposit(&admin, &1, &active, &10, &None, &None);
+    client.deposit(&admin, &2, &missing, &10, &None, &None);
+    client.deposit(&admin, &3, &revoked, &10, &None, &None);
+    client.deposit(&admin, &4, &active, &10, &None, &None);
+    assert_eq!(client.find_orphan_custody(&0, &10).len(), 0);
+
+    let registry_id = env.register(MockRegistry, ());
+    let registry = MockRegistryClient::new(&env, &registry_id);
+    client.set_registry_contract(&admin, &registry_id);
+    registry.set_project(
+        &active,
+        &registry_project(&env, RevocationStatus::NotRevoked),
+    );
+    registry.set_project(
+        &revoked,
+        &registry_project(
+            &env,
+            RevocationStatus::Revoked(Revocation {
+                code: RevocationReason::Fraud,
+                reason: String::from_str(&env, "Fabricated monitoring data"),
+                revoked_at: 0,
+            }),
+        ),
+    );
+
+    assert_eq!(client.find_orphan_custody(&0, &10), vec![&env, 2, 3]);
+    assert_eq!(client.find_orphan_custody(&2, &2), vec![&env, 3]);
+    assert_eq!(client.find_orphan_custody(&3, &1).len(), 0);
+}
+
+#[test]
+fn test_reassign_custody_moves_project_buffer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    let registry_id = env.register(MockRegistry, ());
+    let registry = MockRegistryClient::new(&env, &registry_id);
+    client.set_registry_contract(&admin, &registry_id);
+
+    let source = String::from_str(&env, "PROJECT-A");
+    let target = String::from_str(&env, "PROJECT-B");
+    for project_id in [&source, &target] {
+        registry.set_project(
+            project_id,
+            &registry_project(&env, RevocationStatus::NotRevoked),
+        );
+    }
+
+    client.deposit(&admin, &1, &source, &300, &None, &None);
+    client.deposit(&admin, &2, &source, &200, &None, &None);
+
+    let result = client.try_reassign_custody_project(&admin, &1, &target);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+    let phantom = String::from_str(&env, "PROJECT-PHANTOM");
+    let result = client.try_reassign_custody_project(&governance, &1, &phantom);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::UnknownProject)));
+    let result = client.try_reassign_custody_project(&governance, &9, &target);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::TokenNotFound)));
+
+    client.reassign_custody_project(&governance, &1, &target);
+    assert_eq!(client.get_custody_record(&1).unwrap().project_id, target);
+    assert_eq!(client.get_project_buffer(&source), 200);
+    assert_eq!(client.get_project_buffer(&target), 300);
+    assert_eq!(client.get_total_value_locked(), 500);
+
+    // Later withdrawals draw on the new project's buffer
+    client.withdraw_to_replace(&governance, &1, &99, &100, &None);
+    assert_eq!(client.get_project_buffer(&source), 200);
+    assert_eq!(client.get_project_buffer(&target), 200);
+}
+
+#[test]
+fn test_deposit_fees_accrue_and_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_id = env.register(MockCarbonAsset, ());
+    let carbon = MockCarbonAssetClient::new(&env, &carbon_id);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_id, &500);
+
+    let result = client.try_set_deposit_fee(&admin, &250);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+    let result = client.try_set_deposit_fee(&governance, &10_000);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::InvalidPercentage)));
+    client.set_deposit_fee(&governance, &250);
+    assert_eq!(client.get_deposit_fee(), 250);
+
+    // 2.5% of each deposit accrues as fees; custody keeps the rest
+    let project_id = String::from_str(&env, "PROJECT-001");
+    let record = client.deposit(&admin, &1, &project_id, &1_000, &None, &None);
+    assert_eq!(record.amount, 975);
+    client.deposit(&admin, &2, &project_id, &400, &None, &None);
+    assert_eq!(client.get_accrued_fees(), 35);
+    assert_eq!(client.get_total_value_locked(), 1_365);
+    assert_eq!(client.get_project_buffer(&project_id), 1_365);
+
+    let treasury = Address::generate(&env);
+    let result = client.try_withdraw_accrued_fees(&admin, &treasury, &10);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+
+    client.withdraw_accrued_fees(&governance, &treasury, &20);
+    assert_eq!(client.get_accrued_fees(), 15);
+    assert_eq!(carbon.balance(&treasury), 20);
+
+    let result = client.try_withdraw_accrued_fees(&governance, &treasury, &16);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::InsufficientBalance)));
+    let result = client.try_withdraw_accrued_fees(&governance, &treasury, &0);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::InvalidAmount)));
+    assert_eq!(client.get_accrued_fees(), 15);
+    assert_eq!(carbon.balance(&treasury), 20);
+    assert_eq!(client.get_total_value_locked(), 1_365);
+}
+
+#[test]
+fn test_set_registry_contract_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_contract, &500);
+
+    let registry_id = env.register(MockRegistry, ());
+    let result = client.try_set_registry_contract(&governance, &registry_id);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+    assert_eq!(client.get_registry_contract(), None);
+}
+
+#[test]
+fn test_retirement_triggers_replenishment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+
+    let carbon_id = env.register(MockCarbonAsset, ());
+    let carbon = MockCarbonAssetClient::new(&env, &carbon_id);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_id, &1000);
+
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    // 10% of 25 retired, rounded up
+    assert_eq!(carbon.retire(&client.address, &project_id, &25, &0), 3);
+    assert_eq!(client.get_replenishment_due(&project_id), 3);
+
+    // Deposits for the project pay the replenishment down
+    client.deposit(&admin, &1, &project_id, &1, &None, &None);
+    client.deposit(&admin, &2, &project_id, &1, &None, &None);
+    assert_eq!(client.get_replenishment_due(&project_id), 1);
+
+    let other = String::from_str(&env, "PROJECT-002");
+    client.deposit(&admin, &3, &other, &1, &None, &None);
+    assert_eq!(client.get_replenishment_due(&project_id), 1);
+    assert_eq!(client.get_replenishment_due(&other), 0);
+}
+
+#[test]
+fn test_on_retirement_only_from_carbon_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_id = env.register(MockCarbonAsset, ());
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_id, &1000);
+
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    let result = client.try_on_retirement(&admin, &project_id, &10, &0);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+
+    let result = client.try_on_retirement(&carbon_id, &project_id, &0, &0);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::InvalidAmount)));
+
+    assert_eq!(client.get_replenishment_due(&project_id), 0);
+}
+
+#[test]
+fn test_retirement_blocked_by_min_buffer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+
+    let carbon_id = env.register(MockCarbonAsset, ());
+    let carbon = MockCarbonAssetClient::new(&env, &carbon_id);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_id, &1000);
+
+    // 60 credits buffered against a 5% floor
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &60, &None, &None);
+    client.set_min_buffer_percentage(&governance, &project_id, &500);
+    assert_eq!(client.get_min_buffer_percentage(&project_id), 500);
+    assert_eq!(client.get_project_buffer(&project_id), 60);
+
+    // Owing 10 of the buffer leaves 50, exactly 5% of 1_000 outstanding
+    assert_eq!(
+        carbon.retire(&client.address, &project_id, &100, &1_000),
+        10
+    );
+
+    // Owing another 10 would leave 40, under 5% of the 900 still outstanding
+    let result = carbon.try_retire(&client.address, &project_id, &100, &900);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::BufferTooLow)));
+    assert_eq!(client.get_replenishment_due(&project_id), 10);
+
+    // Topping the buffer up lets the retirement through
+    client.deposit(&admin, &2, &project_id, &20, &None, &None);
+    assert_eq!(carbon.retire(&client.address, &project_id, &100, &900), 10);
+    assert_eq!(client.get_replenishment_due(&project_id), 10);
+}
+
+#[test]
+fn test_min_buffer_tracks_withdrawals_and_requires_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+
+    let carbon_id = env.register(MockCarbonAsset, ());
+    let carbon = MockCarbonAssetClient::new(&env, &carbon_id);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_id, &0);
+
+    let project_id = String::from_str(&env, "PROJECT-001");
+
+    let result = client.try_set_min_buffer_percentage(&admin, &project_id, &500);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+    let result = client.try_set_min_buffer_percentage(&governance, &project_id, &10_001);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::InvalidPercentage)));
+
+    client.set_min_buffer_percentage(&governance, &project_id, &1000);
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+    assert_eq!(carbon.retire(&client.address, &project_id, &50, &1_000), 0);
+
+    // Withdrawing from the buffer lowers what retirements can rely on
+    client.withdraw_to_replace(&governance, &1, &99, &1, &None);
+    assert_eq!(client.get_project_buffer(&project_id), 99);
+    let result = carbon.try_retire(&client.address, &project_id, &50, &1_000);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::BufferTooLow)));
+}
+
+#[test]
+fn test_withdraw_to_recipient_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_id = env.register(MockCarbonAsset, ());
+    let carbon = MockCarbonAssetClient::new(&env, &carbon_id);
+
+    let client = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+    client.initialize(&admin, &governance, &carbon_id, &500);
+    let project_id = String::from_str(&env, "PROJECT-001");
+    client.deposit(&admin, &1, &project_id, &100, &None, &None);
+
+    // With enforcement off any recipient can be paid
+    let custodian = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    assert!(!client.is_recipient_allowlist_enforced());
+    client.withdraw_to(&governance, &1, &stranger, &10);
+    assert_eq!(carbon.balance(&stranger), 10);
+    assert_eq!(client.get_custody_record(&1).unwrap().amount, 90);
+
+    let result = client.try_add_allowed_recipient(&admin, &custodian);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+    client.add_allowed_recipient(&governance, &custodian);
+    assert!(client.is_allowed_recipient(&custodian));
+    let result = client.try_set_enforce_recipient_allowlist(&admin, &true);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+    client.set_enforce_recipient_allowlist(&governance, &true);
+    assert!(client.is_recipient_allowlist_enforced());
+
+    let result = client.try_withdraw_to(&governance, &1, &stranger, &10);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::RecipientNotAllowed)));
+    assert_eq!(carbon.balance(&stranger), 10);
+
+    client.withdraw_to(&governance, &1, &custodian, &30);
+    assert_eq!(carbon.balance(&custodian), 30);
+    assert_eq!(client.get_total_value_locked(), 60);
+    assert_eq!(client.get_project_buffer(&project_id), 60);
+
+    // Removal takes effect on the next withdrawal
+    client.remove_allowed_recipient(&governance, &custodian);
+    assert!(!client.is_allowed_recipient(&custodian));
+    let result = client.try_withdraw_to(&governance, &1, &custodian, &10);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::RecipientNotAllowed)));
+
+    let result = client.try_withdraw_to(&admin, &1, &custodian, &10);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Unauthorized)));
+}
@@ -1,18 +1,19 @@
 use soroban_sdk::contracterror;
 
+/// Codes occupy the carbon asset range, see `shared_types::CARBON_ASSET_ERROR_BASE`.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[contracterror]
 pub enum ContractError {
-    AlreadyInitialized = 1,
-    NotInitialized = 2,
-    NotAuthorized = 3,
-    TokenNotFound = 4,
-    NotOwner = 5,
-    TransferNotAllowed = 6,
-    StatusFrozen = 7,
-    InvalidStatusTransition = 8,
-    ComplianceFailed = 9,
-    RegulatoryNotSet = 10,
-    HostJurisdictionNotSet = 11,
-    TokenAlreadyBurned = 12,
+    AlreadyInitialized = 101,
+    NotInitialized = 102,
+    NotAuthorized = 103,
+    TokenNotFound = 104,
+    NotOwner = 105,
+    TransferNotAllowed = 106,
+    StatusFrozen = 107,
+    InvalidStatusTransition = 108,
+    ComplianceFailed = 109,
+    RegulatoryNotSet = 110,
+    HostJurisdictionNotSet = 111,
+    TokenAlreadyBurned = 112,
 }
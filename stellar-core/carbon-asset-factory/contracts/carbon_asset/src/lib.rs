@@ -3,19 +3,21 @@
 mod errors;
 mod events;
 mod storage;
-mod types;
 #[cfg(test)]
 mod test;
+mod types;
 
-use soroban_sdk::{contract, contractimpl, Address, Env, IntoVal, String, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, IntoVal, String, Symbol, Vec};
 
-use crate::errors::ContractError;
 use crate::events::{
     ApproveEvent, MintEvent, QualityScoreUpdatedEvent, Sep41BurnEvent, Sep41TransferEvent,
     StatusChangeEvent, TransferEvent,
 };
 use crate::storage::DataKey;
-use crate::types::{AllowanceData, AssetStatus, CarbonAssetMetadata, OperationType, ValidationResult};
+use crate::types::{AllowanceData, OperationType, ValidationResult};
+
+pub use crate::errors::ContractError;
+pub use crate::types::{AssetStatus, CarbonAssetMetadata};
 
 // ========================================================================
 // Contract
@@ -128,7 +130,11 @@ impl CarbonAsset {
 
     pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
         let key = DataKey::Allowance(from, spender);
-        if let Some(allowance) = env.storage().persistent().get::<DataKey, AllowanceData>(&key) {
+        if let Some(allowance) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, AllowanceData>(&key)
+        {
             if allowance.live_until_ledger < env.ledger().sequence() {
                 0
             } else {
@@ -175,7 +181,12 @@ impl CarbonAsset {
         Self::balance_of(env, owner)
     }
 
-    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), ContractError> {
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
         from.require_auth();
         Self::transfer_amount_internal(env, from, to, amount)
     }
@@ -258,9 +269,7 @@ impl CarbonAsset {
         env.storage()
             .persistent()
             .set(&DataKey::Burned(token_id), &true);
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Owner(token_id));
+        env.storage().persistent().remove(&DataKey::Owner(token_id));
 
         Ok(())
     }
@@ -277,19 +286,15 @@ impl CarbonAsset {
     ) -> Result<bool, ContractError> {
         let _status = Self::get_status(env.clone(), token_id)?;
 
-        let regulatory_contract: Option<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::RegulatoryCheck);
+        let regulatory_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::RegulatoryCheck);
 
         if regulatory_contract.is_none() {
             return Ok(true);
         }
 
-        let host_jurisdiction: Option<String> = env
-            .storage()
-            .instance()
-            .get(&DataKey::HostJurisdiction);
+        let host_jurisdiction: Option<String> =
+            env.storage().instance().get(&DataKey::HostJurisdiction);
 
         let host_jurisdiction = match host_jurisdiction {
             Some(value) => value,
@@ -309,12 +314,35 @@ impl CarbonAsset {
         args.push_back(to.into_val(&env));
         args.push_back(operation.into_val(&env));
         args.push_back(host_jurisdiction.into_val(&env));
+        args.push_back(None::<BytesN<32>>.into_val(&env));
 
         let result: ValidationResult = env.invoke_contract(&contract, &symbol, args);
 
         Ok(result.is_compliant && !result.requires_authorization)
     }
 
+    /// Count an executing transfer against its jurisdiction pair's rate limit
+    /// in the regulatory check. Validation only reads the limit, so each
+    /// transfer that passed `before_transfer` is counted here.
+    fn record_pair_transfer(env: &Env, from: &Address, to: &Address) -> Result<(), ContractError> {
+        let regulatory_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::RegulatoryCheck);
+        let Some(contract) = regulatory_contract else {
+            return Ok(());
+        };
+
+        let symbol = Symbol::new(env, "record_pair_transfer");
+        let mut args = Vec::new(env);
+        args.push_back(env.current_contract_address().into_val(env));
+        args.push_back(from.into_val(env));
+        args.push_back(to.into_val(env));
+
+        match env.try_invoke_contract::<(), soroban_sdk::Error>(&contract, &symbol, args) {
+            Ok(Ok(())) => Ok(()),
+            _ => Err(ContractError::ComplianceFailed),
+        }
+    }
+
     // ====================================================================
     // Status Management
     // ====================================================================
@@ -485,17 +513,11 @@ impl CarbonAsset {
     }
 
     pub fn name(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Name)
-            .unwrap()
+        env.storage().instance().get(&DataKey::Name).unwrap()
     }
 
     pub fn symbol(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Symbol)
-            .unwrap()
+        env.storage().instance().get(&DataKey::Symbol).unwrap()
     }
 
     pub fn get_retirement_tracker(env: Env) -> Result<Address, ContractError> {
@@ -607,6 +629,7 @@ impl CarbonAsset {
         if !Self::before_transfer(env.clone(), from.clone(), to.clone(), token_id)? {
             return Err(ContractError::ComplianceFailed);
         }
+        Self::record_pair_transfer(&env, &from, &to)?;
 
         Self::remove_token_from_owner(env.clone(), from.clone(), token_id)?;
         Self::add_token_to_owner(env.clone(), to.clone(), token_id);
@@ -650,11 +673,7 @@ impl CarbonAsset {
         Ok(())
     }
 
-    fn burn_amount_internal(
-        env: Env,
-        from: Address,
-        amount: i128,
-    ) -> Result<(), ContractError> {
+    fn burn_amount_internal(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
         if amount <= 0 {
             return Err(ContractError::InvalidStatusTransition);
         }
@@ -802,7 +821,7 @@ impl CarbonAsset {
             .get(&DataKey::TokenIndex(token_id))
             .ok_or(ContractError::TokenNotFound)?;
 
-        if tokens.len() == 0 {
+        if tokens.is_empty() {
             return Err(ContractError::TokenNotFound);
         }
 
@@ -817,8 +836,10 @@ impl CarbonAsset {
         }
 
         tokens.pop_back();
-        if tokens.len() == 0 {
-            env.storage().persistent().remove(&DataKey::OwnerTokens(owner));
+        if tokens.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::OwnerTokens(owner));
         } else {
             env.storage()
                 .persistent()
@@ -1,7 +1,9 @@
 #![cfg(test)]
 
 use super::{CarbonAsset, CarbonAssetClient};
+use crate::errors::ContractError;
 use crate::types::{AssetStatus, CarbonAssetMetadata};
+use shared_types::{error_source, ErrorSource};
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{Address, BytesN, Env, String};
 
@@ -109,3 +111,25 @@ fn test_transfer_to_retirement_tracker_sets_status() {
     client.transfer(&owner, &retirement_tracker, &1);
     assert_eq!(client.get_status(&token_id), AssetStatus::Retired);
 }
+
+#[test]
+fn test_error_codes_in_carbon_asset_range() {
+    let errors = [
+        ContractError::AlreadyInitialized,
+        ContractError::NotInitialized,
+        ContractError::NotAuthorized,
+        ContractError::TokenNotFound,
+        ContractError::NotOwner,
+        ContractError::TransferNotAllowed,
+        ContractError::StatusFrozen,
+        ContractError::InvalidStatusTransition,
+        ContractError::ComplianceFailed,
+        ContractError::RegulatoryNotSet,
+        ContractError::HostJurisdictionNotSet,
+        ContractError::TokenAlreadyBurned,
+    ];
+
+    for error in errors {
+        assert_eq!(error_source(error as u32), Some(ErrorSource::CarbonAsset));
+    }
+}
@@ -20,22 +20,7 @@ pub struct CarbonAssetMetadata {
 }
 
 // Shared with RegulatoryCheck contract for validation.
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[contracttype]
-pub enum OperationType {
-    TRANSFER,
-    RETIREMENT,
-}
-
-#[derive(Clone)]
-#[contracttype]
-pub struct ValidationResult {
-    pub is_compliant: bool,
-    pub rule_id: Option<String>,
-    pub requires_authorization: bool,
-    pub authority_address: Option<Address>,
-    pub error_message: Option<String>,
-}
+pub use shared_types::{OperationType, ValidationResult};
 
 #[derive(Clone)]
 #[contracttype]
@@ -0,0 +1,128 @@
+#![no_std]
+
+//! Kill switch that pauses or resumes the regulatory check, buffer pool and
+//! registry contracts in one call during an incident. Each target stores
+//! this contract as its coordinator and accepts `set_paused_by_coordinator`
+//! from it alone.
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype, Address,
+    Env, Vec,
+};
+
+/// Interface each coordinated contract exposes
+#[contractclient(name = "PausableClient")]
+pub trait PausableInterface {
+    fn set_paused_by_coordinator(env: Env, coordinator_caller: Address, paused: bool);
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    /// Contracts the pause is fanned out to, in call order
+    Targets,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracterror]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotAuthorized = 2,
+}
+
+/// Emitted once every target has been paused or resumed
+#[contractevent(topics = ["coordinated"])]
+pub struct CoordinatedPause {
+    #[topic]
+    pub paused: bool,
+    pub targets: Vec<Address>,
+}
+
+#[contract]
+pub struct PauseCoordinator;
+
+#[contractimpl]
+impl PauseCoordinator {
+    /// Set the admin allowed to trigger the switch and the contracts it reaches
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        targets: Vec<Address>,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Targets, &targets);
+        Ok(())
+    }
+
+    /// Replace the contracts the switch reaches (admin only)
+    pub fn set_targets(
+        env: Env,
+        caller: Address,
+        targets: Vec<Address>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().instance().set(&DataKey::Targets, &targets);
+        Ok(())
+    }
+
+    /// Pause every target (admin only)
+    pub fn pause_all(env: Env, caller: Address) -> Result<(), ContractError> {
+        Self::fan_out(&env, &caller, true)
+    }
+
+    /// Resume every target (admin only)
+    pub fn resume_all(env: Env, caller: Address) -> Result<(), ContractError> {
+        Self::fan_out(&env, &caller, false)
+    }
+
+    /// Get the contracts the switch reaches
+    pub fn get_targets(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Targets)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+
+    // ========================================================================
+    // Helper Functions
+    // ========================================================================
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if Self::get_admin(env.clone()) != Some(caller.clone()) {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        Ok(())
+    }
+
+    /// Call every target in turn. A target that rejects the call aborts the
+    /// whole invocation, so the targets never end up half paused.
+    fn fan_out(env: &Env, caller: &Address, paused: bool) -> Result<(), ContractError> {
+        Self::require_admin(env, caller)?;
+
+        let coordinator = env.current_contract_address();
+        let targets = Self::get_targets(env.clone());
+        for target in targets.iter() {
+            PausableClient::new(env, &target).set_paused_by_coordinator(&coordinator, &paused);
+        }
+
+        CoordinatedPause { paused, targets }.publish(env);
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use crate::{ContractError, PauseCoordinator, PauseCoordinatorClient};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, vec, Address, Env, Symbol};
+
+/// Target that accepts pauses from its stored coordinator only
+#[contract]
+pub struct MockPausable;
+
+#[contractimpl]
+impl MockPausable {
+    pub fn set_coordinator(env: Env, coordinator: Address) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "coordinator"), &coordinator);
+    }
+
+    pub fn set_paused_by_coordinator(env: Env, coordinator_caller: Address, paused: bool) {
+        coordinator_caller.require_auth();
+        let coordinator: Address = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "coordinator"))
+            .unwrap();
+        assert_eq!(coordinator, coordinator_caller);
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "paused"), &paused);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "paused"))
+            .unwrap_or(false)
+    }
+}
+
+#[test]
+fn test_pause_all_fans_out_to_targets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let client = PauseCoordinatorClient::new(&env, &env.register(PauseCoordinator, ()));
+    let first = MockPausableClient::new(&env, &env.register(MockPausable, ()));
+    let second = MockPausableClient::new(&env, &env.register(MockPausable, ()));
+    for target in [&first, &second] {
+        target.set_coordinator(&client.address);
+    }
+
+    let targets = vec![&env, first.address.clone(), second.address.clone()];
+    client.initialize(&admin, &targets);
+    assert_eq!(client.get_targets(), targets);
+    assert_eq!(
+        client.try_initialize(&admin, &targets),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_pause_all(&outsider),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert!(!first.is_paused());
+
+    client.pause_all(&admin);
+    assert!(first.is_paused() && second.is_paused());
+
+    client.resume_all(&admin);
+    assert!(!first.is_paused() && !second.is_paused());
+
+    // Targets dropped from the list are left alone
+    client.set_targets(&admin, &vec![&env, second.address.clone()]);
+    client.pause_all(&admin);
+    assert!(!first.is_paused());
+    assert!(second.is_paused());
+}
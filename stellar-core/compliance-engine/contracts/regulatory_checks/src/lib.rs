@@ -1,18 +1,139 @@
 #![no_std]
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype, Address,
+    BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
+use shared_types::{page_bounds, page_type, Project, ProjectStatus};
+pub use shared_types::{HealthStatus, OperationType, ValidationResult};
+
+/// Emitted by `validate_transaction` when the caller supplies a correlation id
+#[contractevent(topics = ["validated"])]
+pub struct TransactionValidated {
+    #[topic]
+    pub correlation_id: BytesN<32>,
+    pub source: Address,
+    pub destination: Address,
+    pub is_compliant: bool,
+    pub rule_id: Option<String>,
+}
+
+/// Emitted whenever validation settles on a rule, compliant or not
+#[contractevent(topics = ["rule_matched"])]
+pub struct RuleMatched {
+    #[topic]
+    pub rule_id: String,
+    pub is_allowed: bool,
+}
+
+/// Emitted by `rotate_authority` with the rules moved to the new authority
+#[contractevent(topics = ["auth_rotated"])]
+pub struct AuthorityRotated {
+    #[topic]
+    pub old_authority: Address,
+    #[topic]
+    pub new_authority: Address,
+    pub rule_ids: Vec<String>,
+}
+
+/// Emitted by `set_address_jurisdiction` with the previous and new assignment
+#[contractevent(topics = ["jurisdiction"])]
+pub struct JurisdictionChanged {
+    #[topic]
+    pub account: Address,
+    pub old_jurisdiction: Option<String>,
+    pub new_jurisdiction: String,
+}
+
+/// Emitted by `record_authorization` when an authority approves a pending approval
+#[contractevent(topics = ["approval", "recorded"])]
+pub struct ApprovalRecorded {
+    pub approval_key: BytesN<32>,
+    pub authority: Address,
+}
+
+/// Emitted by `check_approval` when the approval it finds has run out of time
+#[contractevent(topics = ["approval", "expired"])]
+pub struct ApprovalExpired {
+    pub approval_key: BytesN<32>,
+}
+
+/// Subset of the CarbonAsset contract interface this contract calls back into
+#[contractclient(name = "CarbonAssetClient")]
+pub trait CarbonAssetInterface {
+    fn owner_of(env: Env, token_id: u32) -> Address;
+}
+
+/// Subset of the project registry interface this contract reads from
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn get_token_project(env: Env, token_id: u32) -> String;
+    fn get_project_jurisdiction(env: Env, project_id: String) -> String;
+    fn get_project(env: Env, project_id: String) -> Project;
+    fn has_valid_address_attestation(env: Env, account: Address) -> bool;
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct JurisdictionRule {
+    pub rule_id: String,
+    pub description: String,
+    pub source_jur: String,
+    pub dest_jur: String,
+    pub host_jur: String,
+    pub operation: OperationType,
+    pub is_allowed: bool,
+    pub required_authority: Option<Address>,
+    /// Ledger timestamp after which the rule no longer matches; `None` never expires
+    pub expires_at: Option<u64>,
+    /// Registry project the rule applies to; it can only be activated once verified
+    pub project_id: Option<String>,
+    /// Without a `required_authority`, require the governance-set default
+    /// authority instead, whichever address that currently is
+    pub use_default_authority: bool,
+    /// Minimum seconds between two authorizations recorded under this rule
+    pub cooldown: Option<u64>,
+    /// Amounts below this, as passed to `validate_transaction_with_amount`,
+    /// are compliant without the rule's authority signing off
+    pub authority_min_amount: Option<i128>,
+    /// With the attestation check enabled, both parties need a valid KYC
+    /// attestation in the registry for the rule to allow the transaction
+    pub requires_attestation: bool,
+    /// Grouping for `get_rules_by_category`, changed with `recategorize_rule`
+    pub category: Option<String>,
+}
+
+/// How one active rule compares against a transaction, from `explain_validation`
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
-pub enum OperationType {
-    TRANSFER,
-    RETIREMENT,
+pub struct RuleMatchExplanation {
+    pub rule_id: String,
+    pub operation_matches: bool,
+    pub source_matches: bool,
+    pub dest_matches: bool,
+    pub host_matches: bool,
+    /// The rule's `expires_at` has passed
+    pub expired: bool,
+    /// Every field matched and the rule has not expired
+    pub matched: bool,
+    pub is_allowed: bool,
 }
 
+page_type!(
+    /// Page of active rules returned by `get_active_rules_paged`
+    #[derive(Clone)]
+    RulePage,
+    JurisdictionRule
+);
+
+/// Rule layout stored at schema version 7, kept so `migrate` can read it
 #[derive(Clone)]
 #[contracttype]
-pub struct JurisdictionRule {
+pub struct JurisdictionRuleV7 {
     pub rule_id: String,
     pub description: String,
     pub source_jur: String,
@@ -21,16 +142,157 @@ pub struct JurisdictionRule {
     pub operation: OperationType,
     pub is_allowed: bool,
     pub required_authority: Option<Address>,
+    pub expires_at: Option<u64>,
+    pub project_id: Option<String>,
+    pub use_default_authority: bool,
+    pub cooldown: Option<u64>,
+    pub authority_min_amount: Option<i128>,
+    pub requires_attestation: bool,
 }
 
+/// Rule layout stored at schema version 6, kept so `migrate` can read it
 #[derive(Clone)]
 #[contracttype]
-pub struct ValidationResult {
-    pub is_compliant: bool,
+pub struct JurisdictionRuleV6 {
+    pub rule_id: String,
+    pub description: String,
+    pub source_jur: String,
+    pub dest_jur: String,
+    pub host_jur: String,
+    pub operation: OperationType,
+    pub is_allowed: bool,
+    pub required_authority: Option<Address>,
+    pub expires_at: Option<u64>,
+    pub project_id: Option<String>,
+    pub use_default_authority: bool,
+    pub cooldown: Option<u64>,
+    pub authority_min_amount: Option<i128>,
+}
+
+/// Rule layout stored at schema version 5, kept so `migrate` can read it
+#[derive(Clone)]
+#[contracttype]
+pub struct JurisdictionRuleV5 {
+    pub rule_id: String,
+    pub description: String,
+    pub source_jur: String,
+    pub dest_jur: String,
+    pub host_jur: String,
+    pub operation: OperationType,
+    pub is_allowed: bool,
+    pub required_authority: Option<Address>,
+    pub expires_at: Option<u64>,
+    pub project_id: Option<String>,
+    pub use_default_authority: bool,
+    pub cooldown: Option<u64>,
+}
+
+/// Rule layout stored at schema version 4, kept so `migrate` can read it
+#[derive(Clone)]
+#[contracttype]
+pub struct JurisdictionRuleV4 {
+    pub rule_id: String,
+    pub description: String,
+    pub source_jur: String,
+    pub dest_jur: String,
+    pub host_jur: String,
+    pub operation: OperationType,
+    pub is_allowed: bool,
+    pub required_authority: Option<Address>,
+    pub expires_at: Option<u64>,
+    pub project_id: Option<String>,
+    pub use_default_authority: bool,
+}
+
+/// Rule layout stored at schema version 3, kept so `migrate` can read it
+#[derive(Clone)]
+#[contracttype]
+pub struct JurisdictionRuleV3 {
+    pub rule_id: String,
+    pub description: String,
+    pub source_jur: String,
+    pub dest_jur: String,
+    pub host_jur: String,
+    pub operation: OperationType,
+    pub is_allowed: bool,
+    pub required_authority: Option<Address>,
+    pub expires_at: Option<u64>,
+    pub project_id: Option<String>,
+}
+
+/// Rule layout stored at schema version 2, kept so `migrate` can read it
+#[derive(Clone)]
+#[contracttype]
+pub struct JurisdictionRuleV2 {
+    pub rule_id: String,
+    pub description: String,
+    pub source_jur: String,
+    pub dest_jur: String,
+    pub host_jur: String,
+    pub operation: OperationType,
+    pub is_allowed: bool,
+    pub required_authority: Option<Address>,
+    pub expires_at: Option<u64>,
+}
+
+/// Rule layout stored before schema version 2, kept so `migrate` can read it
+#[derive(Clone)]
+#[contracttype]
+pub struct JurisdictionRuleV1 {
+    pub rule_id: String,
+    pub description: String,
+    pub source_jur: String,
+    pub dest_jur: String,
+    pub host_jur: String,
+    pub operation: OperationType,
+    pub is_allowed: bool,
+    pub required_authority: Option<Address>,
+}
+
+/// Reusable sign-off from an authority for one source, destination and operation
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StandingAuthorization {
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// Outcome of a validation kept by `validate_and_record` for later disputes
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ValidationRecord {
+    /// Rule that decided the validation, if any matched
     pub rule_id: Option<String>,
-    pub requires_authorization: bool,
-    pub authority_address: Option<Address>,
-    pub error_message: Option<String>,
+    pub is_compliant: bool,
+    pub recorded_at: u64,
+}
+
+/// Result of `validate_transaction_with_memo`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MemoValidation {
+    pub result: ValidationResult,
+    /// Key the memo was kept under, for `get_validation_memo_by_key`. `None`
+    /// when the transaction was not compliant and nothing was kept.
+    pub memo_key: Option<BytesN<32>>,
+}
+
+/// Cap on compliant transfers from one jurisdiction to another per window
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PairRateLimit {
+    pub max_count: u32,
+    pub window_seconds: u64,
+}
+
+/// Which parties must be exempt for `validate_transaction` to skip the rules
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ExemptionMode {
+    /// Both source and destination are exempt
+    Both,
+    /// Either party is exempt
+    Either,
 }
 
 #[derive(Clone)]
@@ -41,7 +303,11 @@ pub struct PendingApproval {
     pub destination: Address,
     pub operation: OperationType,
     pub timestamp: u64,
+    /// Ledger sequence the approval was created or last renewed in
+    pub created_ledger: u32,
     pub approved: bool,
+    /// Rule the approval was staged under by `validate_and_stage`
+    pub rule_id: Option<String>,
 }
 
 #[derive(Clone)]
@@ -50,22 +316,86 @@ pub enum DataKey {
     Admin,
     Governance,
     CarbonAssetContract,
+    RegistryContract,
     Rule(String),
     ActiveRuleIds,
     AddressJurisdiction(Address),
     PendingApproval(BytesN<32>),
+    SchemaVersion,
+    Paused,
+    StandingAuthorization(Address, Address, Address, OperationType),
+    MaxApprovalLedgerAge,
+    ArchivedRule(String),
+    PairLimit(String, String),
+    /// Window start and number of transfers counted in it
+    PairCount(String, String),
+    ApprovalGracePeriod,
+    MaxRules,
+    DefaultAuthority,
+    ArchivedRuleIds,
+    ValidationRecord(BytesN<32>),
+    JurisdictionRisk(String),
+    MaxAllowedRisk,
+    /// Timestamp of the last authorization recorded under a rule
+    LastRuleApproval(String),
+    /// Number of validations the rule has decided
+    RuleHits(String),
+    ValidationMemo(BytesN<32>),
+    /// Keys of every pending approval staged, in staging order
+    PendingApprovalKeys,
+    OperationWindow(OperationType),
+    /// System account that bypasses rule checks
+    ExemptAddress(Address),
+    ExemptionMode,
+    /// Address allowed to pause and resume through `set_paused_by_coordinator`
+    Coordinator,
+    /// Consult registry KYC attestations for rules requiring them
+    AttestationCheck,
+    /// Address allowed to be a transfer source under the source allowlist
+    AllowedSource(Address),
+    EnforceSourceAllowlist,
+    /// IDs of active rules in a category
+    CategoryRules(String),
+    /// Number of addresses assigned to a jurisdiction
+    JurisdictionMembers(String),
+    /// Number of jurisdictions with at least one assigned address
+    JurisdictionCount,
 }
 
-#[derive(Clone, Copy)]
+/// Storage schema version written by this code. Contracts without a stored
+/// `DataKey::SchemaVersion` predate versioning and hold version 1 rules.
+pub const CURRENT_SCHEMA_VERSION: u32 = 8;
+
+/// Active rules allowed until governance sets a different limit. Every
+/// validation scans the active set, so it is kept bounded.
+pub const DEFAULT_MAX_RULES: u32 = 1000;
+
+/// Seconds a pending approval stays usable when its operation has no
+/// governance-set window
+pub const DEFAULT_APPROVAL_WINDOW: u64 = 604800;
+
+/// Codes occupy the regulatory range, see `shared_types::REGULATORY_ERROR_BASE`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[contracterror]
 pub enum ContractError {
-    NotAuthorized = 1,
-    RuleNotFound = 2,
-    RuleAlreadyExists = 3,
-    JurisdictionNotSet = 4,
-    InvalidApprovalKey = 5,
-    ApprovalExpired = 6,
-    NoMatchingRule = 7,
+    NotAuthorized = 201,
+    RuleNotFound = 202,
+    RuleAlreadyExists = 203,
+    JurisdictionNotSet = 204,
+    InvalidApprovalKey = 205,
+    ApprovalExpired = 206,
+    NoMatchingRule = 207,
+    InvalidSchemaVersion = 208,
+    Paused = 209,
+    ProjectNotVerified = 210,
+    InvalidExpiry = 211,
+    AuthorizationNotFound = 212,
+    InvalidRateLimit = 213,
+    RuleLimitReached = 214,
+    AuthorizationCooldown = 215,
+    NotTokenOwner = 216,
+    PairLimitExceeded = 217,
+    MemoAlreadyExists = 218,
 }
 
 #[contract]
@@ -95,6 +425,9 @@ impl RegulatoryCheck {
         env.storage()
             .instance()
             .set(&DataKey::ActiveRuleIds, &active_rules);
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &CURRENT_SCHEMA_VERSION);
     }
 
     // ========================================================================
@@ -107,13 +440,7 @@ impl RegulatoryCheck {
         caller: Address,
         rule: JurisdictionRule,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
-
-        let governance: Address = env.storage().instance().get(&DataKey::Governance).unwrap();
-
-        if caller != governance {
-            return Err(ContractError::NotAuthorized);
-        }
+        Self::require_governance(&env, &caller)?;
 
         let rule_key = DataKey::Rule(rule.rule_id.clone());
 
@@ -122,19 +449,26 @@ impl RegulatoryCheck {
             return Err(ContractError::RuleAlreadyExists);
         }
 
-        // Store the rule
-        env.storage().persistent().set(&rule_key, &rule);
+        Self::require_verified_project(&env, &rule)?;
 
-        // Add to active rules list
         let mut active_rules: Vec<String> = env
             .storage()
             .instance()
             .get(&DataKey::ActiveRuleIds)
             .unwrap_or(Vec::new(&env));
+        if active_rules.len() >= Self::get_rule_limit(env.clone()) {
+            return Err(ContractError::RuleLimitReached);
+        }
+
+        // Store the rule
+        env.storage().persistent().set(&rule_key, &rule);
+
+        // Add to active rules list
         active_rules.push_back(rule.rule_id.clone());
         env.storage()
             .instance()
             .set(&DataKey::ActiveRuleIds, &active_rules);
+        Self::index_rule_category(&env, &rule);
 
         Ok(())
     }
@@ -145,63 +479,235 @@ impl RegulatoryCheck {
         caller: Address,
         rule: JurisdictionRule,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
+        Self::require_governance(&env, &caller)?;
+
+        let rule_key = DataKey::Rule(rule.rule_id.clone());
 
-        let governance: Address = env.storage().instance().get(&DataKey::Governance).unwrap();
+        let old: JurisdictionRule = env
+            .storage()
+            .persistent()
+            .get(&rule_key)
+            .ok_or(ContractError::RuleNotFound)?;
 
-        if caller != governance {
-            return Err(ContractError::NotAuthorized);
+        Self::require_verified_project(&env, &rule)?;
+
+        env.storage().persistent().set(&rule_key, &rule);
+        if old.category != rule.category {
+            Self::unindex_rule_category(&env, &old);
+            Self::index_rule_category(&env, &rule);
         }
 
-        let rule_key = DataKey::Rule(rule.rule_id.clone());
+        Ok(())
+    }
+
+    /// Move an active rule into `new_category`, updating both category
+    /// indexes (governance only)
+    pub fn recategorize_rule(
+        env: Env,
+        caller: Address,
+        rule_id: String,
+        new_category: String,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        let rule_key = DataKey::Rule(rule_id);
+        let mut rule: JurisdictionRule = env
+            .storage()
+            .persistent()
+            .get(&rule_key)
+            .ok_or(ContractError::RuleNotFound)?;
 
-        if !env.storage().persistent().has(&rule_key) {
-            return Err(ContractError::RuleNotFound);
+        if rule.category == Some(new_category.clone()) {
+            return Ok(());
         }
 
+        Self::unindex_rule_category(&env, &rule);
+        rule.category = Some(new_category);
         env.storage().persistent().set(&rule_key, &rule);
+        Self::index_rule_category(&env, &rule);
+
+        Ok(())
+    }
+
+    /// Get the IDs of active rules in a category
+    pub fn get_rules_by_category(env: Env, category: String) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CategoryRules(category))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Cap the number of active rules `add_rule` and `reactivate_rule` may
+    /// grow the set to (governance only). Lowering it below the current count
+    /// leaves existing rules in place but blocks new ones.
+    pub fn set_rule_limit(env: Env, caller: Address, max_rules: u32) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage().instance().set(&DataKey::MaxRules, &max_rules);
+        Ok(())
+    }
+
+    /// Get the active rule limit, `DEFAULT_MAX_RULES` unless governance changed it
+    pub fn get_rule_limit(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxRules)
+            .unwrap_or(DEFAULT_MAX_RULES)
+    }
+
+    /// Set the authority that rules with `use_default_authority` require
+    /// (governance only). `None` leaves those rules without an authority.
+    pub fn set_default_authority(
+        env: Env,
+        caller: Address,
+        authority: Option<Address>,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
 
+        match authority {
+            Some(authority) => env
+                .storage()
+                .instance()
+                .set(&DataKey::DefaultAuthority, &authority),
+            None => env.storage().instance().remove(&DataKey::DefaultAuthority),
+        }
         Ok(())
     }
 
-    /// Deactivate a rule
+    /// Get the default authority, if any
+    pub fn get_default_authority(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::DefaultAuthority)
+    }
+
+    /// Permanently delete a rule. Use `archive_rule` to keep it for later
+    /// inspection or reactivation.
     pub fn deactivate_rule(
         env: Env,
         caller: Address,
         rule_id: String,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
+        Self::require_governance(&env, &caller)?;
 
-        let governance: Address = env.storage().instance().get(&DataKey::Governance).unwrap();
+        let rule_key = DataKey::Rule(rule_id.clone());
 
-        if caller != governance {
-            return Err(ContractError::NotAuthorized);
+        let rule: JurisdictionRule = env
+            .storage()
+            .persistent()
+            .get(&rule_key)
+            .ok_or(ContractError::RuleNotFound)?;
+
+        env.storage().persistent().remove(&rule_key);
+        Self::remove_active_rule_id(&env, &rule_id);
+        Self::unindex_rule_category(&env, &rule);
+
+        Ok(())
+    }
+
+    /// Delete every active rule listed by `get_expired_rules` (governance
+    /// only). Returns the number of rules removed.
+    pub fn prune_expired_rules(env: Env, caller: Address) -> Result<u32, ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        let expired = Self::get_expired_rules(env.clone());
+        for rule_id in expired.iter() {
+            let rule_key = DataKey::Rule(rule_id.clone());
+            if let Some(rule) = env.storage().persistent().get(&rule_key) {
+                Self::unindex_rule_category(&env, &rule);
+            }
+            env.storage().persistent().remove(&rule_key);
+            Self::remove_active_rule_id(&env, &rule_id);
         }
 
+        Ok(expired.len())
+    }
+
+    /// Move a rule out of validation into the archive, where it can be
+    /// inspected with `get_archived_rule` or restored with `reactivate_rule`
+    pub fn archive_rule(env: Env, caller: Address, rule_id: String) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
         let rule_key = DataKey::Rule(rule_id.clone());
+        let rule: JurisdictionRule = env
+            .storage()
+            .persistent()
+            .get(&rule_key)
+            .ok_or(ContractError::RuleNotFound)?;
 
-        if !env.storage().persistent().has(&rule_key) {
-            return Err(ContractError::RuleNotFound);
+        let archive_key = DataKey::ArchivedRule(rule_id.clone());
+        if env.storage().persistent().has(&archive_key) {
+            return Err(ContractError::RuleAlreadyExists);
         }
 
+        env.storage().persistent().set(&archive_key, &rule);
         env.storage().persistent().remove(&rule_key);
+        Self::remove_active_rule_id(&env, &rule_id);
+        Self::unindex_rule_category(&env, &rule);
 
-        let active_rules: Vec<String> = env
+        let mut archived: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ArchivedRuleIds)
+            .unwrap_or(Vec::new(&env));
+        archived.push_back(rule_id);
+        env.storage()
+            .instance()
+            .set(&DataKey::ArchivedRuleIds, &archived);
+
+        Ok(())
+    }
+
+    /// Restore an archived rule to the active set
+    pub fn reactivate_rule(
+        env: Env,
+        caller: Address,
+        rule_id: String,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        let archive_key = DataKey::ArchivedRule(rule_id.clone());
+        let rule: JurisdictionRule = env
+            .storage()
+            .persistent()
+            .get(&archive_key)
+            .ok_or(ContractError::RuleNotFound)?;
+
+        // A rule added under the same id since archiving takes precedence
+        let rule_key = DataKey::Rule(rule_id.clone());
+        if env.storage().persistent().has(&rule_key) {
+            return Err(ContractError::RuleAlreadyExists);
+        }
+
+        Self::require_verified_project(&env, &rule)?;
+
+        let mut active_rules: Vec<String> = env
             .storage()
             .instance()
             .get(&DataKey::ActiveRuleIds)
-            .unwrap();
+            .unwrap_or(Vec::new(&env));
+        if active_rules.len() >= Self::get_rule_limit(env.clone()) {
+            return Err(ContractError::RuleLimitReached);
+        }
 
-        let mut new_rules = Vec::new(&env);
-        for i in 0..active_rules.len() {
-            let rid = active_rules.get(i).unwrap();
-            if rid != rule_id {
-                new_rules.push_back(rid);
-            }
+        env.storage().persistent().set(&rule_key, &rule);
+        env.storage().persistent().remove(&archive_key);
+
+        let mut archived: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ArchivedRuleIds)
+            .unwrap_or(Vec::new(&env));
+        if let Some(index) = archived.first_index_of(&rule_id) {
+            archived.remove(index);
+            env.storage()
+                .instance()
+                .set(&DataKey::ArchivedRuleIds, &archived);
         }
+
+        active_rules.push_back(rule_id);
         env.storage()
             .instance()
-            .set(&DataKey::ActiveRuleIds, &new_rules);
+            .set(&DataKey::ActiveRuleIds, &active_rules);
+        Self::index_rule_category(&env, &rule);
 
         Ok(())
     }
@@ -217,16 +723,25 @@ impl RegulatoryCheck {
         account: Address,
         jurisdiction: String,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let key = DataKey::AddressJurisdiction(account.clone());
+        let old_jurisdiction: Option<String> = env.storage().persistent().get(&key);
+        env.storage().persistent().set(&key, &jurisdiction);
 
-        if caller != admin {
-            return Err(ContractError::NotAuthorized);
+        if old_jurisdiction.as_ref() != Some(&jurisdiction) {
+            if let Some(old) = old_jurisdiction.clone() {
+                Self::adjust_jurisdiction_members(&env, old, false);
+            }
+            Self::adjust_jurisdiction_members(&env, jurisdiction.clone(), true);
         }
 
-        let key = DataKey::AddressJurisdiction(account);
-        env.storage().persistent().set(&key, &jurisdiction);
+        JurisdictionChanged {
+            account,
+            old_jurisdiction,
+            new_jurisdiction: jurisdiction,
+        }
+        .publish(&env);
 
         Ok(())
     }
@@ -237,18 +752,510 @@ impl RegulatoryCheck {
         env.storage().persistent().get(&key)
     }
 
+    /// Number of distinct jurisdictions with at least one assigned address
+    pub fn get_jurisdiction_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::JurisdictionCount)
+            .unwrap_or(0)
+    }
+
+    /// Set the AML risk rating of a jurisdiction (governance only)
+    pub fn set_jurisdiction_risk(
+        env: Env,
+        caller: Address,
+        jurisdiction: String,
+        risk: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::JurisdictionRisk(jurisdiction), &risk);
+        Ok(())
+    }
+
+    /// Get the risk rating of a jurisdiction, 0 when unrated
+    pub fn get_jurisdiction_risk(env: Env, jurisdiction: String) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::JurisdictionRisk(jurisdiction))
+            .unwrap_or(0)
+    }
+
+    /// Deny every validation where either party's jurisdiction is rated above
+    /// `max_risk`, before any rule is consulted (governance only). `None`
+    /// turns the check off.
+    pub fn set_max_allowed_risk(
+        env: Env,
+        caller: Address,
+        max_risk: Option<u32>,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        match max_risk {
+            Some(max_risk) => env
+                .storage()
+                .instance()
+                .set(&DataKey::MaxAllowedRisk, &max_risk),
+            None => env.storage().instance().remove(&DataKey::MaxAllowedRisk),
+        }
+        Ok(())
+    }
+
+    /// Get the highest jurisdiction risk validation allows, if a limit is set
+    pub fn get_max_allowed_risk(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::MaxAllowedRisk)
+    }
+
+    /// Get jurisdictions for several addresses, in the same order as `accounts`
+    pub fn get_address_jurisdictions(env: Env, accounts: Vec<Address>) -> Vec<Option<String>> {
+        let mut jurisdictions = Vec::new(&env);
+        for account in accounts.iter() {
+            jurisdictions.push_back(Self::get_address_jurisdiction(env.clone(), account));
+        }
+
+        jurisdictions
+    }
+
+    /// Addresses from `accounts` that have no jurisdiction assigned, in the
+    /// order given
+    pub fn find_unassigned(env: Env, accounts: Vec<Address>) -> Vec<Address> {
+        let mut unassigned = Vec::new(&env);
+        for account in accounts.iter() {
+            if Self::get_address_jurisdiction(env.clone(), account.clone()).is_none() {
+                unassigned.push_back(account);
+            }
+        }
+
+        unassigned
+    }
+
+    /// Let a system account, such as the buffer pool or a treasury, bypass
+    /// rule checks (governance only)
+    pub fn add_exempt(env: Env, caller: Address, account: Address) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ExemptAddress(account), &true);
+        Ok(())
+    }
+
+    /// Subject an exempt account to rule checks again (governance only)
+    pub fn remove_exempt(env: Env, caller: Address, account: Address) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ExemptAddress(account));
+        Ok(())
+    }
+
+    /// Check whether an account bypasses rule checks
+    pub fn is_exempt(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::ExemptAddress(account))
+    }
+
+    /// Choose whether both parties or either one must be exempt for a
+    /// validation to skip the rules (governance only)
+    pub fn set_exemption_mode(
+        env: Env,
+        caller: Address,
+        mode: ExemptionMode,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage().instance().set(&DataKey::ExemptionMode, &mode);
+        Ok(())
+    }
+
+    /// Get the exemption mode, `Both` until governance changes it
+    pub fn get_exemption_mode(env: Env) -> ExemptionMode {
+        env.storage()
+            .instance()
+            .get(&DataKey::ExemptionMode)
+            .unwrap_or(ExemptionMode::Both)
+    }
+
+    /// Allow an address to be a transfer source while the source allowlist
+    /// is enforced (admin only)
+    pub fn add_allowed_source(
+        env: Env,
+        caller: Address,
+        account: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::AllowedSource(account), &true);
+        Ok(())
+    }
+
+    /// Take an address off the source allowlist (admin only)
+    pub fn remove_allowed_source(
+        env: Env,
+        caller: Address,
+        account: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AllowedSource(account));
+        Ok(())
+    }
+
+    /// Check whether an address is on the source allowlist
+    pub fn is_allowed_source(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::AllowedSource(account))
+    }
+
+    /// Turn enforcement of the source allowlist on or off (governance only).
+    /// While on, only allowlisted addresses can be the source of a transfer.
+    pub fn set_enforce_source_allowlist(
+        env: Env,
+        caller: Address,
+        enforce: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::EnforceSourceAllowlist, &enforce);
+        Ok(())
+    }
+
+    /// Whether the source allowlist is enforced, off until governance sets it
+    pub fn is_source_allowlist_enforced(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::EnforceSourceAllowlist)
+            .unwrap_or(false)
+    }
+
+    /// Turn the registry KYC attestation check for rules with
+    /// `requires_attestation` on or off (governance only)
+    pub fn set_attestation_check(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AttestationCheck, &enabled);
+        Ok(())
+    }
+
+    /// Whether rules with `requires_attestation` consult the registry, off
+    /// until governance enables it
+    pub fn is_attestation_check_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::AttestationCheck)
+            .unwrap_or(false)
+    }
+
     // ========================================================================
     // Compliance Validation
     // ========================================================================
 
-    /// Primary validation function called by CarbonAsset contract
+    /// Primary validation function called by CarbonAsset contract.
+    /// When `correlation_id` is given, a `validated` event carrying it as a topic
+    /// is emitted so indexers can link the check to the rest of the flow.
+    /// Transactions between exempt addresses, per the exemption mode, are
+    /// compliant without consulting any rule.
     pub fn validate_transaction(
         env: Env,
         source_address: Address,
         destination_address: Address,
         operation: OperationType,
         host_jurisdiction: String,
+        correlation_id: Option<BytesN<32>>,
+    ) -> ValidationResult {
+        Self::validate(
+            env,
+            source_address,
+            destination_address,
+            operation,
+            host_jurisdiction,
+            None,
+            correlation_id,
+        )
+    }
+
+    /// Run `validate_transaction` for a transaction of `amount` credits. A
+    /// matching rule with an `authority_min_amount` above `amount` resolves
+    /// compliant without requiring its authority.
+    pub fn validate_transaction_with_amount(
+        env: Env,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        host_jurisdiction: String,
+        amount: i128,
     ) -> ValidationResult {
+        Self::validate(
+            env,
+            source_address,
+            destination_address,
+            operation,
+            host_jurisdiction,
+            Some(amount),
+            None,
+        )
+    }
+
+    fn validate(
+        env: Env,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        host_jurisdiction: String,
+        amount: Option<i128>,
+        correlation_id: Option<BytesN<32>>,
+    ) -> ValidationResult {
+        let result = Self::evaluate_transaction(
+            env.clone(),
+            source_address.clone(),
+            destination_address.clone(),
+            operation,
+            host_jurisdiction,
+            None,
+            amount,
+        );
+
+        let result = Self::apply_pair_limit(&env, result, &source_address, &destination_address);
+
+        if let Some(correlation_id) = correlation_id {
+            TransactionValidated {
+                correlation_id,
+                source: source_address,
+                destination: destination_address,
+                is_compliant: result.is_compliant,
+                rule_id: result.rule_id.clone(),
+            }
+            .publish(&env);
+        }
+
+        result
+    }
+
+    /// Run `validate_transaction` and keep the deciding rule and outcome under
+    /// `record_key` for `get_validation_record`. Only the source or the
+    /// carbon asset contract may record, so nobody else can claim a key
+    /// first. Records are never overwritten: reusing a key returns a
+    /// non-compliant result instead.
+    pub fn validate_and_record(
+        env: Env,
+        caller: Address,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        host_jurisdiction: String,
+        record_key: BytesN<32>,
+    ) -> Result<ValidationResult, ContractError> {
+        caller.require_auth();
+
+        if caller != source_address
+            && !Self::is_stored_address(&env, &DataKey::CarbonAssetContract, &caller)
+        {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let key = DataKey::ValidationRecord(record_key);
+        if env.storage().persistent().has(&key) {
+            return Ok(Self::non_compliant(
+                &env,
+                "Validation record already exists",
+            ));
+        }
+
+        let result = Self::validate_transaction(
+            env.clone(),
+            source_address,
+            destination_address,
+            operation,
+            host_jurisdiction,
+            None,
+        );
+
+        env.storage().persistent().set(
+            &key,
+            &ValidationRecord {
+                rule_id: result.rule_id.clone(),
+                is_compliant: result.is_compliant,
+                recorded_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(result)
+    }
+
+    /// Get the validation kept under `record_key`, if any
+    pub fn get_validation_record(env: Env, record_key: BytesN<32>) -> Option<ValidationRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ValidationRecord(record_key))
+    }
+
+    /// Run `validate_transaction` and, when the transaction is compliant, keep
+    /// `memo` (an invoice number or other reference) for audit. The memo is
+    /// keyed by the parties, operation and ledger timestamp, as the approval
+    /// key would be, and read back with `get_validation_memo`. Only the
+    /// source or the carbon asset contract may attach a memo, and a memo
+    /// already kept under the key is never replaced: the call fails with
+    /// `MemoAlreadyExists` instead.
+    pub fn validate_transaction_with_memo(
+        env: Env,
+        caller: Address,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        host_jurisdiction: String,
+        memo: String,
+    ) -> Result<MemoValidation, ContractError> {
+        caller.require_auth();
+
+        if caller != source_address
+            && !Self::is_stored_address(&env, &DataKey::CarbonAssetContract, &caller)
+        {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let memo_key = Self::derive_approval_key(
+            &env,
+            None,
+            &source_address,
+            &destination_address,
+            &operation,
+        );
+        let storage_key = DataKey::ValidationMemo(memo_key.clone());
+        if env.storage().persistent().has(&storage_key) {
+            return Err(ContractError::MemoAlreadyExists);
+        }
+
+        let result = Self::validate_transaction(
+            env.clone(),
+            source_address,
+            destination_address,
+            operation,
+            host_jurisdiction,
+            None,
+        );
+
+        if !result.is_compliant {
+            return Ok(MemoValidation {
+                result,
+                memo_key: None,
+            });
+        }
+
+        env.storage().persistent().set(&storage_key, &memo);
+        Ok(MemoValidation {
+            result,
+            memo_key: Some(memo_key),
+        })
+    }
+
+    /// Get the memo kept under a key returned by `validate_transaction_with_memo`
+    pub fn get_validation_memo_by_key(env: Env, memo_key: BytesN<32>) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ValidationMemo(memo_key))
+    }
+
+    /// Get the memo kept by `validate_transaction_with_memo` for a transaction
+    /// validated at ledger timestamp `validated_at`
+    pub fn get_validation_memo(
+        env: Env,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        validated_at: u64,
+    ) -> Option<String> {
+        let memo_key = Self::transaction_key(
+            &env,
+            None,
+            &source_address,
+            &destination_address,
+            &operation,
+            validated_at,
+        );
+        env.storage()
+            .persistent()
+            .get(&DataKey::ValidationMemo(memo_key))
+    }
+
+    /// Run `validate_transaction` from `source_address` to each of
+    /// `destinations`, returning one result per destination in the same order
+    pub fn validate_fanout(
+        env: Env,
+        source_address: Address,
+        destinations: Vec<Address>,
+        operation: OperationType,
+        host_jurisdiction: String,
+    ) -> Vec<ValidationResult> {
+        let mut results = Vec::new(&env);
+        for destination in destinations.iter() {
+            results.push_back(Self::validate_transaction(
+                env.clone(),
+                source_address.clone(),
+                destination,
+                operation.clone(),
+                host_jurisdiction.clone(),
+                None,
+            ));
+        }
+
+        results
+    }
+
+    fn evaluate_transaction(
+        env: Env,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        host_jurisdiction: String,
+        token_id: Option<u32>,
+        amount: Option<i128>,
+    ) -> ValidationResult {
+        if Self::is_paused(env.clone()) {
+            return Self::non_compliant(&env, "Contract paused");
+        }
+
+        // The allowlist restricts transfer sources even for exempt accounts
+        if operation == OperationType::TRANSFER
+            && Self::is_source_allowlist_enforced(env.clone())
+            && !Self::is_allowed_source(env.clone(), source_address.clone())
+        {
+            return Self::non_compliant(&env, "Source not allowlisted");
+        }
+
+        let source_exempt = Self::is_exempt(env.clone(), source_address.clone());
+        let dest_exempt = Self::is_exempt(env.clone(), destination_address.clone());
+        let exempt = match Self::get_exemption_mode(env.clone()) {
+            ExemptionMode::Both => source_exempt && dest_exempt,
+            ExemptionMode::Either => source_exempt || dest_exempt,
+        };
+        if exempt {
+            return ValidationResult {
+                is_compliant: true,
+                rule_id: None,
+                requires_authorization: false,
+                authority_address: None,
+                error_message: None,
+                approval_key: None,
+            };
+        }
+
         let source_jur = Self::get_address_jurisdiction(env.clone(), source_address.clone());
 
         let dest_jur = Self::get_address_jurisdiction(env.clone(), destination_address.clone());
@@ -260,12 +1267,21 @@ impl RegulatoryCheck {
                 requires_authorization: false,
                 authority_address: None,
                 error_message: Some(String::from_str(&env, "Jurisdiction not set for address")),
+                approval_key: None,
             };
         }
 
         let source_jur = source_jur.unwrap();
         let dest_jur = dest_jur.unwrap();
 
+        if let Some(max_risk) = Self::get_max_allowed_risk(env.clone()) {
+            if Self::get_jurisdiction_risk(env.clone(), source_jur.clone()) > max_risk
+                || Self::get_jurisdiction_risk(env.clone(), dest_jur.clone()) > max_risk
+            {
+                return Self::non_compliant(&env, "Jurisdiction risk too high");
+            }
+        }
+
         // Get active rules
         let active_rules: Vec<String> = env
             .storage()
@@ -292,8 +1308,53 @@ impl RegulatoryCheck {
                     &operation,
                 ) {
                     // Rule matched
+                    Self::record_rule_hit(&env, &rule);
+
+                    if rule.is_allowed
+                        && rule.requires_attestation
+                        && Self::is_attestation_check_enabled(env.clone())
+                        && !Self::parties_attested(&env, &source_address, &destination_address)
+                    {
+                        return ValidationResult {
+                            is_compliant: false,
+                            rule_id: Some(rule.rule_id.clone()),
+                            requires_authorization: false,
+                            authority_address: None,
+                            error_message: Some(String::from_str(
+                                &env,
+                                "Address lacks KYC attestation",
+                            )),
+                            approval_key: None,
+                        };
+                    }
+
+                    let below_threshold = matches!(
+                        (amount, rule.authority_min_amount),
+                        (Some(amount), Some(min_amount)) if amount < min_amount
+                    );
+
                     if rule.is_allowed {
-                        if let Some(authority) = rule.required_authority.clone() {
+                        let authority =
+                            Self::effective_authority(&env, &rule).filter(|_| !below_threshold);
+                        if let Some(authority) = authority {
+                            // A standing authorization covers this corridor
+                            if Self::has_standing_authorization(
+                                &env,
+                                &authority,
+                                &source_address,
+                                &destination_address,
+                                &operation,
+                            ) {
+                                return ValidationResult {
+                                    is_compliant: true,
+                                    rule_id: Some(rule.rule_id.clone()),
+                                    requires_authorization: false,
+                                    authority_address: Some(authority),
+                                    error_message: None,
+                                    approval_key: None,
+                                };
+                            }
+
                             // Requires authorization
                             return ValidationResult {
                                 is_compliant: true,
@@ -301,15 +1362,24 @@ impl RegulatoryCheck {
                                 requires_authorization: true,
                                 authority_address: Some(authority),
                                 error_message: None,
+                                approval_key: Some(Self::derive_approval_key(
+                                    &env,
+                                    token_id,
+                                    &source_address,
+                                    &destination_address,
+                                    &operation,
+                                )),
                             };
                         } else {
-                            // Allowed without authorization
+                            // Allowed without authorization, or below the
+                            // rule's authority threshold
                             return ValidationResult {
                                 is_compliant: true,
                                 rule_id: Some(rule.rule_id.clone()),
                                 requires_authorization: false,
                                 authority_address: None,
                                 error_message: None,
+                                approval_key: None,
                             };
                         }
                     } else {
@@ -323,6 +1393,7 @@ impl RegulatoryCheck {
                                 &env,
                                 "Transaction prohibited by rule",
                             )),
+                            approval_key: None,
                         };
                     }
                 }
@@ -336,37 +1407,386 @@ impl RegulatoryCheck {
             requires_authorization: false,
             authority_address: None,
             error_message: Some(String::from_str(&env, "No matching rule found")),
+            approval_key: None,
         }
     }
 
-    // ========================================================================
-    // Authority Approval
-    // ========================================================================
-
-    /// Record authorization from required authority
-    pub fn record_authorization(
+    /// Validate a token transfer, additionally confirming with the CarbonAsset
+    /// contract that `source_address` holds `token_id`. The CarbonAsset contract
+    /// itself keeps calling `validate_transaction`, since a call back into it
+    /// from here would be re-entrant.
+    pub fn validate_token_transfer(
         env: Env,
-        authority: Address,
-        approval_key: BytesN<32>,
-    ) -> Result<(), ContractError> {
-        authority.require_auth();
-
-        let key = DataKey::PendingApproval(approval_key.clone());
-
-        let mut pending: PendingApproval = env
-            .storage()
+        source_address: Address,
+        destination_address: Address,
+        token_id: u32,
+        operation: OperationType,
+        host_jurisdiction: String,
+    ) -> ValidationResult {
+        let result = Self::evaluate_transaction(
+            env.clone(),
+            source_address.clone(),
+            destination_address.clone(),
+            operation,
+            host_jurisdiction,
+            Some(token_id),
+            None,
+        );
+        let result = Self::apply_pair_limit(&env, result, &source_address, &destination_address);
+
+        if !result.is_compliant {
+            return result;
+        }
+
+        if Self::holds_token(&env, &source_address, token_id) {
+            result
+        } else {
+            ValidationResult {
+                is_compliant: false,
+                rule_id: result.rule_id,
+                requires_authorization: false,
+                authority_address: None,
+                error_message: Some(String::from_str(&env, "Source does not hold token")),
+                approval_key: None,
+            }
+        }
+    }
+
+    /// Ask the carbon asset contract whether `owner` holds `token_id`. Unknown
+    /// tokens and failed calls count as not held.
+    fn holds_token(env: &Env, owner: &Address, token_id: u32) -> bool {
+        let carbon_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)
+            .unwrap();
+        let carbon = CarbonAssetClient::new(env, &carbon_contract);
+
+        matches!(carbon.try_owner_of(&token_id), Ok(Ok(holder)) if holder == *owner)
+    }
+
+    /// Validate a token transaction using the jurisdiction of the token's
+    /// project, as recorded in the registry, as the host jurisdiction
+    pub fn validate_transaction_for_token(
+        env: Env,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        token_id: u32,
+    ) -> ValidationResult {
+        let registry: Option<Address> = env.storage().instance().get(&DataKey::RegistryContract);
+
+        let Some(registry) = registry else {
+            return Self::non_compliant(&env, "Registry contract not set");
+        };
+        let registry = RegistryClient::new(&env, &registry);
+
+        let Ok(Ok(project_id)) = registry.try_get_token_project(&token_id) else {
+            return Self::non_compliant(&env, "Token not linked to a project");
+        };
+
+        let Ok(Ok(host_jurisdiction)) = registry.try_get_project_jurisdiction(&project_id) else {
+            return Self::non_compliant(&env, "Jurisdiction not set for project");
+        };
+
+        let result = Self::evaluate_transaction(
+            env.clone(),
+            source_address.clone(),
+            destination_address.clone(),
+            operation,
+            host_jurisdiction,
+            Some(token_id),
+            None,
+        );
+        Self::apply_pair_limit(&env, result, &source_address, &destination_address)
+    }
+
+    /// Validate a transaction and, when the matching rule requires an
+    /// authority, stage the pending approval in the same call. The derived
+    /// key is returned in `approval_key` for the authority to approve.
+    /// Nothing is staged for a source that does not hold the token.
+    pub fn validate_and_stage(
+        env: Env,
+        source_address: Address,
+        destination_address: Address,
+        token_id: u32,
+        operation: OperationType,
+        host_jurisdiction: String,
+    ) -> Result<ValidationResult, ContractError> {
+        let result = Self::evaluate_transaction(
+            env.clone(),
+            source_address.clone(),
+            destination_address.clone(),
+            operation.clone(),
+            host_jurisdiction,
+            Some(token_id),
+            None,
+        );
+        let result = Self::apply_pair_limit(&env, result, &source_address, &destination_address);
+
+        if let (true, Some(approval_key)) = (result.is_compliant, result.approval_key.clone()) {
+            if !Self::holds_token(&env, &source_address, token_id) {
+                return Err(ContractError::NotTokenOwner);
+            }
+
+            Self::stage_pending_approval(
+                &env,
+                approval_key,
+                token_id,
+                source_address,
+                destination_address,
+                operation,
+                result.rule_id.clone(),
+            );
+        }
+
+        Ok(result)
+    }
+
+    // ========================================================================
+    // Rate Limiting
+    // ========================================================================
+
+    /// Cap the transfers from `source_jur` to `dest_jur` counted by
+    /// `record_pair_transfer` per window (governance only). Once the window
+    /// is full, every validation of the pair is non-compliant. `None`
+    /// removes the limit and its counter.
+    pub fn set_pair_rate_limit(
+        env: Env,
+        caller: Address,
+        source_jur: String,
+        dest_jur: String,
+        limit: Option<PairRateLimit>,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        let limit_key = DataKey::PairLimit(source_jur.clone(), dest_jur.clone());
+        match limit {
+            Some(limit) => {
+                if limit.max_count == 0 || limit.window_seconds == 0 {
+                    return Err(ContractError::InvalidRateLimit);
+                }
+                env.storage().persistent().set(&limit_key, &limit);
+            }
+            None => {
+                env.storage().persistent().remove(&limit_key);
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::PairCount(source_jur, dest_jur));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the rate limit for a jurisdiction pair, if any
+    pub fn get_pair_rate_limit(
+        env: Env,
+        source_jur: String,
+        dest_jur: String,
+    ) -> Option<PairRateLimit> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PairLimit(source_jur, dest_jur))
+    }
+
+    /// Number of transfers counted for a jurisdiction pair in the current window
+    pub fn get_pair_count(env: Env, source_jur: String, dest_jur: String) -> u32 {
+        let Some(limit) =
+            Self::get_pair_rate_limit(env.clone(), source_jur.clone(), dest_jur.clone())
+        else {
+            return 0;
+        };
+        let (window_start, count): (u64, u32) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PairCount(source_jur, dest_jur))
+            .unwrap_or((0, 0));
+
+        if env.ledger().timestamp() >= window_start.saturating_add(limit.window_seconds) {
+            0
+        } else {
+            count
+        }
+    }
+
+    /// Count one transfer from `source` to `destination` against their
+    /// jurisdiction pair's rate limit (carbon asset contract only), once it
+    /// has validated and is executing the transfer. Validation only checks
+    /// the limit, so unauthenticated calls cannot use up a corridor's
+    /// allowance. Fails with `PairLimitExceeded` when the window is full.
+    pub fn record_pair_transfer(
+        env: Env,
+        caller: Address,
+        source: Address,
+        destination: Address,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let carbon_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::CarbonAssetContract);
+        if carbon_contract.as_ref() != Some(&caller) {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let Some((count_key, limit, window_start, count)) =
+            Self::pair_window(&env, &source, &destination)
+        else {
+            return Ok(());
+        };
+
+        if count >= limit.max_count {
+            return Err(ContractError::PairLimitExceeded);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&count_key, &(window_start, count + 1));
+        Ok(())
+    }
+
+    // ========================================================================
+    // Authority Approval
+    // ========================================================================
+
+    /// Pre-authorize every `operation` from `source` to `destination` until
+    /// `expires_at`. While it holds, validation treats rules requiring this
+    /// authority as satisfied without a pending approval. Replaces any
+    /// existing standing authorization for the same corridor.
+    pub fn create_standing_authorization(
+        env: Env,
+        authority: Address,
+        source: Address,
+        destination: Address,
+        operation: OperationType,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        authority.require_auth();
+
+        if Self::is_paused(env.clone()) {
+            return Err(ContractError::Paused);
+        }
+
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            return Err(ContractError::InvalidExpiry);
+        }
+
+        let key = DataKey::StandingAuthorization(authority, source, destination, operation);
+        let standing = StandingAuthorization {
+            created_at: now,
+            expires_at,
+        };
+        env.storage().persistent().set(&key, &standing);
+
+        Ok(())
+    }
+
+    /// Withdraw a standing authorization (the granting authority only)
+    pub fn revoke_standing_authorization(
+        env: Env,
+        authority: Address,
+        source: Address,
+        destination: Address,
+        operation: OperationType,
+    ) -> Result<(), ContractError> {
+        authority.require_auth();
+
+        let key = DataKey::StandingAuthorization(authority, source, destination, operation);
+        if !env.storage().persistent().has(&key) {
+            return Err(ContractError::AuthorizationNotFound);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    /// Get the standing authorization for a corridor, expired or not
+    pub fn get_standing_authorization(
+        env: Env,
+        authority: Address,
+        source: Address,
+        destination: Address,
+        operation: OperationType,
+    ) -> Option<StandingAuthorization> {
+        let key = DataKey::StandingAuthorization(authority, source, destination, operation);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Record authorization from required authority
+    pub fn record_authorization(
+        env: Env,
+        authority: Address,
+        approval_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        authority.require_auth();
+
+        if Self::is_paused(env.clone()) {
+            return Err(ContractError::Paused);
+        }
+
+        Self::approve(&env, authority, approval_key)
+    }
+
+    /// Record `authority`'s approval for each of `keys` with a single auth,
+    /// returning whether each one succeeded, in order. Keys that are unknown,
+    /// expired, throttled by their rule's cooldown, or staged under a rule
+    /// whose authority is someone else are skipped. Keys staged without a
+    /// rule have no authority to match and are skipped as well.
+    pub fn batch_record_authorization(
+        env: Env,
+        authority: Address,
+        keys: Vec<BytesN<32>>,
+    ) -> Result<Vec<bool>, ContractError> {
+        authority.require_auth();
+
+        if Self::is_paused(env.clone()) {
+            return Err(ContractError::Paused);
+        }
+
+        let mut recorded = Vec::new(&env);
+        for approval_key in keys.iter() {
+            let matches = Self::get_pending_approval(env.clone(), approval_key.clone())
+                .and_then(|pending| pending.rule_id)
+                .and_then(|rule_id| Self::get_rule(env.clone(), rule_id))
+                .and_then(|rule| Self::effective_authority(&env, &rule))
+                == Some(authority.clone());
+            recorded
+                .push_back(matches && Self::approve(&env, authority.clone(), approval_key).is_ok());
+        }
+
+        Ok(recorded)
+    }
+
+    /// Restart an approval's window and clear its authorization, so the
+    /// authority has to approve it again. Approvals are created on behalf of
+    /// the transfer source, so only the source or the admin may renew one.
+    pub fn renew_approval(
+        env: Env,
+        caller: Address,
+        approval_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if Self::is_paused(env.clone()) {
+            return Err(ContractError::Paused);
+        }
+
+        let key = DataKey::PendingApproval(approval_key);
+
+        let mut pending: PendingApproval = env
+            .storage()
             .persistent()
             .get(&key)
             .ok_or(ContractError::InvalidApprovalKey)?;
 
-        // Check if expired (7 days = 604800 seconds)
-        let current_time = env.ledger().timestamp();
-        if current_time > pending.timestamp + 604800 {
-            return Err(ContractError::ApprovalExpired);
+        if caller != pending.source && !Self::is_stored_address(&env, &DataKey::Admin, &caller) {
+            return Err(ContractError::NotAuthorized);
         }
 
-        // Mark as approved
-        pending.approved = true;
+        pending.timestamp = env.ledger().timestamp();
+        pending.created_ledger = env.ledger().sequence();
+        pending.approved = false;
         env.storage().persistent().set(&key, &pending);
 
         Ok(())
@@ -381,39 +1801,613 @@ impl RegulatoryCheck {
         destination: Address,
         operation: OperationType,
     ) {
+        Self::stage_pending_approval(
+            &env,
+            approval_key,
+            token_id,
+            source,
+            destination,
+            operation,
+            None,
+        );
+    }
+
+    /// Count a validation decided by `rule` and report the match
+    fn record_rule_hit(env: &Env, rule: &JurisdictionRule) {
+        let key = DataKey::RuleHits(rule.rule_id.clone());
+        let hits: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &hits.saturating_add(1));
+
+        RuleMatched {
+            rule_id: rule.rule_id.clone(),
+            is_allowed: rule.is_allowed,
+        }
+        .publish(env);
+    }
+
+    /// Mark a pending approval as approved, enforcing its window and the
+    /// cooldown of the rule it was staged under
+    fn approve(
+        env: &Env,
+        authority: Address,
+        approval_key: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let key = DataKey::PendingApproval(approval_key.clone());
+
+        let mut pending: PendingApproval = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::InvalidApprovalKey)?;
+
+        if Self::approval_expired(env, &pending) {
+            return Err(ContractError::ApprovalExpired);
+        }
+
+        if let Some(rule_id) = pending.rule_id.clone() {
+            let now = env.ledger().timestamp();
+            let last_key = DataKey::LastRuleApproval(rule_id.clone());
+            let cooldown = Self::get_rule(env.clone(), rule_id).and_then(|rule| rule.cooldown);
+            let last: Option<u64> = env.storage().persistent().get(&last_key);
+            if let (Some(cooldown), Some(last)) = (cooldown, last) {
+                if now < last.saturating_add(cooldown) {
+                    return Err(ContractError::AuthorizationCooldown);
+                }
+            }
+            env.storage().persistent().set(&last_key, &now);
+        }
+
+        // Mark as approved
+        pending.approved = true;
+        env.storage().persistent().set(&key, &pending);
+
+        ApprovalRecorded {
+            approval_key,
+            authority,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Store a fresh pending approval, remembering the rule it was staged
+    /// under so `record_authorization` can apply that rule's cooldown. An
+    /// approval already staged under the key is left as it is, so staging
+    /// again cannot clear an authorization already recorded.
+    fn stage_pending_approval(
+        env: &Env,
+        approval_key: BytesN<32>,
+        token_id: u32,
+        source: Address,
+        destination: Address,
+        operation: OperationType,
+        rule_id: Option<String>,
+    ) {
+        let key = DataKey::PendingApproval(approval_key.clone());
+        if env.storage().persistent().has(&key) {
+            return;
+        }
+
         let pending = PendingApproval {
             token_id,
             source,
             destination,
             operation,
-            timestamp: env.ledger().timestamp(),
-            approved: false,
-        };
-
-        let key = DataKey::PendingApproval(approval_key);
-        env.storage().persistent().set(&key, &pending);
+            timestamp: env.ledger().timestamp(),
+            created_ledger: env.ledger().sequence(),
+            approved: false,
+            rule_id,
+        };
+
+        let mut keys: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingApprovalKeys)
+            .unwrap_or(Vec::new(env));
+        keys.push_back(approval_key);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingApprovalKeys, &keys);
+        env.storage().persistent().set(&key, &pending);
+    }
+
+    /// Drop a staged approval and its entry in the pending approval index
+    fn remove_pending_approval(env: &Env, approval_key: &BytesN<32>) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingApproval(approval_key.clone()));
+
+        let mut keys: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingApprovalKeys)
+            .unwrap_or(Vec::new(env));
+        if let Some(index) = keys.first_index_of(approval_key) {
+            keys.remove(index);
+            env.storage()
+                .persistent()
+                .set(&DataKey::PendingApprovalKeys, &keys);
+        }
+    }
+
+    /// Count staged approvals whose window has passed, approved or not, to
+    /// size a cleanup before running it
+    pub fn count_expired_approvals(env: Env) -> u32 {
+        let keys: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingApprovalKeys)
+            .unwrap_or(Vec::new(&env));
+
+        let mut count = 0;
+        for approval_key in keys.iter() {
+            if let Some(pending) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, PendingApproval>(&DataKey::PendingApproval(approval_key))
+            {
+                if Self::approval_expired(&env, &pending) {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Check if approval exists and is valid. Publishes an `ApprovalExpired`
+    /// event when the stored approval has run out of time.
+    pub fn check_approval(env: Env, approval_key: BytesN<32>) -> bool {
+        let key = DataKey::PendingApproval(approval_key.clone());
+
+        if let Some(pending) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PendingApproval>(&key)
+        {
+            if Self::approval_expired(&env, &pending) {
+                ApprovalExpired { approval_key }.publish(&env);
+                return false;
+            }
+            pending.approved
+        } else {
+            false
+        }
+    }
+
+    /// Get a pending approval, including the ledger it was created in
+    pub fn get_pending_approval(env: Env, approval_key: BytesN<32>) -> Option<PendingApproval> {
+        let key = DataKey::PendingApproval(approval_key);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Limit how many ledgers an approval stays usable for, on top of the
+    /// approval window (admin only). `None` removes the limit.
+    pub fn set_max_approval_ledger_age(
+        env: Env,
+        caller: Address,
+        max_ledger_age: Option<u32>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        match max_ledger_age {
+            Some(age) => env
+                .storage()
+                .instance()
+                .set(&DataKey::MaxApprovalLedgerAge, &age),
+            None => env
+                .storage()
+                .instance()
+                .remove(&DataKey::MaxApprovalLedgerAge),
+        }
+        Ok(())
+    }
+
+    /// Get the approval ledger age limit, if any
+    pub fn get_max_approval_ledger_age(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::MaxApprovalLedgerAge)
+    }
+
+    /// Keep approvals usable for `grace_period` seconds past the approval window
+    /// (governance only). Defaults to 0.
+    pub fn set_approval_grace_period(
+        env: Env,
+        caller: Address,
+        grace_period: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ApprovalGracePeriod, &grace_period);
+        Ok(())
+    }
+
+    /// Give approvals for `operation` their own validity window in place of
+    /// `DEFAULT_APPROVAL_WINDOW` (governance only). `None` restores the default.
+    pub fn set_operation_window(
+        env: Env,
+        caller: Address,
+        operation: OperationType,
+        window: Option<u64>,
+    ) -> Result<(), ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        let key = DataKey::OperationWindow(operation);
+        match window {
+            Some(0) => return Err(ContractError::InvalidExpiry),
+            Some(window) => env.storage().instance().set(&key, &window),
+            None => env.storage().instance().remove(&key),
+        }
+        Ok(())
+    }
+
+    /// Get the approval window applied to `operation`, in seconds
+    pub fn get_approval_window(env: Env, operation: OperationType) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OperationWindow(operation))
+            .unwrap_or(DEFAULT_APPROVAL_WINDOW)
+    }
+
+    /// Get the grace period applied after the approval window, in seconds
+    pub fn get_approval_grace_period(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ApprovalGracePeriod)
+            .unwrap_or(0)
+    }
+
+    /// Gate execution on a staged approval in a single call. Returns a
+    /// compliant result carrying the approval key and the rule it was staged
+    /// under once the authority has approved, and a non-compliant result if it
+    /// is unknown, pending, or expired. A successful call consumes the
+    /// approval, so it cannot be replayed for a second execution. Only the
+    /// approval's source or the carbon asset contract may consume it.
+    pub fn finalize_if_approved(
+        env: Env,
+        caller: Address,
+        approval_key: BytesN<32>,
+    ) -> Result<ValidationResult, ContractError> {
+        caller.require_auth();
+
+        let key = DataKey::PendingApproval(approval_key.clone());
+
+        let Some(pending) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PendingApproval>(&key)
+        else {
+            return Ok(Self::non_compliant(&env, "Invalid approval key"));
+        };
+
+        if caller != pending.source
+            && !Self::is_stored_address(&env, &DataKey::CarbonAssetContract, &caller)
+        {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        if Self::approval_expired(&env, &pending) {
+            return Ok(Self::non_compliant(&env, "Approval expired"));
+        }
+
+        if !pending.approved {
+            return Ok(Self::non_compliant(&env, "Approval pending"));
+        }
+
+        Self::remove_pending_approval(&env, &approval_key);
+
+        Ok(ValidationResult {
+            is_compliant: true,
+            rule_id: pending.rule_id,
+            requires_authorization: false,
+            authority_address: None,
+            error_message: None,
+            approval_key: Some(approval_key),
+        })
+    }
+
+    // ========================================================================
+    // Helper Functions
+    // ========================================================================
+
+    /// Approvals expire their operation's window (7 days unless governance
+    /// set another) plus the grace period after creation, or sooner once they
+    /// are older than the configured ledger age limit
+    fn approval_expired(env: &Env, pending: &PendingApproval) -> bool {
+        let window = Self::get_approval_window(env.clone(), pending.operation.clone());
+        let grace_period = Self::get_approval_grace_period(env.clone());
+        let expires_at = pending
+            .timestamp
+            .saturating_add(window)
+            .saturating_add(grace_period);
+        if env.ledger().timestamp() > expires_at {
+            return true;
+        }
+
+        let max_ledger_age: Option<u32> =
+            env.storage().instance().get(&DataKey::MaxApprovalLedgerAge);
+        max_ledger_age.is_some_and(|max_age| {
+            env.ledger()
+                .sequence()
+                .saturating_sub(pending.created_ledger)
+                > max_age
+        })
+    }
+
+    /// Current window of the rate limit on the parties' jurisdiction pair:
+    /// its counter key, the limit, and the window start and count, with a new
+    /// window begun once the stored one has elapsed. `None` when either party
+    /// has no jurisdiction or the pair is unlimited.
+    fn pair_window(
+        env: &Env,
+        source: &Address,
+        destination: &Address,
+    ) -> Option<(DataKey, PairRateLimit, u64, u32)> {
+        let source_jur = Self::get_address_jurisdiction(env.clone(), source.clone())?;
+        let dest_jur = Self::get_address_jurisdiction(env.clone(), destination.clone())?;
+
+        let limit: PairRateLimit = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PairLimit(source_jur.clone(), dest_jur.clone()))?;
+
+        let count_key = DataKey::PairCount(source_jur, dest_jur);
+        let now = env.ledger().timestamp();
+        let (window_start, count): (u64, u32) = env
+            .storage()
+            .persistent()
+            .get(&count_key)
+            .unwrap_or((now, 0));
+
+        if now >= window_start.saturating_add(limit.window_seconds) {
+            Some((count_key, limit, now, 0))
+        } else {
+            Some((count_key, limit, window_start, count))
+        }
+    }
+
+    /// Turn a compliant result non-compliant when the parties' pair has used
+    /// up its rate limit for the current window. Nothing is counted here;
+    /// transfers are counted by `record_pair_transfer`.
+    fn apply_pair_limit(
+        env: &Env,
+        result: ValidationResult,
+        source: &Address,
+        destination: &Address,
+    ) -> ValidationResult {
+        if !result.is_compliant {
+            return result;
+        }
+
+        match Self::pair_window(env, source, destination) {
+            Some((_, limit, _, count)) if count >= limit.max_count => ValidationResult {
+                is_compliant: false,
+                rule_id: result.rule_id,
+                requires_authorization: false,
+                authority_address: None,
+                error_message: Some(String::from_str(env, "Pair rate limit exceeded")),
+                approval_key: None,
+            },
+            _ => result,
+        }
+    }
+
+    fn remove_active_rule_id(env: &Env, rule_id: &String) {
+        let active_rules: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActiveRuleIds)
+            .unwrap();
+
+        let mut new_rules = Vec::new(env);
+        for i in 0..active_rules.len() {
+            let rid = active_rules.get(i).unwrap();
+            if rid != *rule_id {
+                new_rules.push_back(rid);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveRuleIds, &new_rules);
+    }
+
+    /// Add or remove one member from a jurisdiction's count, keeping the
+    /// distinct jurisdiction count in step as it gains its first member or
+    /// loses its last.
+    fn adjust_jurisdiction_members(env: &Env, jurisdiction: String, joined: bool) {
+        let key = DataKey::JurisdictionMembers(jurisdiction);
+        let members: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let distinct = Self::get_jurisdiction_count(env.clone());
+
+        if joined {
+            if members == 0 {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::JurisdictionCount, &(distinct + 1));
+            }
+            env.storage().persistent().set(&key, &(members + 1));
+        } else if members > 0 {
+            if members == 1 {
+                env.storage().persistent().remove(&key);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::JurisdictionCount, &distinct.saturating_sub(1));
+            } else {
+                env.storage().persistent().set(&key, &(members - 1));
+            }
+        }
+    }
+
+    fn index_rule_category(env: &Env, rule: &JurisdictionRule) {
+        let Some(category) = rule.category.clone() else {
+            return;
+        };
+
+        let mut rule_ids = Self::get_rules_by_category(env.clone(), category.clone());
+        if !rule_ids.contains(&rule.rule_id) {
+            rule_ids.push_back(rule.rule_id.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::CategoryRules(category), &rule_ids);
+        }
+    }
+
+    fn unindex_rule_category(env: &Env, rule: &JurisdictionRule) {
+        let Some(category) = rule.category.clone() else {
+            return;
+        };
+
+        let mut rule_ids = Self::get_rules_by_category(env.clone(), category.clone());
+        if let Some(index) = rule_ids.first_index_of(&rule.rule_id) {
+            rule_ids.remove(index);
+            env.storage()
+                .persistent()
+                .set(&DataKey::CategoryRules(category), &rule_ids);
+        }
+    }
+
+    /// Authenticate `caller` and check it is the admin. An uninitialized
+    /// contract has no admin, so every caller is rejected.
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !Self::is_stored_address(env, &DataKey::Admin, caller) {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        Ok(())
+    }
+
+    /// Authenticate `caller` and check it is governance. An uninitialized
+    /// contract has no governance, so every caller is rejected.
+    fn require_governance(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !Self::is_stored_address(env, &DataKey::Governance, caller) {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        Ok(())
+    }
+
+    fn is_stored_address(env: &Env, key: &DataKey, caller: &Address) -> bool {
+        env.storage().instance().get::<_, Address>(key).as_ref() == Some(caller)
+    }
+
+    /// Rules tied to a registry project only activate once it is Verified
+    fn require_verified_project(env: &Env, rule: &JurisdictionRule) -> Result<(), ContractError> {
+        let Some(project_id) = rule.project_id.clone() else {
+            return Ok(());
+        };
+
+        let registry: Option<Address> = env.storage().instance().get(&DataKey::RegistryContract);
+        let Some(registry) = registry else {
+            return Err(ContractError::ProjectNotVerified);
+        };
+
+        match RegistryClient::new(env, &registry).try_get_project(&project_id) {
+            Ok(Ok(project)) if project.status == ProjectStatus::Verified => Ok(()),
+            _ => Err(ContractError::ProjectNotVerified),
+        }
+    }
+
+    /// Ask the registry whether both parties hold a valid KYC attestation.
+    /// Without a registry, or when the call fails, they count as unattested.
+    fn parties_attested(env: &Env, source: &Address, destination: &Address) -> bool {
+        let registry: Option<Address> = env.storage().instance().get(&DataKey::RegistryContract);
+        let Some(registry) = registry else {
+            return false;
+        };
+        let registry = RegistryClient::new(env, &registry);
+
+        [source, destination].iter().all(|account| {
+            matches!(
+                registry.try_has_valid_address_attestation(account),
+                Ok(Ok(true))
+            )
+        })
+    }
+
+    fn non_compliant(env: &Env, message: &str) -> ValidationResult {
+        ValidationResult {
+            is_compliant: false,
+            rule_id: None,
+            requires_authorization: false,
+            authority_address: None,
+            error_message: Some(String::from_str(env, message)),
+            approval_key: None,
+        }
+    }
+
+    /// Key a transaction requiring authorization is staged and approved
+    /// under. The same transaction in the same ledger always yields the same
+    /// key; validations without a token use `None`.
+    fn derive_approval_key(
+        env: &Env,
+        token_id: Option<u32>,
+        source: &Address,
+        destination: &Address,
+        operation: &OperationType,
+    ) -> BytesN<32> {
+        Self::transaction_key(
+            env,
+            token_id,
+            source,
+            destination,
+            operation,
+            env.ledger().timestamp(),
+        )
     }
 
-    /// Check if approval exists and is valid
-    pub fn check_approval(env: Env, approval_key: BytesN<32>) -> bool {
-        let key = DataKey::PendingApproval(approval_key);
+    fn transaction_key(
+        env: &Env,
+        token_id: Option<u32>,
+        source: &Address,
+        destination: &Address,
+        operation: &OperationType,
+        timestamp: u64,
+    ) -> BytesN<32> {
+        let preimage = (
+            token_id,
+            source.clone(),
+            destination.clone(),
+            operation.clone(),
+            timestamp,
+        );
+        env.crypto().sha256(&preimage.to_xdr(env)).into()
+    }
 
-        if let Some(pending) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, PendingApproval>(&key)
-        {
-            let current_time = env.ledger().timestamp();
-            // Check if not expired and approved
-            pending.approved && current_time <= pending.timestamp + 604800
-        } else {
-            false
+    /// Authority a rule requires: its own, or the default one if it opts in
+    fn effective_authority(env: &Env, rule: &JurisdictionRule) -> Option<Address> {
+        match &rule.required_authority {
+            Some(authority) => Some(authority.clone()),
+            None if rule.use_default_authority => Self::get_default_authority(env.clone()),
+            None => None,
         }
     }
 
-    // ========================================================================
-    // Helper Functions
-    // ========================================================================
+    fn has_standing_authorization(
+        env: &Env,
+        authority: &Address,
+        source: &Address,
+        destination: &Address,
+        operation: &OperationType,
+    ) -> bool {
+        let key = DataKey::StandingAuthorization(
+            authority.clone(),
+            source.clone(),
+            destination.clone(),
+            operation.clone(),
+        );
+
+        env.storage()
+            .persistent()
+            .get::<DataKey, StandingAuthorization>(&key)
+            .is_some_and(|standing| env.ledger().timestamp() < standing.expires_at)
+    }
 
     fn rule_matches(
         env: &Env,
@@ -423,62 +2417,381 @@ impl RegulatoryCheck {
         host_jur: &String,
         operation: &OperationType,
     ) -> bool {
+        Self::explain_rule(env, rule, source_jur, dest_jur, host_jur, operation).matched
+    }
+
+    fn explain_rule(
+        env: &Env,
+        rule: &JurisdictionRule,
+        source_jur: &String,
+        dest_jur: &String,
+        host_jur: &String,
+        operation: &OperationType,
+    ) -> RuleMatchExplanation {
         let any = String::from_str(env, "ANY");
 
-        if rule.operation != *operation {
-            return false;
+        let operation_matches = rule.operation == *operation;
+        let source_matches = rule.source_jur == any || rule.source_jur == *source_jur;
+        let dest_matches = rule.dest_jur == any || rule.dest_jur == *dest_jur;
+        let host_matches = rule.host_jur == any || rule.host_jur == *host_jur;
+        let expired = rule
+            .expires_at
+            .is_some_and(|expires_at| env.ledger().timestamp() >= expires_at);
+
+        RuleMatchExplanation {
+            rule_id: rule.rule_id.clone(),
+            operation_matches,
+            source_matches,
+            dest_matches,
+            host_matches,
+            expired,
+            matched: operation_matches
+                && source_matches
+                && dest_matches
+                && host_matches
+                && !expired,
+            is_allowed: rule.is_allowed,
         }
+    }
 
-        if rule.source_jur != any && rule.source_jur != *source_jur {
-            return false;
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Update admin address
+    pub fn update_admin(
+        env: Env,
+        caller: Address,
+        new_admin: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Ok(())
+    }
+
+    /// Upgrade the contract to new Wasm, preserving rules and jurisdictions
+    pub fn upgrade(
+        env: Env,
+        caller: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    /// Rewrite stored rules from the old layout up to `schema_version`.
+    /// Re-running at or below the stored version is a no-op.
+    pub fn migrate(env: Env, caller: Address, schema_version: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        if schema_version == 0 || schema_version > CURRENT_SCHEMA_VERSION {
+            return Err(ContractError::InvalidSchemaVersion);
         }
 
-        if rule.dest_jur != any && rule.dest_jur != *dest_jur {
-            return false;
+        let current = Self::get_schema_version(env.clone());
+        if schema_version <= current {
+            return Ok(());
         }
 
-        if rule.host_jur != any && rule.host_jur != *host_jur {
-            return false;
+        // Rules are rewritten straight to the layout this code reads
+        if schema_version != CURRENT_SCHEMA_VERSION {
+            return Err(ContractError::InvalidSchemaVersion);
+        }
+
+        let active_rules: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActiveRuleIds)
+            .unwrap_or(Vec::new(&env));
+        for rule_id in active_rules.iter() {
+            Self::migrate_rule(&env, &DataKey::Rule(rule_id));
+        }
+
+        let archived_rules: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ArchivedRuleIds)
+            .unwrap_or(Vec::new(&env));
+        for rule_id in archived_rules.iter() {
+            Self::migrate_rule(&env, &DataKey::ArchivedRule(rule_id));
         }
 
-        true
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &schema_version);
+        Ok(())
     }
 
-    // ========================================================================
-    // Admin Functions
-    // ========================================================================
+    /// Rewrite one stored rule into the current layout. v1 -> v2 added
+    /// `expires_at` and v2 -> v3 added `project_id`, both defaulting to
+    /// `None`; v3 -> v4 added `use_default_authority`, defaulting to `false`;
+    /// v4 -> v5 added `cooldown` and v5 -> v6 `authority_min_amount`, both
+    /// defaulting to `None`; v6 -> v7 added `requires_attestation`,
+    /// defaulting to `false`; v7 -> v8 added `category`, defaulting to
+    /// `None`. Rules already in the current layout are left as-is.
+    fn migrate_rule(env: &Env, rule_key: &DataKey) {
+        let Some(fields) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Map<Symbol, Val>>(rule_key)
+        else {
+            return;
+        };
 
-    /// Update admin address
-    pub fn update_admin(
+        if fields.contains_key(Symbol::new(env, "category")) {
+            return;
+        }
+
+        let old: JurisdictionRuleV7 =
+            if fields.contains_key(Symbol::new(env, "requires_attestation")) {
+                fields.to_val().into_val(env)
+            } else {
+                let v6 = Self::read_rule_v6(env, fields);
+                JurisdictionRuleV7 {
+                    rule_id: v6.rule_id,
+                    description: v6.description,
+                    source_jur: v6.source_jur,
+                    dest_jur: v6.dest_jur,
+                    host_jur: v6.host_jur,
+                    operation: v6.operation,
+                    is_allowed: v6.is_allowed,
+                    required_authority: v6.required_authority,
+                    expires_at: v6.expires_at,
+                    project_id: v6.project_id,
+                    use_default_authority: v6.use_default_authority,
+                    cooldown: v6.cooldown,
+                    authority_min_amount: v6.authority_min_amount,
+                    requires_attestation: false,
+                }
+            };
+
+        let rule = JurisdictionRule {
+            rule_id: old.rule_id,
+            description: old.description,
+            source_jur: old.source_jur,
+            dest_jur: old.dest_jur,
+            host_jur: old.host_jur,
+            operation: old.operation,
+            is_allowed: old.is_allowed,
+            required_authority: old.required_authority,
+            expires_at: old.expires_at,
+            project_id: old.project_id,
+            use_default_authority: old.use_default_authority,
+            cooldown: old.cooldown,
+            authority_min_amount: old.authority_min_amount,
+            requires_attestation: old.requires_attestation,
+            category: None,
+        };
+        env.storage().persistent().set(rule_key, &rule);
+    }
+
+    /// Read a stored rule from any layout up to v6 as a v6 rule
+    fn read_rule_v6(env: &Env, fields: Map<Symbol, Val>) -> JurisdictionRuleV6 {
+        if fields.contains_key(Symbol::new(env, "authority_min_amount")) {
+            fields.to_val().into_val(env)
+        } else {
+            let v5 = Self::read_rule_v5(env, fields);
+            JurisdictionRuleV6 {
+                rule_id: v5.rule_id,
+                description: v5.description,
+                source_jur: v5.source_jur,
+                dest_jur: v5.dest_jur,
+                host_jur: v5.host_jur,
+                operation: v5.operation,
+                is_allowed: v5.is_allowed,
+                required_authority: v5.required_authority,
+                expires_at: v5.expires_at,
+                project_id: v5.project_id,
+                use_default_authority: v5.use_default_authority,
+                cooldown: v5.cooldown,
+                authority_min_amount: None,
+            }
+        }
+    }
+
+    /// Read a stored rule from any layout up to v5 as a v5 rule
+    fn read_rule_v5(env: &Env, fields: Map<Symbol, Val>) -> JurisdictionRuleV5 {
+        if fields.contains_key(Symbol::new(env, "cooldown")) {
+            fields.to_val().into_val(env)
+        } else {
+            let v4 = Self::read_rule_v4(env, fields);
+            JurisdictionRuleV5 {
+                rule_id: v4.rule_id,
+                description: v4.description,
+                source_jur: v4.source_jur,
+                dest_jur: v4.dest_jur,
+                host_jur: v4.host_jur,
+                operation: v4.operation,
+                is_allowed: v4.is_allowed,
+                required_authority: v4.required_authority,
+                expires_at: v4.expires_at,
+                project_id: v4.project_id,
+                use_default_authority: v4.use_default_authority,
+                cooldown: None,
+            }
+        }
+    }
+
+    /// Read a stored rule from any layout up to v4 as a v4 rule
+    fn read_rule_v4(env: &Env, fields: Map<Symbol, Val>) -> JurisdictionRuleV4 {
+        if fields.contains_key(Symbol::new(env, "use_default_authority")) {
+            fields.to_val().into_val(env)
+        } else {
+            let v3 = Self::read_rule_v3(env, fields);
+            JurisdictionRuleV4 {
+                rule_id: v3.rule_id,
+                description: v3.description,
+                source_jur: v3.source_jur,
+                dest_jur: v3.dest_jur,
+                host_jur: v3.host_jur,
+                operation: v3.operation,
+                is_allowed: v3.is_allowed,
+                required_authority: v3.required_authority,
+                expires_at: v3.expires_at,
+                project_id: v3.project_id,
+                use_default_authority: false,
+            }
+        }
+    }
+
+    /// Read a stored rule from any layout up to v3 as a v3 rule
+    fn read_rule_v3(env: &Env, fields: Map<Symbol, Val>) -> JurisdictionRuleV3 {
+        if fields.contains_key(Symbol::new(env, "project_id")) {
+            fields.to_val().into_val(env)
+        } else {
+            let v2: JurisdictionRuleV2 = if fields.contains_key(Symbol::new(env, "expires_at")) {
+                fields.to_val().into_val(env)
+            } else {
+                let v1: JurisdictionRuleV1 = fields.to_val().into_val(env);
+                JurisdictionRuleV2 {
+                    rule_id: v1.rule_id,
+                    description: v1.description,
+                    source_jur: v1.source_jur,
+                    dest_jur: v1.dest_jur,
+                    host_jur: v1.host_jur,
+                    operation: v1.operation,
+                    is_allowed: v1.is_allowed,
+                    required_authority: v1.required_authority,
+                    expires_at: None,
+                }
+            };
+            JurisdictionRuleV3 {
+                rule_id: v2.rule_id,
+                description: v2.description,
+                source_jur: v2.source_jur,
+                dest_jur: v2.dest_jur,
+                host_jur: v2.host_jur,
+                operation: v2.operation,
+                is_allowed: v2.is_allowed,
+                required_authority: v2.required_authority,
+                expires_at: v2.expires_at,
+                project_id: None,
+            }
+        }
+    }
+
+    /// Get the storage schema version, treating unversioned storage as version 1
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(1)
+    }
+
+    /// Set the project registry used to resolve token jurisdictions
+    pub fn set_registry_contract(
         env: Env,
         caller: Address,
-        new_admin: Address,
+        registry: Address,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RegistryContract, &registry);
+        Ok(())
+    }
+
+    /// Pause compliance validation and authorization recording. Rule and
+    /// jurisdiction management stay available so governance can fix them.
+    pub fn pause(env: Env, caller: Address) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().instance().set(&DataKey::Paused, &true);
+        Ok(())
+    }
+
+    /// Resume a paused contract
+    pub fn unpause(env: Env, caller: Address) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().instance().set(&DataKey::Paused, &false);
+        Ok(())
+    }
+
+    /// Set the coordinator that may pause and resume this contract together
+    /// with the others during an incident (admin only)
+    pub fn set_coordinator(
+        env: Env,
+        caller: Address,
+        coordinator: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        env.storage()
+            .instance()
+            .set(&DataKey::Coordinator, &coordinator);
+        Ok(())
+    }
+
+    /// Get the configured coordinator, if any
+    pub fn get_coordinator(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Coordinator)
+    }
+
+    /// Pause or resume on behalf of the configured coordinator
+    pub fn set_paused_by_coordinator(
+        env: Env,
+        coordinator_caller: Address,
+        paused: bool,
+    ) -> Result<(), ContractError> {
+        coordinator_caller.require_auth();
 
-        if caller != admin {
+        if Self::get_coordinator(env.clone()) != Some(coordinator_caller) {
             return Err(ContractError::NotAuthorized);
         }
 
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().set(&DataKey::Paused, &paused);
         Ok(())
     }
 
+    /// Whether the contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Report initialization, pause state and the number of active rules
+    pub fn health_check(env: Env) -> HealthStatus {
+        HealthStatus {
+            initialized: env.storage().instance().has(&DataKey::Admin),
+            paused: Self::is_paused(env.clone()),
+            entity_count: Self::get_active_rules(env).len(),
+        }
+    }
+
     /// Update governance address
     pub fn update_governance(
         env: Env,
         caller: Address,
         new_governance: Address,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
-
-        let governance: Address = env.storage().instance().get(&DataKey::Governance).unwrap();
-
-        if caller != governance {
-            return Err(ContractError::NotAuthorized);
-        }
+        Self::require_governance(&env, &caller)?;
 
         env.storage()
             .instance()
@@ -492,6 +2805,26 @@ impl RegulatoryCheck {
         env.storage().persistent().get(&key)
     }
 
+    /// Get an archived rule by ID
+    pub fn get_archived_rule(env: Env, rule_id: String) -> Option<JurisdictionRule> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArchivedRule(rule_id))
+    }
+
+    /// Get the CarbonAsset contract address
+    pub fn get_carbon_asset_contract(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::CarbonAssetContract)
+            .unwrap()
+    }
+
+    /// Get the project registry address, if set
+    pub fn get_registry_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RegistryContract)
+    }
+
     /// Get all active rule IDs
     pub fn get_active_rules(env: Env) -> Vec<String> {
         env.storage()
@@ -499,4 +2832,235 @@ impl RegulatoryCheck {
             .get(&DataKey::ActiveRuleIds)
             .unwrap_or(Vec::new(&env))
     }
+
+    /// Diagnostic view of how each active rule, in evaluation order, compares
+    /// against a transaction. Validation applies the first rule with `matched`
+    /// set. Addresses without a jurisdiction only match `ANY` fields here,
+    /// although validation rejects them outright.
+    pub fn explain_validation(
+        env: Env,
+        source_address: Address,
+        destination_address: Address,
+        operation: OperationType,
+        host_jurisdiction: String,
+    ) -> Vec<RuleMatchExplanation> {
+        let unset = String::from_str(&env, "");
+        let source_jur =
+            Self::get_address_jurisdiction(env.clone(), source_address).unwrap_or(unset.clone());
+        let dest_jur =
+            Self::get_address_jurisdiction(env.clone(), destination_address).unwrap_or(unset);
+
+        let mut explanations = Vec::new(&env);
+        for rule_id in Self::get_active_rules(env.clone()).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id) {
+                explanations.push_back(Self::explain_rule(
+                    &env,
+                    &rule,
+                    &source_jur,
+                    &dest_jur,
+                    &host_jurisdiction,
+                    &operation,
+                ));
+            }
+        }
+
+        explanations
+    }
+
+    /// What-if view of reassigning `account` to `new_jurisdiction`: the IDs of
+    /// active rules, in evaluation order, that would match a transfer between
+    /// `account` and any of `partners`, in either direction. The host
+    /// jurisdiction is not known ahead of time and is not compared. Partners
+    /// without a jurisdiction are skipped, and nothing is written.
+    pub fn preview_jurisdiction_change(
+        env: Env,
+        account: Address,
+        new_jurisdiction: String,
+        partners: Vec<Address>,
+    ) -> Vec<String> {
+        let mut partner_jurs = Vec::new(&env);
+        for partner in partners.iter() {
+            if partner == account {
+                continue;
+            }
+            if let Some(jurisdiction) = Self::get_address_jurisdiction(env.clone(), partner) {
+                partner_jurs.push_back(jurisdiction);
+            }
+        }
+
+        let mut rule_ids = Vec::new(&env);
+        for rule_id in Self::get_active_rules(env.clone()).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id.clone()) {
+                let matches = partner_jurs.iter().any(|partner_jur| {
+                    [
+                        (&new_jurisdiction, &partner_jur),
+                        (&partner_jur, &new_jurisdiction),
+                    ]
+                    .into_iter()
+                    .any(|(source_jur, dest_jur)| {
+                        // Passing the rule's own host jurisdiction leaves it uncompared
+                        Self::rule_matches(
+                            &env,
+                            &rule,
+                            source_jur,
+                            dest_jur,
+                            &rule.host_jur,
+                            &OperationType::TRANSFER,
+                        )
+                    })
+                });
+                if matches {
+                    rule_ids.push_back(rule_id);
+                }
+            }
+        }
+
+        rule_ids
+    }
+
+    /// Point every active rule requiring `old_authority` at `new_authority`
+    /// (governance only). Returns the number of rules updated.
+    pub fn rotate_authority(
+        env: Env,
+        caller: Address,
+        old_authority: Address,
+        new_authority: Address,
+    ) -> Result<u32, ContractError> {
+        Self::require_governance(&env, &caller)?;
+
+        let rule_ids = Self::get_rules_by_authority(env.clone(), old_authority.clone());
+        for rule_id in rule_ids.iter() {
+            let rule_key = DataKey::Rule(rule_id);
+            let mut rule: JurisdictionRule = env.storage().persistent().get(&rule_key).unwrap();
+            rule.required_authority = Some(new_authority.clone());
+            env.storage().persistent().set(&rule_key, &rule);
+        }
+
+        let count = rule_ids.len();
+        AuthorityRotated {
+            old_authority,
+            new_authority,
+            rule_ids,
+        }
+        .publish(&env);
+
+        Ok(count)
+    }
+
+    /// Get the IDs of active rules whose `required_authority` is `authority`
+    pub fn get_rules_by_authority(env: Env, authority: Address) -> Vec<String> {
+        let mut rule_ids = Vec::new(&env);
+        for rule_id in Self::get_active_rules(env.clone()).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id.clone()) {
+                if rule.required_authority == Some(authority.clone()) {
+                    rule_ids.push_back(rule_id);
+                }
+            }
+        }
+
+        rule_ids
+    }
+
+    /// Get the IDs of active rules whose source or destination jurisdiction
+    /// is `account`'s jurisdiction or `ANY`, that is every rule that could
+    /// apply to it on either side of a transaction. An address without a
+    /// jurisdiction gets none.
+    pub fn get_rules_affecting_address(env: Env, account: Address) -> Vec<String> {
+        let mut rule_ids = Vec::new(&env);
+        let Some(jurisdiction) = Self::get_address_jurisdiction(env.clone(), account) else {
+            return rule_ids;
+        };
+
+        let any = String::from_str(&env, "ANY");
+        for rule_id in Self::get_active_rules(env.clone()).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id.clone()) {
+                let affects = [rule.source_jur, rule.dest_jur]
+                    .iter()
+                    .any(|side| *side == jurisdiction || *side == any);
+                if affects {
+                    rule_ids.push_back(rule_id);
+                }
+            }
+        }
+
+        rule_ids
+    }
+
+    /// Get the IDs of active rules whose decision is `allowed`, in active
+    /// order, to review the allow and deny rules separately
+    pub fn get_rules_by_decision(env: Env, allowed: bool) -> Vec<String> {
+        let mut rule_ids = Vec::new(&env);
+        for rule_id in Self::get_active_rules(env.clone()).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id.clone()) {
+                if rule.is_allowed == allowed {
+                    rule_ids.push_back(rule_id);
+                }
+            }
+        }
+
+        rule_ids
+    }
+
+    /// Get the distinct jurisdictions named by active rules as source,
+    /// destination or host, in the order first seen. `ANY` is left out, so a
+    /// jurisdiction missing here has no rule of its own.
+    pub fn get_covered_jurisdictions(env: Env) -> Vec<String> {
+        let any = String::from_str(&env, "ANY");
+        let mut jurisdictions: Vec<String> = Vec::new(&env);
+        for rule_id in Self::get_active_rules(env.clone()).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id) {
+                for jurisdiction in [rule.source_jur, rule.dest_jur, rule.host_jur] {
+                    if jurisdiction != any && !jurisdictions.contains(&jurisdiction) {
+                        jurisdictions.push_back(jurisdiction);
+                    }
+                }
+            }
+        }
+
+        jurisdictions
+    }
+
+    /// Get the IDs of active rules whose `expires_at` has passed and which no
+    /// longer match, awaiting `prune_expired_rules`
+    pub fn get_expired_rules(env: Env) -> Vec<String> {
+        let now = env.ledger().timestamp();
+        let mut rule_ids = Vec::new(&env);
+        for rule_id in Self::get_active_rules(env.clone()).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id.clone()) {
+                if rule.expires_at.is_some_and(|expires_at| now >= expires_at) {
+                    rule_ids.push_back(rule_id);
+                }
+            }
+        }
+
+        rule_ids
+    }
+
+    /// Get how many validations `rule_id` has decided, so rules that are never
+    /// exercised can be found and pruned
+    pub fn get_rule_hits(env: Env, rule_id: String) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RuleHits(rule_id))
+            .unwrap_or(0)
+    }
+
+    /// Get up to `limit` active rules starting at `cursor`, in activation order
+    pub fn get_active_rules_paged(env: Env, cursor: u32, limit: u32) -> RulePage {
+        let rule_ids = Self::get_active_rules(env.clone());
+        let (start, end, next_cursor) = page_bounds(rule_ids.len(), cursor, limit);
+
+        let mut items = Vec::new(&env);
+        for rule_id in rule_ids.slice(start..end).iter() {
+            if let Some(rule) = Self::get_rule(env.clone(), rule_id) {
+                items.push_back(rule);
+            }
+        }
+
+        RulePage {
+            items,
+            next_cursor,
+            total: rule_ids.len(),
+        }
+    }
 }
@@ -0,0 +1,3074 @@
+#![cfg(test)]
+
+use super::*;
+use shared_types::{error_source, ErrorSource};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, BytesN, Env, Event, String, Vec,
+};
+
+mod upgrade_target {
+    soroban_sdk::contractimport!(file = "test_wasms/upgrade_target.wasm");
+}
+
+/// Stand-in for the CarbonAsset contract, answering `owner_of` from storage
+#[contract]
+struct MockCarbonAsset;
+
+#[contractimpl]
+impl MockCarbonAsset {
+    pub fn set_owner(env: Env, token_id: u32, owner: Address) {
+        env.storage().persistent().set(&token_id, &owner);
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Address {
+        env.storage().persistent().get(&token_id).unwrap()
+    }
+}
+
+fn set_token_owner(env: &Env, client: &RegulatoryCheckClient, token_id: u32, owner: &Address) {
+    MockCarbonAssetClient::new(env, &client.get_carbon_asset_contract())
+        .set_owner(&token_id, owner);
+}
+
+fn setup_test_env<'a>() -> (Env, Address, Address, Address, RegulatoryCheckClient<'a>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_contract = env.register(MockCarbonAsset, ());
+
+    let contract_id = env.register(RegulatoryCheck, ());
+    let client = RegulatoryCheckClient::new(&env, &contract_id);
+    client.initialize(&admin, &governance, &carbon_contract);
+
+    (env, admin, governance, carbon_contract, client)
+}
+
+fn transfer_rule(env: &Env, rule_id: &str, source: &str, dest: &str) -> JurisdictionRule {
+    JurisdictionRule {
+        rule_id: String::from_str(env, rule_id),
+        description: String::from_str(env, "Test rule"),
+        source_jur: String::from_str(env, source),
+        dest_jur: String::from_str(env, dest),
+        host_jur: String::from_str(env, "ANY"),
+        operation: OperationType::TRANSFER,
+        is_allowed: true,
+        required_authority: None,
+        expires_at: None,
+        project_id: None,
+        use_default_authority: false,
+        cooldown: None,
+        authority_min_amount: None,
+        requires_attestation: false,
+        category: None,
+    }
+}
+
+fn v1_transfer_rule(env: &Env, rule_id: &str, source: &str, dest: &str) -> JurisdictionRuleV1 {
+    JurisdictionRuleV1 {
+        rule_id: String::from_str(env, rule_id),
+        description: String::from_str(env, "Legacy rule"),
+        source_jur: String::from_str(env, source),
+        dest_jur: String::from_str(env, dest),
+        host_jur: String::from_str(env, "ANY"),
+        operation: OperationType::TRANSFER,
+        is_allowed: true,
+        required_authority: None,
+    }
+}
+
+fn v2_transfer_rule(env: &Env, rule_id: &str, expires_at: Option<u64>) -> JurisdictionRuleV2 {
+    JurisdictionRuleV2 {
+        rule_id: String::from_str(env, rule_id),
+        description: String::from_str(env, "Expiring rule"),
+        source_jur: String::from_str(env, "US"),
+        dest_jur: String::from_str(env, "EU"),
+        host_jur: String::from_str(env, "ANY"),
+        operation: OperationType::TRANSFER,
+        is_allowed: true,
+        required_authority: None,
+        expires_at,
+    }
+}
+
+/// Write rules in the v1 layout and drop the schema marker, as a contract
+/// deployed before versioning would have left its storage.
+fn seed_v1_rules(env: &Env, contract_id: &Address, rules: &[JurisdictionRuleV1]) {
+    env.as_contract(contract_id, || {
+        let mut active_rules: Vec<String> = Vec::new(env);
+        for rule in rules {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Rule(rule.rule_id.clone()), rule);
+            active_rules.push_back(rule.rule_id.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveRuleIds, &active_rules);
+        env.storage().instance().remove(&DataKey::SchemaVersion);
+    });
+}
+
+#[test]
+fn test_upgrade_preserves_rules() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let rule = transfer_rule(&env, "US-EU", "US", "EU");
+    client.add_rule(&governance, &rule);
+
+    let account = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &account, &String::from_str(&env, "US"));
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgrade_target::WASM);
+    client.upgrade(&admin, &new_wasm_hash);
+
+    // The contract address now runs the new Wasm
+    let upgraded = upgrade_target::Client::new(&env, &client.address);
+    assert_eq!(upgraded.add(&2, &3), 5);
+
+    // Persistent rule and jurisdiction data survive the upgrade
+    env.as_contract(&client.address, || {
+        let stored: JurisdictionRule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Rule(rule.rule_id.clone()))
+            .unwrap();
+        assert_eq!(stored.source_jur, rule.source_jur);
+
+        let jurisdiction: String = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AddressJurisdiction(account.clone()))
+            .unwrap();
+        assert_eq!(jurisdiction, String::from_str(&env, "US"));
+    });
+}
+
+#[test]
+fn test_upgrade_rejects_non_admin() {
+    let (env, _, governance, _, client) = setup_test_env();
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgrade_target::WASM);
+    let result = client.try_upgrade(&governance, &new_wasm_hash);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    // The original code is still in place
+    assert_eq!(client.get_active_rules().len(), 0);
+}
+
+#[test]
+fn test_initialize_sets_current_schema_version() {
+    let (_, _, _, _, client) = setup_test_env();
+    assert_eq!(client.get_schema_version(), CURRENT_SCHEMA_VERSION);
+}
+
+#[test]
+fn test_privileged_calls_before_initialize_are_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = RegulatoryCheckClient::new(&env, &env.register(RegulatoryCheck, ()));
+    let caller = Address::generate(&env);
+
+    // With no admin or governance stored, every caller is turned away
+    // rather than the call panicking
+    assert_eq!(
+        client.try_add_rule(&caller, &transfer_rule(&env, "US-EU", "US", "EU")),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_set_address_jurisdiction(&caller, &caller, &String::from_str(&env, "US")),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_pause(&caller),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_update_admin(&caller, &caller),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_update_governance(&caller, &caller),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert!(client.get_active_rules().is_empty());
+}
+
+#[test]
+fn test_migrate_rewrites_v1_rules() {
+    let (env, admin, _, _, client) = setup_test_env();
+
+    seed_v1_rules(
+        &env,
+        &client.address,
+        &[
+            v1_transfer_rule(&env, "US-EU", "US", "EU"),
+            v1_transfer_rule(&env, "EU-US", "EU", "US"),
+        ],
+    );
+    assert_eq!(client.get_schema_version(), 1);
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+    assert_eq!(client.get_schema_version(), CURRENT_SCHEMA_VERSION);
+
+    let rule = client.get_rule(&String::from_str(&env, "US-EU")).unwrap();
+    assert_eq!(rule.source_jur, String::from_str(&env, "US"));
+    assert_eq!(rule.description, String::from_str(&env, "Legacy rule"));
+    assert_eq!(rule.expires_at, None);
+    assert_eq!(rule.project_id, None);
+
+    // Migrated rules are used by validation again
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "EU"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "US"));
+    let result = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &String::from_str(&env, "US"),
+        &None,
+    );
+    assert!(result.is_compliant);
+    assert_eq!(result.rule_id, Some(String::from_str(&env, "EU-US")));
+}
+
+#[test]
+fn test_migrate_rewrites_v2_rules() {
+    let (env, admin, _, _, client) = setup_test_env();
+
+    env.as_contract(&client.address, || {
+        let rule = v2_transfer_rule(&env, "US-EU", Some(5_000));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Rule(rule.rule_id.clone()), &rule);
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveRuleIds, &vec![&env, rule.rule_id]);
+        env.storage().instance().set(&DataKey::SchemaVersion, &2u32);
+    });
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+    assert_eq!(client.get_schema_version(), CURRENT_SCHEMA_VERSION);
+
+    let rule = client.get_rule(&String::from_str(&env, "US-EU")).unwrap();
+    assert_eq!(rule.description, String::from_str(&env, "Expiring rule"));
+    assert_eq!(rule.expires_at, Some(5_000));
+    assert_eq!(rule.project_id, None);
+}
+
+#[test]
+fn test_migrate_rewrites_v3_active_and_archived_rules() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    env.as_contract(&client.address, || {
+        for (rule_id, key, index) in [
+            (
+                "US-EU",
+                DataKey::Rule(String::from_str(&env, "US-EU")),
+                DataKey::ActiveRuleIds,
+            ),
+            (
+                "EU-US",
+                DataKey::ArchivedRule(String::from_str(&env, "EU-US")),
+                DataKey::ArchivedRuleIds,
+            ),
+        ] {
+            let rule = JurisdictionRuleV3 {
+                rule_id: String::from_str(&env, rule_id),
+                description: String::from_str(&env, "Project rule"),
+                source_jur: String::from_str(&env, "US"),
+                dest_jur: String::from_str(&env, "EU"),
+                host_jur: String::from_str(&env, "ANY"),
+                operation: OperationType::TRANSFER,
+                is_allowed: true,
+                required_authority: None,
+                expires_at: Some(5_000),
+                project_id: None,
+            };
+            env.storage().persistent().set(&key, &rule);
+            env.storage()
+                .instance()
+                .set(&index, &vec![&env, rule.rule_id]);
+        }
+        env.storage().instance().set(&DataKey::SchemaVersion, &3u32);
+    });
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+    assert_eq!(client.get_schema_version(), CURRENT_SCHEMA_VERSION);
+
+    let rule = client.get_rule(&String::from_str(&env, "US-EU")).unwrap();
+    assert_eq!(rule.expires_at, Some(5_000));
+    assert!(!rule.use_default_authority);
+
+    // Archived rules are readable and can be restored after the upgrade
+    let archived = client
+        .get_archived_rule(&String::from_str(&env, "EU-US"))
+        .unwrap();
+    assert!(!archived.use_default_authority);
+    client.reactivate_rule(&governance, &String::from_str(&env, "EU-US"));
+    assert_eq!(client.get_active_rules().len(), 2);
+}
+
+#[test]
+fn test_migrate_rewrites_v4_rules() {
+    let (env, admin, _, _, client) = setup_test_env();
+
+    env.as_contract(&client.address, || {
+        let rule = JurisdictionRuleV4 {
+            rule_id: String::from_str(&env, "US-EU"),
+            description: String::from_str(&env, "Default authority rule"),
+            source_jur: String::from_str(&env, "US"),
+            dest_jur: String::from_str(&env, "EU"),
+            host_jur: String::from_str(&env, "ANY"),
+            operation: OperationType::TRANSFER,
+            is_allowed: true,
+            required_authority: None,
+            expires_at: None,
+            project_id: None,
+            use_default_authority: true,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Rule(rule.rule_id.clone()), &rule);
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveRuleIds, &vec![&env, rule.rule_id]);
+        env.storage().instance().set(&DataKey::SchemaVersion, &4u32);
+    });
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+
+    let rule = client.get_rule(&String::from_str(&env, "US-EU")).unwrap();
+    assert!(rule.use_default_authority);
+    assert_eq!(rule.cooldown, None);
+}
+
+#[test]
+fn test_migrate_rewrites_v5_rules() {
+    let (env, admin, _, _, client) = setup_test_env();
+
+    env.as_contract(&client.address, || {
+        let rule = JurisdictionRuleV5 {
+            rule_id: String::from_str(&env, "US-EU"),
+            description: String::from_str(&env, "Cooldown rule"),
+            source_jur: String::from_str(&env, "US"),
+            dest_jur: String::from_str(&env, "EU"),
+            host_jur: String::from_str(&env, "ANY"),
+            operation: OperationType::TRANSFER,
+            is_allowed: true,
+            required_authority: None,
+            expires_at: None,
+            project_id: None,
+            use_default_authority: false,
+            cooldown: Some(60),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Rule(rule.rule_id.clone()), &rule);
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveRuleIds, &vec![&env, rule.rule_id]);
+        env.storage().instance().set(&DataKey::SchemaVersion, &5u32);
+    });
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+
+    let rule = client.get_rule(&String::from_str(&env, "US-EU")).unwrap();
+    assert_eq!(rule.cooldown, Some(60));
+    assert_eq!(rule.authority_min_amount, None);
+}
+
+#[test]
+fn test_migrate_rewrites_v6_rules() {
+    let (env, admin, _, _, client) = setup_test_env();
+
+    env.as_contract(&client.address, || {
+        let rule = JurisdictionRuleV6 {
+            rule_id: String::from_str(&env, "US-EU"),
+            description: String::from_str(&env, "Threshold rule"),
+            source_jur: String::from_str(&env, "US"),
+            dest_jur: String::from_str(&env, "EU"),
+            host_jur: String::from_str(&env, "ANY"),
+            operation: OperationType::TRANSFER,
+            is_allowed: true,
+            required_authority: None,
+            expires_at: None,
+            project_id: None,
+            use_default_authority: false,
+            cooldown: Some(60),
+            authority_min_amount: Some(1_000),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Rule(rule.rule_id.clone()), &rule);
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveRuleIds, &vec![&env, rule.rule_id]);
+        env.storage().instance().set(&DataKey::SchemaVersion, &6u32);
+    });
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+
+    let rule = client.get_rule(&String::from_str(&env, "US-EU")).unwrap();
+    assert_eq!(rule.cooldown, Some(60));
+    assert_eq!(rule.authority_min_amount, Some(1_000));
+    assert!(!rule.requires_attestation);
+}
+
+#[test]
+fn test_migrate_rewrites_v7_rules() {
+    let (env, admin, _, _, client) = setup_test_env();
+
+    env.as_contract(&client.address, || {
+        let rule = JurisdictionRuleV7 {
+            rule_id: String::from_str(&env, "US-EU"),
+            description: String::from_str(&env, "Attested rule"),
+            source_jur: String::from_str(&env, "US"),
+            dest_jur: String::from_str(&env, "EU"),
+            host_jur: String::from_str(&env, "ANY"),
+            operation: OperationType::TRANSFER,
+            is_allowed: true,
+            required_authority: None,
+            expires_at: None,
+            project_id: None,
+            use_default_authority: false,
+            cooldown: None,
+            authority_min_amount: Some(1_000),
+            requires_attestation: true,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Rule(rule.rule_id.clone()), &rule);
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveRuleIds, &vec![&env, rule.rule_id]);
+        env.storage().instance().set(&DataKey::SchemaVersion, &7u32);
+    });
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+
+    let rule = client.get_rule(&String::from_str(&env, "US-EU")).unwrap();
+    assert_eq!(rule.authority_min_amount, Some(1_000));
+    assert!(rule.requires_attestation);
+    assert_eq!(rule.category, None);
+}
+
+#[test]
+fn test_migrate_rejects_intermediate_version() {
+    let (env, admin, _, _, client) = setup_test_env();
+
+    seed_v1_rules(
+        &env,
+        &client.address,
+        &[v1_transfer_rule(&env, "US-EU", "US", "EU")],
+    );
+
+    let result = client.try_migrate(&admin, &2);
+    assert_eq!(result, Err(Ok(ContractError::InvalidSchemaVersion)));
+    assert_eq!(client.get_schema_version(), 1);
+}
+
+#[test]
+fn test_add_rule_with_project_requires_registry() {
+    let (env, _, governance, _, client) = setup_test_env();
+
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.project_id = Some(String::from_str(&env, "PROJ-1"));
+
+    let result = client.try_add_rule(&governance, &rule);
+    assert_eq!(result, Err(Ok(ContractError::ProjectNotVerified)));
+    assert_eq!(client.get_active_rules().len(), 0);
+}
+
+#[test]
+fn test_migrate_is_idempotent() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    seed_v1_rules(
+        &env,
+        &client.address,
+        &[v1_transfer_rule(&env, "US-EU", "US", "EU")],
+    );
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+
+    // A rule added in the new layout after migration must survive a re-run
+    let mut expiring = transfer_rule(&env, "EU-US", "EU", "US");
+    expiring.expires_at = Some(5_000);
+    client.add_rule(&governance, &expiring);
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+    client.migrate(&admin, &1);
+
+    assert_eq!(client.get_schema_version(), CURRENT_SCHEMA_VERSION);
+    assert_eq!(client.get_active_rules().len(), 2);
+    let rule = client.get_rule(&String::from_str(&env, "EU-US")).unwrap();
+    assert_eq!(rule.expires_at, Some(5_000));
+}
+
+#[test]
+fn test_migrate_skips_rules_already_in_new_layout() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    // Rule added after the code upgrade but before migrate ran
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.expires_at = Some(5_000);
+    client.add_rule(&governance, &rule);
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::SchemaVersion);
+    });
+
+    client.migrate(&admin, &CURRENT_SCHEMA_VERSION);
+
+    let stored = client.get_rule(&rule.rule_id).unwrap();
+    assert_eq!(stored.expires_at, Some(5_000));
+}
+
+#[test]
+fn test_migrate_rejects_non_admin_and_unknown_version() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    seed_v1_rules(
+        &env,
+        &client.address,
+        &[v1_transfer_rule(&env, "US-EU", "US", "EU")],
+    );
+
+    let result = client.try_migrate(&governance, &CURRENT_SCHEMA_VERSION);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    let result = client.try_migrate(&admin, &(CURRENT_SCHEMA_VERSION + 1));
+    assert_eq!(result, Err(Ok(ContractError::InvalidSchemaVersion)));
+
+    let result = client.try_migrate(&admin, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidSchemaVersion)));
+
+    assert_eq!(client.get_schema_version(), 1);
+}
+
+#[test]
+fn test_expired_rule_does_not_match() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.expires_at = Some(1_000);
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+
+    let host = String::from_str(&env, "US");
+    let result = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert!(result.is_compliant);
+
+    env.ledger().set_timestamp(1_000);
+    let result = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(result.rule_id, None);
+}
+
+#[test]
+fn test_validate_and_stage_creates_pending_approval() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    set_token_owner(&env, &client, 42, &source);
+
+    let result = client.validate_and_stage(
+        &source,
+        &destination,
+        &42,
+        &OperationType::TRANSFER,
+        &String::from_str(&env, "US"),
+    );
+    assert!(result.is_compliant);
+    assert!(result.requires_authorization);
+    assert_eq!(result.authority_address, Some(authority.clone()));
+    let approval_key = result.approval_key.unwrap();
+
+    env.as_contract(&client.address, || {
+        let pending: PendingApproval = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingApproval(approval_key.clone()))
+            .unwrap();
+        assert_eq!(pending.token_id, 42);
+        assert_eq!(pending.source, source);
+        assert_eq!(pending.destination, destination);
+        assert!(!pending.approved);
+        assert_eq!(pending.rule_id, Some(String::from_str(&env, "US-EU")));
+    });
+
+    assert!(!client.check_approval(&approval_key));
+    client.record_authorization(&authority, &approval_key);
+    assert!(client.check_approval(&approval_key));
+}
+
+#[test]
+fn test_validate_and_stage_keeps_recorded_authorization() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    set_token_owner(&env, &client, 42, &source);
+
+    let stage = || {
+        client.validate_and_stage(
+            &source,
+            &destination,
+            &42,
+            &OperationType::TRANSFER,
+            &String::from_str(&env, "US"),
+        )
+    };
+
+    let approval_key = stage().approval_key.unwrap();
+    client.record_authorization(&authority, &approval_key);
+    assert!(client.check_approval(&approval_key));
+
+    // Staging the same transfer again in the same ledger yields the same key
+    // and must not clear the recorded authorization
+    assert_eq!(stage().approval_key, Some(approval_key.clone()));
+    assert!(client.check_approval(&approval_key));
+}
+
+#[test]
+fn test_authority_min_amount_waives_authorization_below_threshold() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    rule.authority_min_amount = Some(1_000);
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    let validate = |amount: i128| {
+        client.validate_transaction_with_amount(
+            &source,
+            &destination,
+            &OperationType::TRANSFER,
+            &String::from_str(&env, "US"),
+            &amount,
+        )
+    };
+
+    let below = validate(999);
+    assert!(below.is_compliant);
+    assert!(!below.requires_authorization);
+    assert_eq!(below.authority_address, None);
+    assert_eq!(below.approval_key, None);
+    assert_eq!(below.rule_id, Some(String::from_str(&env, "US-EU")));
+
+    let above = validate(1_000);
+    assert!(above.is_compliant);
+    assert!(above.requires_authorization);
+    assert_eq!(above.authority_address, Some(authority.clone()));
+    assert!(above.approval_key.is_some());
+
+    // Without an amount the threshold cannot apply
+    let no_amount = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &String::from_str(&env, "US"),
+        &None,
+    );
+    assert!(no_amount.requires_authorization);
+}
+
+#[test]
+fn test_rule_cooldown_throttles_authorizations() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    rule.cooldown = Some(3_600);
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    let stage = |token_id: u32| {
+        set_token_owner(&env, &client, token_id, &source);
+        client
+            .validate_and_stage(
+                &source,
+                &destination,
+                &token_id,
+                &OperationType::TRANSFER,
+                &String::from_str(&env, "US"),
+            )
+            .approval_key
+            .unwrap()
+    };
+
+    env.ledger().set_timestamp(10_000);
+    let first = stage(1);
+    let second = stage(2);
+    let third = stage(3);
+    client.record_authorization(&authority, &first);
+
+    // A second approval under the same rule inside the cooldown is rejected
+    env.ledger().set_timestamp(10_000 + 3_599);
+    let result = client.try_record_authorization(&authority, &second);
+    assert_eq!(result, Err(Ok(ContractError::AuthorizationCooldown)));
+    assert!(!client.check_approval(&second));
+
+    // Once the cooldown has passed the next approval goes through and restarts it
+    env.ledger().set_timestamp(10_000 + 3_600);
+    client.record_authorization(&authority, &second);
+    assert!(client.check_approval(&second));
+    let result = client.try_record_authorization(&authority, &third);
+    assert_eq!(result, Err(Ok(ContractError::AuthorizationCooldown)));
+}
+
+#[test]
+fn test_batch_record_authorization() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let authority = Address::generate(&env);
+    let other_authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    client.add_rule(&governance, &rule);
+    let mut other_rule = transfer_rule(&env, "US-BR", "US", "BR");
+    other_rule.required_authority = Some(other_authority.clone());
+    client.add_rule(&governance, &other_rule);
+
+    let source = Address::generate(&env);
+    let eu = Address::generate(&env);
+    let br = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu, &String::from_str(&env, "EU"));
+    client.set_address_jurisdiction(&admin, &br, &String::from_str(&env, "BR"));
+    let stage = |token_id: u32, destination: &Address| {
+        set_token_owner(&env, &client, token_id, &source);
+        client
+            .validate_and_stage(
+                &source,
+                destination,
+                &token_id,
+                &OperationType::TRANSFER,
+                &String::from_str(&env, "US"),
+            )
+            .approval_key
+            .unwrap()
+    };
+
+    let expired = stage(1, &eu);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + DEFAULT_APPROVAL_WINDOW + 1);
+    let first = stage(2, &eu);
+    let second = stage(3, &eu);
+    let someone_elses = stage(4, &br);
+    let unknown = BytesN::from_array(&env, &[9u8; 32]);
+    let ruleless = BytesN::from_array(&env, &[8u8; 32]);
+    client.create_pending_approval(&ruleless, &5, &source, &eu, &OperationType::TRANSFER);
+
+    let recorded = client.batch_record_authorization(
+        &authority,
+        &vec![
+            &env,
+            first.clone(),
+            expired.clone(),
+            someone_elses.clone(),
+            unknown,
+            ruleless.clone(),
+            second.clone(),
+        ],
+    );
+    assert_eq!(recorded, vec![&env, true, false, false, false, false, true]);
+    assert!(client.check_approval(&first));
+    assert!(client.check_approval(&second));
+    assert!(!client.check_approval(&someone_elses));
+    assert!(!client.check_approval(&ruleless));
+
+    // Skipped keys stay open for their own authority
+    client.record_authorization(&other_authority, &someone_elses);
+    assert!(client.check_approval(&someone_elses));
+
+    client.pause(&admin);
+    let result = client.try_batch_record_authorization(&authority, &vec![&env, first]);
+    assert_eq!(result, Err(Ok(ContractError::Paused)));
+}
+
+#[test]
+fn test_rule_without_cooldown_allows_back_to_back_authorizations() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+
+    for token_id in [1, 2] {
+        set_token_owner(&env, &client, token_id, &source);
+        let approval_key = client
+            .validate_and_stage(
+                &source,
+                &destination,
+                &token_id,
+                &OperationType::TRANSFER,
+                &String::from_str(&env, "US"),
+            )
+            .approval_key
+            .unwrap();
+        client.record_authorization(&authority, &approval_key);
+        assert!(client.check_approval(&approval_key));
+    }
+}
+
+#[test]
+fn test_validate_and_stage_skips_staging_without_authority() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+
+    let result = client.validate_and_stage(
+        &source,
+        &destination,
+        &42,
+        &OperationType::TRANSFER,
+        &String::from_str(&env, "US"),
+    );
+    assert!(result.is_compliant);
+    assert!(!result.requires_authorization);
+    assert_eq!(result.approval_key, None);
+}
+
+fn staged_approval(
+    env: &Env,
+    admin: &Address,
+    governance: &Address,
+    client: &RegulatoryCheckClient,
+) -> (Address, BytesN<32>) {
+    let authority = Address::generate(env);
+    let mut rule = transfer_rule(env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    client.add_rule(governance, &rule);
+
+    let source = Address::generate(env);
+    let destination = Address::generate(env);
+    client.set_address_jurisdiction(admin, &source, &String::from_str(env, "US"));
+    client.set_address_jurisdiction(admin, &destination, &String::from_str(env, "EU"));
+    set_token_owner(env, client, 42, &source);
+
+    let result = client.validate_and_stage(
+        &source,
+        &destination,
+        &42,
+        &OperationType::TRANSFER,
+        &String::from_str(env, "US"),
+    );
+    (authority, result.approval_key.unwrap())
+}
+
+#[test]
+fn test_finalize_if_approved_after_authorization() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    let (authority, approval_key) = staged_approval(&env, &admin, &governance, &client);
+
+    client.record_authorization(&authority, &approval_key);
+
+    let result = client.finalize_if_approved(&carbon_contract, &approval_key);
+    assert!(result.is_compliant);
+    assert!(!result.requires_authorization);
+    assert_eq!(result.rule_id, Some(String::from_str(&env, "US-EU")));
+    assert_eq!(result.approval_key, Some(approval_key));
+}
+
+#[test]
+fn test_finalize_if_approved_cannot_be_replayed() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    let (authority, approval_key) = staged_approval(&env, &admin, &governance, &client);
+    client.record_authorization(&authority, &approval_key);
+
+    assert!(
+        client
+            .finalize_if_approved(&carbon_contract, &approval_key)
+            .is_compliant
+    );
+
+    // The approval is spent by the first execution
+    let result = client.finalize_if_approved(&carbon_contract, &approval_key);
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Invalid approval key"))
+    );
+    assert!(client.get_pending_approval(&approval_key).is_none());
+    assert!(!client.check_approval(&approval_key));
+}
+
+#[test]
+fn test_finalize_if_approved_is_limited_to_source_and_carbon_contract() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let (authority, approval_key) = staged_approval(&env, &admin, &governance, &client);
+    client.record_authorization(&authority, &approval_key);
+
+    // A third party cannot spend the approval ahead of the transfer
+    let outsider = Address::generate(&env);
+    let result = client.try_finalize_if_approved(&outsider, &approval_key);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    assert!(client.check_approval(&approval_key));
+
+    let source = client.get_pending_approval(&approval_key).unwrap().source;
+    assert!(
+        client
+            .finalize_if_approved(&source, &approval_key)
+            .is_compliant
+    );
+}
+
+#[test]
+fn test_finalize_if_approved_while_pending() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    let (_, approval_key) = staged_approval(&env, &admin, &governance, &client);
+
+    let result = client.finalize_if_approved(&carbon_contract, &approval_key);
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Approval pending"))
+    );
+}
+
+#[test]
+fn test_get_expired_rules_and_prune() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    let mut early = transfer_rule(&env, "US-EU", "US", "EU");
+    early.expires_at = Some(1_000);
+    let mut late = transfer_rule(&env, "EU-US", "EU", "US");
+    late.expires_at = Some(5_000);
+    client.add_rule(&governance, &early);
+    client.add_rule(&governance, &late);
+    client.add_rule(&governance, &transfer_rule(&env, "US-BR", "US", "BR"));
+    assert_eq!(client.get_expired_rules().len(), 0);
+
+    // A rule counts as expired from its `expires_at` timestamp onwards
+    env.ledger().set_timestamp(1_000);
+    assert_eq!(
+        client.get_expired_rules(),
+        vec![&env, early.rule_id.clone()]
+    );
+
+    let result = client.try_prune_expired_rules(&admin);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    assert_eq!(client.prune_expired_rules(&governance), 1);
+    assert!(client.get_rule(&early.rule_id).is_none());
+    assert_eq!(
+        client.get_active_rules(),
+        vec![&env, late.rule_id.clone(), String::from_str(&env, "US-BR")]
+    );
+    assert_eq!(client.get_expired_rules().len(), 0);
+
+    env.ledger().set_timestamp(6_000);
+    assert_eq!(client.get_expired_rules(), vec![&env, late.rule_id]);
+}
+
+#[test]
+fn test_count_expired_approvals() {
+    let (env, _, _, _, client) = setup_test_env();
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let stage = |seed: u8| {
+        let approval_key = BytesN::from_array(&env, &[seed; 32]);
+        client.create_pending_approval(
+            &approval_key,
+            &(seed as u32),
+            &source,
+            &destination,
+            &OperationType::TRANSFER,
+        );
+        approval_key
+    };
+    assert_eq!(client.count_expired_approvals(), 0);
+
+    env.ledger().set_timestamp(1_000);
+    stage(1);
+    let renewed = stage(2);
+    env.ledger().set_timestamp(100_000);
+    stage(3);
+
+    // Only the two approvals staged at 1_000 are past the 7-day window
+    env.ledger().set_timestamp(1_000 + 604_801);
+    assert_eq!(client.count_expired_approvals(), 2);
+
+    // Renewing refreshes the approval, while re-staging an existing key
+    // leaves it as it was and does not count it twice
+    client.renew_approval(&source, &renewed);
+    stage(1);
+    assert_eq!(client.count_expired_approvals(), 1);
+
+    env.ledger().set_timestamp(100_000 + 604_801);
+    assert_eq!(client.count_expired_approvals(), 2);
+}
+
+#[test]
+fn test_operation_windows_override_default() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let transfer_key = BytesN::from_array(&env, &[1u8; 32]);
+    let retirement_key = BytesN::from_array(&env, &[2u8; 32]);
+
+    let result = client.try_set_operation_window(&admin, &OperationType::RETIREMENT, &Some(1));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    let result = client.try_set_operation_window(&governance, &OperationType::RETIREMENT, &Some(0));
+    assert_eq!(result, Err(Ok(ContractError::InvalidExpiry)));
+
+    let thirty_days = 30 * 24 * 60 * 60;
+    client.set_operation_window(&governance, &OperationType::RETIREMENT, &Some(thirty_days));
+    assert_eq!(
+        client.get_approval_window(&OperationType::RETIREMENT),
+        thirty_days
+    );
+    assert_eq!(
+        client.get_approval_window(&OperationType::TRANSFER),
+        DEFAULT_APPROVAL_WINDOW
+    );
+
+    env.ledger().set_timestamp(1_000);
+    for (approval_key, operation) in [
+        (&transfer_key, OperationType::TRANSFER),
+        (&retirement_key, OperationType::RETIREMENT),
+    ] {
+        client.create_pending_approval(approval_key, &1, &source, &destination, &operation);
+    }
+    let authority = Address::generate(&env);
+
+    // Past the default window only the retirement approval is still open
+    env.ledger()
+        .set_timestamp(1_000 + DEFAULT_APPROVAL_WINDOW + 1);
+    let result = client.try_record_authorization(&authority, &transfer_key);
+    assert_eq!(result, Err(Ok(ContractError::ApprovalExpired)));
+    client.record_authorization(&authority, &retirement_key);
+    assert!(client.check_approval(&retirement_key));
+
+    env.ledger().set_timestamp(1_000 + thirty_days + 1);
+    assert!(!client.check_approval(&retirement_key));
+
+    // Clearing the override falls back to the default window
+    client.set_operation_window(&governance, &OperationType::RETIREMENT, &None);
+    assert_eq!(
+        client.get_approval_window(&OperationType::RETIREMENT),
+        DEFAULT_APPROVAL_WINDOW
+    );
+}
+
+#[test]
+fn test_finalize_if_approved_after_expiry() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    let (authority, approval_key) = staged_approval(&env, &admin, &governance, &client);
+
+    client.record_authorization(&authority, &approval_key);
+    env.ledger().set_timestamp(604801);
+
+    let result = client.finalize_if_approved(&carbon_contract, &approval_key);
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Approval expired"))
+    );
+}
+
+#[test]
+fn test_finalize_if_approved_unknown_key() {
+    let (env, _, _, carbon_contract, client) = setup_test_env();
+
+    let result =
+        client.finalize_if_approved(&carbon_contract, &BytesN::from_array(&env, &[7u8; 32]));
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Invalid approval key"))
+    );
+}
+
+#[test]
+fn test_error_codes_in_regulatory_range() {
+    let errors = [
+        ContractError::NotAuthorized,
+        ContractError::RuleNotFound,
+        ContractError::RuleAlreadyExists,
+        ContractError::JurisdictionNotSet,
+        ContractError::InvalidApprovalKey,
+        ContractError::ApprovalExpired,
+        ContractError::NoMatchingRule,
+        ContractError::InvalidSchemaVersion,
+        ContractError::Paused,
+        ContractError::ProjectNotVerified,
+        ContractError::InvalidExpiry,
+        ContractError::AuthorizationNotFound,
+        ContractError::InvalidRateLimit,
+        ContractError::RuleLimitReached,
+        ContractError::AuthorizationCooldown,
+        ContractError::NotTokenOwner,
+        ContractError::PairLimitExceeded,
+        ContractError::MemoAlreadyExists,
+    ];
+
+    for error in errors {
+        assert_eq!(error_source(error as u32), Some(ErrorSource::Regulatory));
+    }
+}
+
+#[test]
+fn test_health_check() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = RegulatoryCheckClient::new(&env, &env.register(RegulatoryCheck, ()));
+
+    let status = client.health_check();
+    assert!(!status.initialized);
+    assert!(!status.paused);
+    assert_eq!(status.entity_count, 0);
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    client.initialize(&admin, &governance, &Address::generate(&env));
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+
+    let status = client.health_check();
+    assert!(status.initialized);
+    assert!(!status.paused);
+    assert_eq!(status.entity_count, 1);
+
+    client.pause(&admin);
+    assert!(client.health_check().paused);
+
+    client.unpause(&admin);
+    assert!(!client.health_check().paused);
+}
+
+#[test]
+fn test_pause_blocks_validation() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    let host = String::from_str(&env, "US");
+
+    assert_eq!(
+        client.try_pause(&governance),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    client.pause(&admin);
+    let result = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Contract paused"))
+    );
+
+    let authority = Address::generate(&env);
+    let result = client.try_record_authorization(&authority, &BytesN::from_array(&env, &[1u8; 32]));
+    assert_eq!(result, Err(Ok(ContractError::Paused)));
+
+    client.unpause(&admin);
+    let result = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert!(result.is_compliant);
+}
+
+#[test]
+fn test_set_paused_by_coordinator() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let coordinator = Address::generate(&env);
+
+    assert_eq!(client.get_coordinator(), None);
+    assert_eq!(
+        client.try_set_paused_by_coordinator(&coordinator, &true),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_set_coordinator(&governance, &coordinator),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    client.set_coordinator(&admin, &coordinator);
+    assert_eq!(client.get_coordinator(), Some(coordinator.clone()));
+    assert_eq!(
+        client.try_set_paused_by_coordinator(&admin, &true),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    client.set_paused_by_coordinator(&coordinator, &true);
+    assert!(client.is_paused());
+    client.set_paused_by_coordinator(&coordinator, &false);
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_validate_transaction_with_memo_keeps_compliant_memos() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    let mut blocked = transfer_rule(&env, "EU-US", "EU", "US");
+    blocked.is_allowed = false;
+    client.add_rule(&governance, &blocked);
+
+    let us_account = Address::generate(&env);
+    let eu_account = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &us_account, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_account, &String::from_str(&env, "EU"));
+    let host = String::from_str(&env, "US");
+    let memo = String::from_str(&env, "INV-2024-0042");
+    env.ledger().set_timestamp(1_000);
+
+    let validation = client.validate_transaction_with_memo(
+        &us_account,
+        &us_account,
+        &eu_account,
+        &OperationType::TRANSFER,
+        &host,
+        &memo,
+    );
+    assert!(validation.result.is_compliant);
+    let memo_key = validation.memo_key.unwrap();
+    assert_eq!(
+        client.get_validation_memo_by_key(&memo_key),
+        Some(memo.clone())
+    );
+    assert_eq!(
+        client.get_validation_memo(&us_account, &eu_account, &OperationType::TRANSFER, &1_000),
+        Some(memo.clone())
+    );
+    // The memo belongs to that transaction only
+    assert_eq!(
+        client.get_validation_memo(&us_account, &eu_account, &OperationType::TRANSFER, &1_001),
+        None
+    );
+
+    let validation = client.validate_transaction_with_memo(
+        &eu_account,
+        &eu_account,
+        &us_account,
+        &OperationType::TRANSFER,
+        &host,
+        &memo,
+    );
+    assert!(!validation.result.is_compliant);
+    assert_eq!(validation.memo_key, None);
+    assert_eq!(
+        client.get_validation_memo(&eu_account, &us_account, &OperationType::TRANSFER, &1_000),
+        None
+    );
+
+    // The carbon asset contract may attach memos on the source's behalf
+    env.ledger().set_timestamp(2_000);
+    let validation = client.validate_transaction_with_memo(
+        &carbon_contract,
+        &us_account,
+        &eu_account,
+        &OperationType::TRANSFER,
+        &host,
+        &memo,
+    );
+    assert!(validation.memo_key.is_some());
+}
+
+#[test]
+fn test_validation_memo_cannot_be_overwritten() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    let us_account = Address::generate(&env);
+    let eu_account = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &us_account, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_account, &String::from_str(&env, "EU"));
+    let host = String::from_str(&env, "US");
+    let memo = String::from_str(&env, "INV-2024-0042");
+    let forged = String::from_str(&env, "FORGED");
+    env.ledger().set_timestamp(1_000);
+
+    // Third parties cannot attach a memo to someone else's transfer
+    let outsider = Address::generate(&env);
+    let result = client.try_validate_transaction_with_memo(
+        &outsider,
+        &us_account,
+        &eu_account,
+        &OperationType::TRANSFER,
+        &host,
+        &forged,
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    client.validate_transaction_with_memo(
+        &us_account,
+        &us_account,
+        &eu_account,
+        &OperationType::TRANSFER,
+        &host,
+        &memo,
+    );
+
+    // A second memo in the same ledger leaves the first in place
+    let result = client.try_validate_transaction_with_memo(
+        &us_account,
+        &us_account,
+        &eu_account,
+        &OperationType::TRANSFER,
+        &host,
+        &forged,
+    );
+    assert_eq!(result, Err(Ok(ContractError::MemoAlreadyExists)));
+    assert_eq!(
+        client.get_validation_memo(&us_account, &eu_account, &OperationType::TRANSFER, &1_000),
+        Some(memo)
+    );
+}
+
+#[test]
+fn test_validate_transaction_correlation_id_in_event_topics() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    let host = String::from_str(&env, "US");
+    let correlation_id = BytesN::from_array(&env, &[3u8; 32]);
+
+    client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &Some(correlation_id.clone()),
+    );
+
+    let matched = RuleMatched {
+        rule_id: String::from_str(&env, "US-EU"),
+        is_allowed: true,
+    };
+    let expected = TransactionValidated {
+        correlation_id,
+        source: source.clone(),
+        destination: destination.clone(),
+        is_compliant: true,
+        rule_id: Some(String::from_str(&env, "US-EU")),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                matched.topics(&env),
+                matched.data(&env)
+            ),
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+
+    // Without a correlation id only the rule match is reported
+    client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                matched.topics(&env),
+                matched.data(&env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_rule_hits_count_decisive_matches() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    let mut blocked = transfer_rule(&env, "EU-US", "EU", "US");
+    blocked.is_allowed = false;
+    client.add_rule(&governance, &blocked);
+    client.add_rule(&governance, &transfer_rule(&env, "US-BR", "US", "BR"));
+
+    let us_account = Address::generate(&env);
+    let eu_account = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &us_account, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_account, &String::from_str(&env, "EU"));
+    let host = String::from_str(&env, "US");
+
+    for _ in 0..3 {
+        client.validate_transaction(
+            &us_account,
+            &eu_account,
+            &OperationType::TRANSFER,
+            &host,
+            &None,
+        );
+    }
+    let result = client.validate_transaction(
+        &eu_account,
+        &us_account,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert!(!result.is_compliant);
+
+    // Prohibiting rules count too; rules nothing reached stay at zero
+    assert_eq!(client.get_rule_hits(&String::from_str(&env, "US-EU")), 3);
+    assert_eq!(client.get_rule_hits(&String::from_str(&env, "EU-US")), 1);
+    assert_eq!(client.get_rule_hits(&String::from_str(&env, "US-BR")), 0);
+
+    // Validations that never reach a rule are not counted
+    let unassigned = Address::generate(&env);
+    client.validate_transaction(
+        &unassigned,
+        &eu_account,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert_eq!(client.get_rule_hits(&String::from_str(&env, "US-EU")), 3);
+}
+
+#[test]
+fn test_get_active_rules_paged() {
+    let (env, _, governance, _, client) = setup_test_env();
+
+    for (rule_id, source) in [("R1", "US"), ("R2", "EU"), ("R3", "BR")] {
+        client.add_rule(&governance, &transfer_rule(&env, rule_id, source, "ANY"));
+    }
+
+    let page = client.get_active_rules_paged(&0, &2);
+    assert_eq!(page.total, 3);
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(
+        page.items.get(0).unwrap().rule_id,
+        String::from_str(&env, "R1")
+    );
+    assert_eq!(page.next_cursor, Some(2));
+
+    let last = client.get_active_rules_paged(&2, &2);
+    assert_eq!(last.items.len(), 1);
+    assert_eq!(
+        last.items.get(0).unwrap().rule_id,
+        String::from_str(&env, "R3")
+    );
+    assert_eq!(last.next_cursor, None);
+
+    let past_end = client.get_active_rules_paged(&5, &2);
+    assert_eq!(past_end.items.len(), 0);
+    assert_eq!(past_end.next_cursor, None);
+    assert_eq!(past_end.total, 3);
+}
+
+#[test]
+fn test_renew_approval_restarts_window() {
+    let (env, admin, _, carbon_contract, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let approval_key = BytesN::from_array(&env, &[5u8; 32]);
+
+    client.create_pending_approval(
+        &approval_key,
+        &42,
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+    );
+    client.record_authorization(&authority, &approval_key);
+    assert!(client.check_approval(&approval_key));
+
+    // Past the 7-day window the approval is no longer usable
+    env.ledger().with_mut(|li| li.timestamp += 604_801);
+    assert!(!client.check_approval(&approval_key));
+    let result = client.try_record_authorization(&authority, &approval_key);
+    assert_eq!(result, Err(Ok(ContractError::ApprovalExpired)));
+
+    // Renewal restarts the window but needs a fresh authorization
+    client.renew_approval(&source, &approval_key);
+    assert!(!client.check_approval(&approval_key));
+    assert_eq!(
+        client
+            .finalize_if_approved(&carbon_contract, &approval_key)
+            .error_message,
+        Some(String::from_str(&env, "Approval pending"))
+    );
+
+    client.record_authorization(&authority, &approval_key);
+    assert!(client.check_approval(&approval_key));
+
+    // The new window runs from the renewal, and the admin may renew too
+    env.ledger().with_mut(|li| li.timestamp += 604_801);
+    assert!(!client.check_approval(&approval_key));
+    client.renew_approval(&admin, &approval_key);
+    client.record_authorization(&authority, &approval_key);
+    assert!(
+        client
+            .finalize_if_approved(&carbon_contract, &approval_key)
+            .is_compliant
+    );
+}
+
+#[test]
+fn test_approval_lifecycle_events() {
+    let (env, _, _, _, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let approval_key = BytesN::from_array(&env, &[6u8; 32]);
+
+    client.create_pending_approval(
+        &approval_key,
+        &42,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &OperationType::TRANSFER,
+    );
+    client.record_authorization(&authority, &approval_key);
+
+    let recorded = ApprovalRecorded {
+        approval_key: approval_key.clone(),
+        authority: authority.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                recorded.topics(&env),
+                recorded.data(&env)
+            )
+        ]
+    );
+
+    // A valid approval is reported without an expiry event
+    assert!(client.check_approval(&approval_key));
+    assert_eq!(env.events().all().len(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp += 604_801);
+    assert!(!client.check_approval(&approval_key));
+    let expired = ApprovalExpired {
+        approval_key: approval_key.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expired.topics(&env),
+                expired.data(&env)
+            )
+        ]
+    );
+    assert_eq!(
+        expired.topics(&env),
+        (Symbol::new(&env, "approval"), Symbol::new(&env, "expired")).into_val(&env)
+    );
+}
+
+#[test]
+fn test_renew_approval_rejects_unknown_key_and_other_callers() {
+    let (env, _, governance, _, client) = setup_test_env();
+    let source = Address::generate(&env);
+    let approval_key = BytesN::from_array(&env, &[6u8; 32]);
+
+    let result = client.try_renew_approval(&source, &approval_key);
+    assert_eq!(result, Err(Ok(ContractError::InvalidApprovalKey)));
+
+    client.create_pending_approval(
+        &approval_key,
+        &42,
+        &source,
+        &Address::generate(&env),
+        &OperationType::TRANSFER,
+    );
+    let result = client.try_renew_approval(&governance, &approval_key);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+}
+
+#[test]
+fn test_explain_validation_distinguishes_mismatches() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "BR-EU", "BR", "EU"));
+    let mut retirement = transfer_rule(&env, "US-EU-RETIRE", "US", "EU");
+    retirement.operation = OperationType::RETIREMENT;
+    client.add_rule(&governance, &retirement);
+    let mut expired = transfer_rule(&env, "US-EU-OLD", "US", "EU");
+    expired.expires_at = Some(env.ledger().timestamp());
+    client.add_rule(&governance, &expired);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+
+    let explanations = client.explain_validation(
+        &source,
+        &destination,
+        &OperationType::RETIREMENT,
+        &String::from_str(&env, "US"),
+    );
+    assert_eq!(explanations.len(), 4);
+
+    // Same jurisdictions, different operation
+    let transfer = explanations.get(0).unwrap();
+    assert_eq!(transfer.rule_id, String::from_str(&env, "US-EU"));
+    assert!(!transfer.operation_matches);
+    assert!(transfer.source_matches && transfer.dest_matches && transfer.host_matches);
+    assert!(!transfer.matched);
+
+    let matched = explanations.get(2).unwrap();
+    assert!(matched.operation_matches && matched.source_matches);
+    assert!(!matched.expired);
+    assert!(matched.matched);
+
+    let explanations = client.explain_validation(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &String::from_str(&env, "US"),
+    );
+    assert!(explanations.get(0).unwrap().matched);
+
+    // Same operation, different source jurisdiction
+    let brazil = explanations.get(1).unwrap();
+    assert!(brazil.operation_matches);
+    assert!(!brazil.source_matches);
+    assert!(brazil.dest_matches && brazil.host_matches);
+    assert!(!brazil.matched);
+
+    // Only the expiry excludes the old rule
+    let old = explanations.get(3).unwrap();
+    assert!(old.operation_matches && old.source_matches && old.dest_matches);
+    assert!(old.expired);
+    assert!(!old.matched);
+}
+
+#[test]
+fn test_exempt_addresses_bypass_rules() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let pool = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let regular = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &regular, &String::from_str(&env, "US"));
+    let host = String::from_str(&env, "US");
+
+    let result = client.try_add_exempt(&admin, &pool);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.add_exempt(&governance, &pool);
+    client.add_exempt(&governance, &treasury);
+    assert!(client.is_exempt(&pool));
+    assert!(!client.is_exempt(&regular));
+    assert_eq!(client.get_exemption_mode(), ExemptionMode::Both);
+
+    // Exempt to exempt passes with no jurisdictions and no rules
+    let result =
+        client.validate_transaction(&pool, &treasury, &OperationType::TRANSFER, &host, &None);
+    assert!(result.is_compliant);
+    assert_eq!(result.rule_id, None);
+
+    // In `Both` mode one regular party still goes through the rules
+    let result =
+        client.validate_transaction(&pool, &regular, &OperationType::TRANSFER, &host, &None);
+    assert!(!result.is_compliant);
+
+    let result = client.try_set_exemption_mode(&admin, &ExemptionMode::Either);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_exemption_mode(&governance, &ExemptionMode::Either);
+    assert!(
+        client
+            .validate_transaction(&regular, &pool, &OperationType::TRANSFER, &host, &None)
+            .is_compliant
+    );
+
+    client.remove_exempt(&governance, &pool);
+    assert!(!client.is_exempt(&pool));
+    assert!(
+        !client
+            .validate_transaction(&regular, &pool, &OperationType::TRANSFER, &host, &None)
+            .is_compliant
+    );
+}
+
+#[test]
+fn test_source_allowlist_enforcement() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    let mut retirement = transfer_rule(&env, "US-EU-RETIRE", "US", "EU");
+    retirement.operation = OperationType::RETIREMENT;
+    client.add_rule(&governance, &retirement);
+
+    let allowed = Address::generate(&env);
+    let other = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let us = String::from_str(&env, "US");
+    client.set_address_jurisdiction(&admin, &allowed, &us);
+    client.set_address_jurisdiction(&admin, &other, &us);
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    let validate = |source: &Address, operation: OperationType| {
+        client.validate_transaction(source, &destination, &operation, &us, &None)
+    };
+
+    let result = client.try_add_allowed_source(&governance, &allowed);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.add_allowed_source(&admin, &allowed);
+    assert!(client.is_allowed_source(&allowed));
+    assert!(!client.is_allowed_source(&other));
+
+    // Not enforced: any source passes the rules as before
+    assert!(!client.is_source_allowlist_enforced());
+    assert!(validate(&other, OperationType::TRANSFER).is_compliant);
+
+    let result = client.try_set_enforce_source_allowlist(&admin, &true);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_enforce_source_allowlist(&governance, &true);
+    assert!(client.is_source_allowlist_enforced());
+
+    assert!(validate(&allowed, OperationType::TRANSFER).is_compliant);
+    let result = validate(&other, OperationType::TRANSFER);
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Source not allowlisted"))
+    );
+    // Retirements are not transfers and are left alone
+    assert!(validate(&other, OperationType::RETIREMENT).is_compliant);
+
+    client.remove_allowed_source(&admin, &allowed);
+    assert!(!validate(&allowed, OperationType::TRANSFER).is_compliant);
+
+    client.set_enforce_source_allowlist(&governance, &false);
+    assert!(validate(&allowed, OperationType::TRANSFER).is_compliant);
+}
+
+#[test]
+fn test_recategorize_rule_moves_between_indexes() {
+    let (env, _, governance, _, client) = setup_test_env();
+    let sanctions = String::from_str(&env, "sanctions");
+    let tax = String::from_str(&env, "tax");
+
+    let mut us_eu = transfer_rule(&env, "US-EU", "US", "EU");
+    us_eu.category = Some(sanctions.clone());
+    client.add_rule(&governance, &us_eu);
+    let mut eu_us = transfer_rule(&env, "EU-US", "EU", "US");
+    eu_us.category = Some(sanctions.clone());
+    client.add_rule(&governance, &eu_us);
+    client.add_rule(&governance, &transfer_rule(&env, "BR-EU", "BR", "EU"));
+    assert_eq!(
+        client.get_rules_by_category(&sanctions),
+        vec![&env, us_eu.rule_id.clone(), eu_us.rule_id.clone()]
+    );
+
+    let result = client.try_recategorize_rule(&Address::generate(&env), &us_eu.rule_id, &tax);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    let result =
+        client.try_recategorize_rule(&governance, &String::from_str(&env, "MISSING"), &tax);
+    assert_eq!(result, Err(Ok(ContractError::RuleNotFound)));
+
+    client.recategorize_rule(&governance, &us_eu.rule_id, &tax);
+    assert_eq!(
+        client.get_rules_by_category(&sanctions),
+        vec![&env, eu_us.rule_id.clone()]
+    );
+    assert_eq!(
+        client.get_rules_by_category(&tax),
+        vec![&env, us_eu.rule_id.clone()]
+    );
+    assert_eq!(
+        client.get_rule(&us_eu.rule_id).unwrap().category,
+        Some(tax.clone())
+    );
+
+    // An uncategorized rule joins its first category
+    let br_eu = String::from_str(&env, "BR-EU");
+    client.recategorize_rule(&governance, &br_eu, &tax);
+    assert_eq!(
+        client.get_rules_by_category(&tax),
+        vec![&env, us_eu.rule_id.clone(), br_eu.clone()]
+    );
+
+    // Rules leave the index when they stop being active
+    client.archive_rule(&governance, &us_eu.rule_id);
+    client.deactivate_rule(&governance, &eu_us.rule_id);
+    assert_eq!(client.get_rules_by_category(&tax), vec![&env, br_eu]);
+    assert!(client.get_rules_by_category(&sanctions).is_empty());
+    client.reactivate_rule(&governance, &us_eu.rule_id);
+    assert_eq!(client.get_rules_by_category(&tax).len(), 2);
+}
+
+#[test]
+fn test_get_covered_jurisdictions() {
+    let (env, _, governance, _, client) = setup_test_env();
+    assert!(client.get_covered_jurisdictions().is_empty());
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "EU-US", "EU", "US"));
+    client.add_rule(&governance, &transfer_rule(&env, "ANY-BR", "ANY", "BR"));
+    let mut hosted = transfer_rule(&env, "UK-ANY", "UK", "ANY");
+    hosted.host_jur = String::from_str(&env, "SG");
+    client.add_rule(&governance, &hosted);
+    client.add_rule(&governance, &transfer_rule(&env, "CN-JP", "CN", "JP"));
+    client.deactivate_rule(&governance, &String::from_str(&env, "CN-JP"));
+
+    assert_eq!(
+        client.get_covered_jurisdictions(),
+        vec![
+            &env,
+            String::from_str(&env, "US"),
+            String::from_str(&env, "EU"),
+            String::from_str(&env, "BR"),
+            String::from_str(&env, "UK"),
+            String::from_str(&env, "SG"),
+        ]
+    );
+}
+
+#[test]
+fn test_get_rules_affecting_address() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "EU-US", "EU", "US"));
+    client.add_rule(&governance, &transfer_rule(&env, "ANY-BR", "ANY", "BR"));
+    client.add_rule(&governance, &transfer_rule(&env, "UK-ANY", "UK", "ANY"));
+    client.add_rule(&governance, &transfer_rule(&env, "CN-JP", "CN", "JP"));
+    client.add_rule(&governance, &transfer_rule(&env, "US-SG", "US", "SG"));
+    client.deactivate_rule(&governance, &String::from_str(&env, "US-SG"));
+
+    let account = Address::generate(&env);
+    assert!(client.get_rules_affecting_address(&account).is_empty());
+
+    client.set_address_jurisdiction(&admin, &account, &String::from_str(&env, "US"));
+    assert_eq!(
+        client.get_rules_affecting_address(&account),
+        vec![
+            &env,
+            String::from_str(&env, "US-EU"),
+            String::from_str(&env, "EU-US"),
+            String::from_str(&env, "ANY-BR"),
+            String::from_str(&env, "UK-ANY"),
+        ]
+    );
+}
+
+#[test]
+fn test_get_rules_by_decision() {
+    let (env, _, governance, _, client) = setup_test_env();
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    let mut deny = transfer_rule(&env, "US-CN", "US", "CN");
+    deny.is_allowed = false;
+    client.add_rule(&governance, &deny);
+    client.add_rule(&governance, &transfer_rule(&env, "EU-US", "EU", "US"));
+    let mut deny = transfer_rule(&env, "ANY-KP", "ANY", "KP");
+    deny.is_allowed = false;
+    client.add_rule(&governance, &deny);
+    let mut inactive = transfer_rule(&env, "US-RU", "US", "RU");
+    inactive.is_allowed = false;
+    client.add_rule(&governance, &inactive);
+    client.deactivate_rule(&governance, &String::from_str(&env, "US-RU"));
+
+    assert_eq!(
+        client.get_rules_by_decision(&true),
+        vec![
+            &env,
+            String::from_str(&env, "US-EU"),
+            String::from_str(&env, "EU-US"),
+        ]
+    );
+    assert_eq!(
+        client.get_rules_by_decision(&false),
+        vec![
+            &env,
+            String::from_str(&env, "US-CN"),
+            String::from_str(&env, "ANY-KP"),
+        ]
+    );
+}
+
+#[test]
+fn test_preview_jurisdiction_change() {
+    let (env, admin, governance, _, client) = setup_test_env();
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "BR-EU", "BR", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "EU-BR", "EU", "BR"));
+    let mut retirement = transfer_rule(&env, "BR-EU-RETIRE", "BR", "EU");
+    retirement.operation = OperationType::RETIREMENT;
+    client.add_rule(&governance, &retirement);
+    let mut expired = transfer_rule(&env, "BR-EU-OLD", "BR", "EU");
+    expired.expires_at = Some(env.ledger().timestamp());
+    client.add_rule(&governance, &expired);
+
+    let account = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let unassigned = Address::generate(&env);
+    let us = String::from_str(&env, "US");
+    client.set_address_jurisdiction(&admin, &account, &us);
+    client.set_address_jurisdiction(&admin, &partner, &String::from_str(&env, "EU"));
+    let partners = vec![&env, partner.clone(), unassigned];
+
+    // Previewing the current jurisdiction shows the rules matching today
+    assert_eq!(
+        client.preview_jurisdiction_change(&account, &us, &partners),
+        vec![&env, String::from_str(&env, "US-EU")]
+    );
+
+    // Transfer rules in both directions match after the move; retirement
+    // and expired rules do not
+    assert_eq!(
+        client.preview_jurisdiction_change(&account, &String::from_str(&env, "BR"), &partners),
+        vec![
+            &env,
+            String::from_str(&env, "BR-EU"),
+            String::from_str(&env, "EU-BR")
+        ]
+    );
+    assert_eq!(client.get_address_jurisdiction(&account), Some(us));
+
+    assert!(client
+        .preview_jurisdiction_change(&account, &String::from_str(&env, "CN"), &partners)
+        .is_empty());
+}
+
+#[test]
+fn test_standing_authorization_covers_repeat_transfers() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let other_destination = Address::generate(&env);
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+    client.set_address_jurisdiction(&admin, &source, &us);
+    client.set_address_jurisdiction(&admin, &destination, &eu);
+    client.set_address_jurisdiction(&admin, &other_destination, &eu);
+
+    let expires_at = env.ledger().timestamp() + 1_000;
+    client.create_standing_authorization(
+        &authority,
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &expires_at,
+    );
+
+    // Every transfer in the corridor is covered, with nothing staged
+    for _ in 0..3 {
+        let result =
+            client.validate_and_stage(&source, &destination, &42, &OperationType::TRANSFER, &us);
+        assert!(result.is_compliant);
+        assert!(!result.requires_authorization);
+        assert_eq!(result.authority_address, Some(authority.clone()));
+        assert_eq!(result.approval_key, None);
+        env.ledger().with_mut(|li| li.timestamp += 100);
+    }
+
+    // Other destinations still need a per-transaction approval
+    let result = client.validate_transaction(
+        &source,
+        &other_destination,
+        &OperationType::TRANSFER,
+        &us,
+        &None,
+    );
+    assert!(result.requires_authorization);
+
+    // Past expiry the rule's authority requirement applies again
+    env.ledger().with_mut(|li| li.timestamp = expires_at);
+    let result =
+        client.validate_transaction(&source, &destination, &OperationType::TRANSFER, &us, &None);
+    assert!(result.requires_authorization);
+}
+
+#[test]
+fn test_revoke_standing_authorization() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let mut rule = transfer_rule(&env, "US-EU", "US", "EU");
+    rule.required_authority = Some(authority.clone());
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let us = String::from_str(&env, "US");
+    client.set_address_jurisdiction(&admin, &source, &us);
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+
+    let result = client.try_create_standing_authorization(
+        &authority,
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &env.ledger().timestamp(),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidExpiry)));
+
+    client.create_standing_authorization(
+        &authority,
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &(env.ledger().timestamp() + 1_000),
+    );
+    let result =
+        client.validate_transaction(&source, &destination, &OperationType::TRANSFER, &us, &None);
+    assert!(!result.requires_authorization);
+
+    client.revoke_standing_authorization(
+        &authority,
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+    );
+    assert_eq!(
+        client.get_standing_authorization(
+            &authority,
+            &source,
+            &destination,
+            &OperationType::TRANSFER
+        ),
+        None
+    );
+    let result =
+        client.validate_transaction(&source, &destination, &OperationType::TRANSFER, &us, &None);
+    assert!(result.requires_authorization);
+
+    let result = client.try_revoke_standing_authorization(
+        &authority,
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+    );
+    assert_eq!(result, Err(Ok(ContractError::AuthorizationNotFound)));
+}
+
+#[test]
+fn test_get_rules_by_authority() {
+    let (env, _, governance, _, client) = setup_test_env();
+    let shared = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    for (rule_id, authority) in [
+        ("US-EU", Some(shared.clone())),
+        ("EU-US", Some(other.clone())),
+        ("US-BR", None),
+        ("BR-US", Some(shared.clone())),
+    ] {
+        let mut rule = transfer_rule(&env, rule_id, "US", "EU");
+        rule.required_authority = authority;
+        client.add_rule(&governance, &rule);
+    }
+
+    assert_eq!(
+        client.get_rules_by_authority(&shared),
+        vec![
+            &env,
+            String::from_str(&env, "US-EU"),
+            String::from_str(&env, "BR-US")
+        ]
+    );
+    assert_eq!(
+        client.get_rules_by_authority(&other),
+        vec![&env, String::from_str(&env, "EU-US")]
+    );
+
+    // Deactivated rules no longer count as depending on the authority
+    client.deactivate_rule(&governance, &String::from_str(&env, "US-EU"));
+    assert_eq!(
+        client.get_rules_by_authority(&shared),
+        vec![&env, String::from_str(&env, "BR-US")]
+    );
+    assert_eq!(
+        client
+            .get_rules_by_authority(&Address::generate(&env))
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_rotate_authority_updates_referencing_rules() {
+    let (env, _, governance, _, client) = setup_test_env();
+    let old_authority = Address::generate(&env);
+    let new_authority = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    for (rule_id, authority) in [
+        ("US-EU", Some(old_authority.clone())),
+        ("EU-US", Some(other.clone())),
+        ("US-BR", None),
+        ("BR-US", Some(old_authority.clone())),
+    ] {
+        let mut rule = transfer_rule(&env, rule_id, "US", "EU");
+        rule.required_authority = authority;
+        client.add_rule(&governance, &rule);
+    }
+
+    let result = client.try_rotate_authority(&old_authority, &old_authority, &new_authority);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    assert_eq!(
+        client.rotate_authority(&governance, &old_authority, &new_authority),
+        2
+    );
+
+    let rotated = vec![
+        &env,
+        String::from_str(&env, "US-EU"),
+        String::from_str(&env, "BR-US"),
+    ];
+    let expected = AuthorityRotated {
+        old_authority: old_authority.clone(),
+        new_authority: new_authority.clone(),
+        rule_ids: rotated.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+
+    assert_eq!(client.get_rules_by_authority(&new_authority), rotated);
+    assert_eq!(client.get_rules_by_authority(&old_authority).len(), 0);
+    let untouched = client.get_rule(&String::from_str(&env, "EU-US")).unwrap();
+    assert_eq!(untouched.required_authority, Some(other));
+    let unrestricted = client.get_rule(&String::from_str(&env, "US-BR")).unwrap();
+    assert_eq!(unrestricted.required_authority, None);
+}
+
+#[test]
+fn test_set_address_jurisdiction_emits_change_event() {
+    let (env, admin, _, _, client) = setup_test_env();
+    let account = Address::generate(&env);
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+
+    client.set_address_jurisdiction(&admin, &account, &us);
+    let expected = JurisdictionChanged {
+        account: account.clone(),
+        old_jurisdiction: None,
+        new_jurisdiction: us.clone(),
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+
+    client.set_address_jurisdiction(&admin, &account, &eu);
+    let expected = JurisdictionChanged {
+        account: account.clone(),
+        old_jurisdiction: Some(us),
+        new_jurisdiction: eu,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_get_address_jurisdictions() {
+    let (env, admin, _, _, client) = setup_test_env();
+    let us_account = Address::generate(&env);
+    let unassigned = Address::generate(&env);
+    let eu_account = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &us_account, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_account, &String::from_str(&env, "EU"));
+
+    let jurisdictions = client.get_address_jurisdictions(&vec![
+        &env,
+        us_account.clone(),
+        unassigned,
+        eu_account,
+        us_account,
+    ]);
+    assert_eq!(
+        jurisdictions,
+        vec![
+            &env,
+            Some(String::from_str(&env, "US")),
+            None,
+            Some(String::from_str(&env, "EU")),
+            Some(String::from_str(&env, "US")),
+        ]
+    );
+
+    assert_eq!(client.get_address_jurisdictions(&Vec::new(&env)).len(), 0);
+}
+
+#[test]
+fn test_find_unassigned() {
+    let (env, admin, _, _, client) = setup_test_env();
+    let us_account = Address::generate(&env);
+    let first_unassigned = Address::generate(&env);
+    let eu_account = Address::generate(&env);
+    let second_unassigned = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &us_account, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_account, &String::from_str(&env, "EU"));
+
+    let unassigned = client.find_unassigned(&vec![
+        &env,
+        us_account.clone(),
+        first_unassigned.clone(),
+        eu_account.clone(),
+        second_unassigned.clone(),
+    ]);
+    assert_eq!(unassigned, vec![&env, first_unassigned, second_unassigned]);
+
+    assert_eq!(
+        client
+            .find_unassigned(&vec![&env, us_account, eu_account])
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_pending_approval_records_ledger_sequence() {
+    let (env, _, _, _, client) = setup_test_env();
+    let approval_key = BytesN::from_array(&env, &[7u8; 32]);
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+
+    client.create_pending_approval(
+        &approval_key,
+        &42,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &OperationType::TRANSFER,
+    );
+
+    let pending = client.get_pending_approval(&approval_key).unwrap();
+    assert_eq!(pending.created_ledger, 1_000);
+    assert!(!pending.approved);
+    assert!(client
+        .get_pending_approval(&BytesN::from_array(&env, &[8u8; 32]))
+        .is_none());
+}
+
+#[test]
+fn test_max_approval_ledger_age() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let source = Address::generate(&env);
+    let approval_key = BytesN::from_array(&env, &[7u8; 32]);
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+
+    let result = client.try_set_max_approval_ledger_age(&governance, &Some(100));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_max_approval_ledger_age(&admin, &Some(100));
+    assert_eq!(client.get_max_approval_ledger_age(), Some(100));
+
+    client.create_pending_approval(
+        &approval_key,
+        &42,
+        &source,
+        &Address::generate(&env),
+        &OperationType::TRANSFER,
+    );
+    client.record_authorization(&authority, &approval_key);
+
+    // Usable up to the limit, well inside the 7-day window
+    env.ledger().with_mut(|li| li.sequence_number = 1_100);
+    assert!(client.check_approval(&approval_key));
+
+    env.ledger().with_mut(|li| li.sequence_number = 1_101);
+    assert!(!client.check_approval(&approval_key));
+    assert_eq!(
+        client
+            .finalize_if_approved(&carbon_contract, &approval_key)
+            .error_message,
+        Some(String::from_str(&env, "Approval expired"))
+    );
+
+    // Renewal restarts the ledger count as well
+    client.renew_approval(&source, &approval_key);
+    assert_eq!(
+        client
+            .get_pending_approval(&approval_key)
+            .unwrap()
+            .created_ledger,
+        1_101
+    );
+    client.record_authorization(&authority, &approval_key);
+    assert!(client.check_approval(&approval_key));
+
+    // Without a limit only the time window applies
+    env.ledger().with_mut(|li| li.sequence_number = 5_000);
+    assert!(!client.check_approval(&approval_key));
+    client.set_max_approval_ledger_age(&admin, &None);
+    assert!(client.check_approval(&approval_key));
+}
+
+#[test]
+fn test_archive_and_reactivate_rule() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let rule_id = String::from_str(&env, "US-EU");
+    let rule = transfer_rule(&env, "US-EU", "US", "EU");
+    client.add_rule(&governance, &rule);
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+    let host = String::from_str(&env, "US");
+
+    client.archive_rule(&governance, &rule_id);
+    assert!(client.get_rule(&rule_id).is_none());
+    assert_eq!(
+        client.get_archived_rule(&rule_id).unwrap().dest_jur,
+        rule.dest_jur
+    );
+    assert_eq!(client.get_active_rules().len(), 0);
+
+    // Archived rules take no part in validation
+    let result = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(result.rule_id, None);
+
+    client.reactivate_rule(&governance, &rule_id);
+    assert_eq!(client.get_rule(&rule_id).unwrap().dest_jur, rule.dest_jur);
+    assert!(client.get_archived_rule(&rule_id).is_none());
+    assert_eq!(client.get_active_rules(), vec![&env, rule_id.clone()]);
+
+    let result = client.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &host,
+        &None,
+    );
+    assert!(result.is_compliant);
+    assert_eq!(result.rule_id, Some(rule_id));
+}
+
+#[test]
+fn test_archive_rule_errors() {
+    let (env, _, governance, _, client) = setup_test_env();
+    let rule_id = String::from_str(&env, "US-EU");
+    let outsider = Address::generate(&env);
+
+    assert_eq!(
+        client.try_archive_rule(&governance, &rule_id),
+        Err(Ok(ContractError::RuleNotFound))
+    );
+    assert_eq!(
+        client.try_reactivate_rule(&governance, &rule_id),
+        Err(Ok(ContractError::RuleNotFound))
+    );
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    assert_eq!(
+        client.try_archive_rule(&outsider, &rule_id),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    client.archive_rule(&governance, &rule_id);
+    assert_eq!(
+        client.try_reactivate_rule(&outsider, &rule_id),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    // A replacement added under the same id blocks reactivation and re-archiving
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    assert_eq!(
+        client.try_reactivate_rule(&governance, &rule_id),
+        Err(Ok(ContractError::RuleAlreadyExists))
+    );
+    assert_eq!(
+        client.try_archive_rule(&governance, &rule_id),
+        Err(Ok(ContractError::RuleAlreadyExists))
+    );
+
+    // Hard delete leaves the archived copy untouched
+    client.deactivate_rule(&governance, &rule_id);
+    assert!(client.get_rule(&rule_id).is_none());
+    assert!(client.get_archived_rule(&rule_id).is_some());
+}
+
+#[test]
+fn test_pair_rate_limit_blocks_and_resets() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "US-BR", "US", "BR"));
+
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+    let source = Address::generate(&env);
+    let eu_dest = Address::generate(&env);
+    let br_dest = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &us);
+    client.set_address_jurisdiction(&admin, &eu_dest, &eu);
+    client.set_address_jurisdiction(&admin, &br_dest, &String::from_str(&env, "BR"));
+
+    let limit = PairRateLimit {
+        max_count: 2,
+        window_seconds: 3_600,
+    };
+    client.set_pair_rate_limit(&governance, &us, &eu, &Some(limit.clone()));
+    assert_eq!(client.get_pair_rate_limit(&us, &eu), Some(limit));
+
+    let validate = |destination: &Address| {
+        client.validate_transaction(&source, destination, &OperationType::TRANSFER, &us, &None)
+    };
+    let record = |destination: &Address| {
+        client.try_record_pair_transfer(&carbon_contract, &source, destination)
+    };
+
+    // Validating alone counts nothing
+    for _ in 0..3 {
+        assert!(validate(&eu_dest).is_compliant);
+    }
+    assert_eq!(client.get_pair_count(&us, &eu), 0);
+
+    assert_eq!(record(&eu_dest), Ok(Ok(())));
+    assert_eq!(record(&eu_dest), Ok(Ok(())));
+    assert_eq!(client.get_pair_count(&us, &eu), 2);
+
+    let result = validate(&eu_dest);
+    assert!(!result.is_compliant);
+    assert_eq!(result.rule_id, Some(String::from_str(&env, "US-EU")));
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Pair rate limit exceeded"))
+    );
+    assert_eq!(record(&eu_dest), Err(Ok(ContractError::PairLimitExceeded)));
+    assert_eq!(client.get_pair_count(&us, &eu), 2);
+
+    // Other corridors are unaffected
+    for _ in 0..5 {
+        assert_eq!(record(&br_dest), Ok(Ok(())));
+        assert!(validate(&br_dest).is_compliant);
+    }
+
+    // The counter starts over once the window has elapsed
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    assert_eq!(client.get_pair_count(&us, &eu), 0);
+    assert!(validate(&eu_dest).is_compliant);
+    assert_eq!(record(&eu_dest), Ok(Ok(())));
+    assert_eq!(client.get_pair_count(&us, &eu), 1);
+
+    // Removing the limit lifts the cap
+    client.set_pair_rate_limit(&governance, &us, &eu, &None);
+    for _ in 0..3 {
+        assert_eq!(record(&eu_dest), Ok(Ok(())));
+        assert!(validate(&eu_dest).is_compliant);
+    }
+    assert_eq!(client.get_pair_count(&us, &eu), 0);
+}
+
+#[test]
+fn test_record_pair_transfer_requires_carbon_contract() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &us);
+    client.set_address_jurisdiction(&admin, &destination, &eu);
+    client.set_pair_rate_limit(
+        &governance,
+        &us,
+        &eu,
+        &Some(PairRateLimit {
+            max_count: 1,
+            window_seconds: 3_600,
+        }),
+    );
+
+    // Any other caller is refused, leaving the allowance untouched
+    for caller in [source.clone(), admin, Address::generate(&env)] {
+        assert_eq!(
+            client.try_record_pair_transfer(&caller, &source, &destination),
+            Err(Ok(ContractError::NotAuthorized))
+        );
+    }
+    assert_eq!(client.get_pair_count(&us, &eu), 0);
+}
+
+#[test]
+fn test_pair_rate_limit_with_huge_window() {
+    let (env, admin, governance, carbon_contract, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &us);
+    client.set_address_jurisdiction(&admin, &destination, &eu);
+
+    env.ledger().set_timestamp(1_000);
+    client.set_pair_rate_limit(
+        &governance,
+        &us,
+        &eu,
+        &Some(PairRateLimit {
+            max_count: 1,
+            window_seconds: u64::MAX,
+        }),
+    );
+
+    // The window never ends rather than overflowing
+    let validate =
+        || client.validate_transaction(&source, &destination, &OperationType::TRANSFER, &us, &None);
+    assert!(validate().is_compliant);
+    client.record_pair_transfer(&carbon_contract, &source, &destination);
+    assert!(!validate().is_compliant);
+    assert_eq!(client.get_pair_count(&us, &eu), 1);
+}
+
+#[test]
+fn test_set_pair_rate_limit_validation() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+
+    let result = client.try_set_pair_rate_limit(
+        &admin,
+        &us,
+        &eu,
+        &Some(PairRateLimit {
+            max_count: 1,
+            window_seconds: 60,
+        }),
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    for (max_count, window_seconds) in [(0, 60), (1, 0)] {
+        let result = client.try_set_pair_rate_limit(
+            &governance,
+            &us,
+            &eu,
+            &Some(PairRateLimit {
+                max_count,
+                window_seconds,
+            }),
+        );
+        assert_eq!(result, Err(Ok(ContractError::InvalidRateLimit)));
+    }
+    assert_eq!(client.get_pair_rate_limit(&us, &eu), None);
+}
+
+#[test]
+fn test_huge_approval_window_and_grace_period_never_expire() {
+    let (env, _, governance, carbon_contract, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let approval_key = BytesN::from_array(&env, &[9u8; 32]);
+    env.ledger().set_timestamp(1_000);
+
+    client.set_operation_window(&governance, &OperationType::TRANSFER, &Some(u64::MAX));
+    client.set_approval_grace_period(&governance, &u64::MAX);
+    client.create_pending_approval(
+        &approval_key,
+        &42,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &OperationType::TRANSFER,
+    );
+
+    // The expiry saturates instead of overflowing
+    client.record_authorization(&authority, &approval_key);
+    assert!(client.check_approval(&approval_key));
+    assert!(
+        client
+            .finalize_if_approved(&carbon_contract, &approval_key)
+            .is_compliant
+    );
+    assert_eq!(client.count_expired_approvals(), 0);
+}
+
+#[test]
+fn test_approval_grace_period_extends_window() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let approval_key = BytesN::from_array(&env, &[8u8; 32]);
+    env.ledger().set_timestamp(1_000);
+
+    assert_eq!(client.get_approval_grace_period(), 0);
+    let result = client.try_set_approval_grace_period(&admin, &60);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    client.create_pending_approval(
+        &approval_key,
+        &42,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &OperationType::TRANSFER,
+    );
+    client.record_authorization(&authority, &approval_key);
+
+    // Without a grace period the 7-day window is a hard cutoff
+    env.ledger().set_timestamp(1_000 + 604_800);
+    assert!(client.check_approval(&approval_key));
+    env.ledger().set_timestamp(1_000 + 604_801);
+    assert!(!client.check_approval(&approval_key));
+    assert_eq!(
+        client.try_record_authorization(&authority, &approval_key),
+        Err(Ok(ContractError::ApprovalExpired))
+    );
+
+    client.set_approval_grace_period(&governance, &60);
+    assert_eq!(client.get_approval_grace_period(), 60);
+    env.ledger().set_timestamp(1_000 + 604_860);
+    assert!(client.check_approval(&approval_key));
+    client.record_authorization(&authority, &approval_key);
+
+    env.ledger().set_timestamp(1_000 + 604_861);
+    assert!(!client.check_approval(&approval_key));
+    assert_eq!(
+        client.try_record_authorization(&authority, &approval_key),
+        Err(Ok(ContractError::ApprovalExpired))
+    );
+}
+
+#[test]
+fn test_rule_limit() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    assert_eq!(client.get_rule_limit(), DEFAULT_MAX_RULES);
+
+    let result = client.try_set_rule_limit(&admin, &2);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_rule_limit(&governance, &2);
+    assert_eq!(client.get_rule_limit(), 2);
+
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "EU-US", "EU", "US"));
+    let result = client.try_add_rule(&governance, &transfer_rule(&env, "US-BR", "US", "BR"));
+    assert_eq!(result, Err(Ok(ContractError::RuleLimitReached)));
+    assert!(client.get_rule(&String::from_str(&env, "US-BR")).is_none());
+    assert_eq!(client.get_active_rules().len(), 2);
+
+    // Archiving frees a slot, and reactivating counts against the limit too
+    client.archive_rule(&governance, &String::from_str(&env, "US-EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "US-BR", "US", "BR"));
+    let result = client.try_reactivate_rule(&governance, &String::from_str(&env, "US-EU"));
+    assert_eq!(result, Err(Ok(ContractError::RuleLimitReached)));
+
+    client.set_rule_limit(&governance, &3);
+    client.reactivate_rule(&governance, &String::from_str(&env, "US-EU"));
+    assert_eq!(client.get_active_rules().len(), 3);
+}
+
+#[test]
+fn test_validate_transaction_returns_approval_key_when_authorization_required() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let authority = Address::generate(&env);
+    let mut gated = transfer_rule(&env, "US-EU", "US", "EU");
+    gated.required_authority = Some(authority.clone());
+    client.add_rule(&governance, &gated);
+    client.add_rule(&governance, &transfer_rule(&env, "US-BR", "US", "BR"));
+
+    let source = Address::generate(&env);
+    let eu_dest = Address::generate(&env);
+    let br_dest = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_dest, &String::from_str(&env, "EU"));
+    client.set_address_jurisdiction(&admin, &br_dest, &String::from_str(&env, "BR"));
+    let host = String::from_str(&env, "US");
+
+    let validate = |destination: &Address| {
+        client.validate_transaction(&source, destination, &OperationType::TRANSFER, &host, &None)
+    };
+
+    let result = validate(&eu_dest);
+    assert!(result.requires_authorization);
+    let approval_key = result.approval_key.unwrap();
+
+    // The key is derived from the transaction, so repeating it gives the same key
+    assert_eq!(validate(&eu_dest).approval_key, Some(approval_key.clone()));
+
+    // The caller can stage and approve under the key it was given
+    client.create_pending_approval(
+        &approval_key,
+        &0,
+        &source,
+        &eu_dest,
+        &OperationType::TRANSFER,
+    );
+    client.record_authorization(&authority, &approval_key);
+    assert!(client.check_approval(&approval_key));
+
+    // Only the authorization-required branch carries a key
+    let result = validate(&br_dest);
+    assert!(result.is_compliant);
+    assert!(!result.requires_authorization);
+    assert_eq!(result.approval_key, None);
+
+    let unknown = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &unknown, &String::from_str(&env, "CN"));
+    let result = validate(&unknown);
+    assert!(!result.is_compliant);
+    assert_eq!(result.approval_key, None);
+}
+
+#[test]
+fn test_validate_fanout_reports_each_destination() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    let mut prohibited = transfer_rule(&env, "US-KP", "US", "KP");
+    prohibited.is_allowed = false;
+    client.add_rule(&governance, &prohibited);
+
+    let source = Address::generate(&env);
+    let eu_dest = Address::generate(&env);
+    let kp_dest = Address::generate(&env);
+    let unassigned = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_dest, &String::from_str(&env, "EU"));
+    client.set_address_jurisdiction(&admin, &kp_dest, &String::from_str(&env, "KP"));
+
+    let results = client.validate_fanout(
+        &source,
+        &vec![&env, eu_dest.clone(), kp_dest, unassigned, eu_dest],
+        &OperationType::TRANSFER,
+        &String::from_str(&env, "US"),
+    );
+    assert_eq!(results.len(), 4);
+
+    let eu = results.get(0).unwrap();
+    assert!(eu.is_compliant);
+    assert_eq!(eu.rule_id, Some(String::from_str(&env, "US-EU")));
+
+    let kp = results.get(1).unwrap();
+    assert!(!kp.is_compliant);
+    assert_eq!(kp.rule_id, Some(String::from_str(&env, "US-KP")));
+
+    let missing = results.get(2).unwrap();
+    assert!(!missing.is_compliant);
+    assert_eq!(
+        missing.error_message,
+        Some(String::from_str(&env, "Jurisdiction not set for address"))
+    );
+
+    assert_eq!(results.get(3).unwrap(), eu);
+    assert_eq!(
+        client
+            .validate_fanout(
+                &source,
+                &Vec::new(&env),
+                &OperationType::TRANSFER,
+                &String::from_str(&env, "US"),
+            )
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_default_authority_applies_only_to_opted_in_rules() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let default_authority = Address::generate(&env);
+    let own_authority = Address::generate(&env);
+
+    let mut opted_in = transfer_rule(&env, "US-EU", "US", "EU");
+    opted_in.use_default_authority = true;
+    client.add_rule(&governance, &opted_in);
+    client.add_rule(&governance, &transfer_rule(&env, "US-BR", "US", "BR"));
+    let mut explicit = transfer_rule(&env, "US-JP", "US", "JP");
+    explicit.required_authority = Some(own_authority.clone());
+    explicit.use_default_authority = true;
+    client.add_rule(&governance, &explicit);
+
+    let source = Address::generate(&env);
+    let eu_dest = Address::generate(&env);
+    let br_dest = Address::generate(&env);
+    let jp_dest = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_dest, &String::from_str(&env, "EU"));
+    client.set_address_jurisdiction(&admin, &br_dest, &String::from_str(&env, "BR"));
+    client.set_address_jurisdiction(&admin, &jp_dest, &String::from_str(&env, "JP"));
+    let host = String::from_str(&env, "US");
+    let validate = |destination: &Address| {
+        client.validate_transaction(&source, destination, &OperationType::TRANSFER, &host, &None)
+    };
+
+    // Opting in without a default set leaves the rule unrestricted
+    assert!(!validate(&eu_dest).requires_authorization);
+
+    let result = client.try_set_default_authority(&admin, &Some(default_authority.clone()));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_default_authority(&governance, &Some(default_authority.clone()));
+    assert_eq!(
+        client.get_default_authority(),
+        Some(default_authority.clone())
+    );
+
+    let result = validate(&eu_dest);
+    assert!(result.requires_authorization);
+    assert_eq!(result.authority_address, Some(default_authority));
+
+    // Rules that did not opt in, or name their own authority, are unaffected
+    let result = validate(&br_dest);
+    assert!(!result.requires_authorization);
+    assert_eq!(result.authority_address, None);
+    assert_eq!(validate(&jp_dest).authority_address, Some(own_authority));
+
+    // Changing the default moves every opted-in rule at once
+    let replacement = Address::generate(&env);
+    client.set_default_authority(&governance, &Some(replacement.clone()));
+    assert_eq!(validate(&eu_dest).authority_address, Some(replacement));
+
+    client.set_default_authority(&governance, &None);
+    assert!(!validate(&eu_dest).requires_authorization);
+}
+
+#[test]
+fn test_validate_and_record_keeps_decision() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    let mut prohibited = transfer_rule(&env, "US-KP", "US", "KP");
+    prohibited.is_allowed = false;
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &prohibited);
+
+    let source = Address::generate(&env);
+    let eu_dest = Address::generate(&env);
+    let kp_dest = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &eu_dest, &String::from_str(&env, "EU"));
+    client.set_address_jurisdiction(&admin, &kp_dest, &String::from_str(&env, "KP"));
+    let host = String::from_str(&env, "US");
+    let allowed_key = BytesN::from_array(&env, &[1u8; 32]);
+    let blocked_key = BytesN::from_array(&env, &[2u8; 32]);
+
+    assert_eq!(client.get_validation_record(&allowed_key), None);
+
+    // Third parties cannot claim a key for someone else's transaction
+    let outsider = Address::generate(&env);
+    let result = client.try_validate_and_record(
+        &outsider,
+        &source,
+        &kp_dest,
+        &OperationType::TRANSFER,
+        &host,
+        &allowed_key,
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    assert_eq!(client.get_validation_record(&allowed_key), None);
+
+    env.ledger().set_timestamp(700);
+    let result = client.validate_and_record(
+        &source,
+        &source,
+        &eu_dest,
+        &OperationType::TRANSFER,
+        &host,
+        &allowed_key,
+    );
+    assert!(result.is_compliant);
+    assert_eq!(
+        client.get_validation_record(&allowed_key),
+        Some(ValidationRecord {
+            rule_id: Some(String::from_str(&env, "US-EU")),
+            is_compliant: true,
+            recorded_at: 700,
+        })
+    );
+
+    client.validate_and_record(
+        &source,
+        &source,
+        &kp_dest,
+        &OperationType::TRANSFER,
+        &host,
+        &blocked_key,
+    );
+    let record = client.get_validation_record(&blocked_key).unwrap();
+    assert_eq!(record.rule_id, Some(String::from_str(&env, "US-KP")));
+    assert!(!record.is_compliant);
+
+    // A used key keeps its original decision
+    let result = client.validate_and_record(
+        &source,
+        &source,
+        &kp_dest,
+        &OperationType::TRANSFER,
+        &host,
+        &allowed_key,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&env, "Validation record already exists"))
+    );
+    assert!(
+        client
+            .get_validation_record(&allowed_key)
+            .unwrap()
+            .is_compliant
+    );
+}
+
+#[test]
+fn test_jurisdiction_count_tracks_distinct_assignments() {
+    let (env, admin, _, _, client) = setup_test_env();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+    assert_eq!(client.get_jurisdiction_count(), 0);
+
+    client.set_address_jurisdiction(&admin, &alice, &us);
+    client.set_address_jurisdiction(&admin, &bob, &us);
+    assert_eq!(client.get_jurisdiction_count(), 1);
+
+    // Reassigning to the same jurisdiction changes nothing
+    client.set_address_jurisdiction(&admin, &bob, &us);
+    assert_eq!(client.get_jurisdiction_count(), 1);
+
+    client.set_address_jurisdiction(&admin, &bob, &eu);
+    assert_eq!(client.get_jurisdiction_count(), 2);
+
+    // US keeps alice until she moves as well
+    client.set_address_jurisdiction(&admin, &alice, &eu);
+    assert_eq!(client.get_jurisdiction_count(), 1);
+}
+
+#[test]
+fn test_jurisdiction_risk_threshold() {
+    let (env, admin, governance, _, client) = setup_test_env();
+    client.add_rule(&governance, &transfer_rule(&env, "US-EU", "US", "EU"));
+    client.add_rule(&governance, &transfer_rule(&env, "EU-US", "EU", "US"));
+
+    let us = String::from_str(&env, "US");
+    let eu = String::from_str(&env, "EU");
+    let us_account = Address::generate(&env);
+    let eu_account = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &us_account, &us);
+    client.set_address_jurisdiction(&admin, &eu_account, &eu);
+    let validate = |source: &Address, destination: &Address| {
+        client.validate_transaction(source, destination, &OperationType::TRANSFER, &us, &None)
+    };
+
+    let result = client.try_set_jurisdiction_risk(&admin, &eu, &80);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_jurisdiction_risk(&governance, &eu, &80);
+    client.set_jurisdiction_risk(&governance, &us, &10);
+    assert_eq!(client.get_jurisdiction_risk(&eu), 80);
+    assert_eq!(
+        client.get_jurisdiction_risk(&String::from_str(&env, "BR")),
+        0
+    );
+
+    // Ratings alone block nothing until a threshold is set
+    assert!(validate(&us_account, &eu_account).is_compliant);
+
+    client.set_max_allowed_risk(&governance, &Some(80));
+    assert_eq!(client.get_max_allowed_risk(), Some(80));
+    assert!(validate(&us_account, &eu_account).is_compliant);
+
+    // Above the threshold on either side denies before rules are consulted
+    client.set_max_allowed_risk(&governance, &Some(79));
+    for (source, destination) in [(&us_account, &eu_account), (&eu_account, &us_account)] {
+        let result = validate(source, destination);
+        assert!(!result.is_compliant);
+        assert_eq!(result.rule_id, None);
+        assert_eq!(
+            result.error_message,
+            Some(String::from_str(&env, "Jurisdiction risk too high"))
+        );
+    }
+
+    client.set_max_allowed_risk(&governance, &None);
+    assert!(validate(&eu_account, &us_account).is_compliant);
+}
@@ -0,0 +1,311 @@
+#![cfg(test)]
+
+use regulatory_checks::{
+    ContractError, JurisdictionRule, OperationType, PairRateLimit, RegulatoryCheck,
+    RegulatoryCheckClient,
+};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, testutils::Address as _, Address, Env,
+    String,
+};
+
+/// Minimal stand-in for the CarbonAsset contract exposing `owner_of`
+#[contract]
+pub struct MockCarbonAsset;
+
+#[contracttype]
+enum MockKey {
+    Owner(u32),
+}
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MockError {
+    TokenNotFound = 104,
+}
+
+#[contractimpl]
+impl MockCarbonAsset {
+    pub fn set_owner(env: Env, token_id: u32, owner: Address) {
+        env.storage()
+            .persistent()
+            .set(&MockKey::Owner(token_id), &owner);
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, MockError> {
+        env.storage()
+            .persistent()
+            .get(&MockKey::Owner(token_id))
+            .ok_or(MockError::TokenNotFound)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    client: RegulatoryCheckClient<'a>,
+    carbon: MockCarbonAssetClient<'a>,
+    governance: Address,
+    source: Address,
+    destination: Address,
+    host: String,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+
+    let carbon_id = env.register(MockCarbonAsset, ());
+    let carbon = MockCarbonAssetClient::new(&env, &carbon_id);
+
+    let client = RegulatoryCheckClient::new(&env, &env.register(RegulatoryCheck, ()));
+    client.initialize(&admin, &governance, &carbon_id);
+
+    client.add_rule(
+        &governance,
+        &JurisdictionRule {
+            rule_id: String::from_str(&env, "US-EU"),
+            description: String::from_str(&env, "US to EU transfers"),
+            source_jur: String::from_str(&env, "US"),
+            dest_jur: String::from_str(&env, "EU"),
+            host_jur: String::from_str(&env, "ANY"),
+            operation: OperationType::TRANSFER,
+            is_allowed: true,
+            required_authority: None,
+            expires_at: None,
+            project_id: None,
+            use_default_authority: false,
+            cooldown: None,
+            authority_min_amount: None,
+            requires_attestation: false,
+            category: None,
+        },
+    );
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+
+    let host = String::from_str(&env, "US");
+
+    Setup {
+        env,
+        client,
+        carbon,
+        governance,
+        source,
+        destination,
+        host,
+    }
+}
+
+#[test]
+fn test_validate_token_transfer_confirms_ownership() {
+    let s = setup();
+    assert_eq!(s.client.get_carbon_asset_contract(), s.carbon.address);
+
+    s.carbon.set_owner(&1, &s.source);
+
+    let result = s.client.validate_token_transfer(
+        &s.source,
+        &s.destination,
+        &1,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert!(result.is_compliant);
+    assert_eq!(result.rule_id, Some(String::from_str(&s.env, "US-EU")));
+}
+
+#[test]
+fn test_validate_token_transfer_rejects_non_holder() {
+    let s = setup();
+
+    let other = Address::generate(&s.env);
+    s.carbon.set_owner(&1, &other);
+
+    let result = s.client.validate_token_transfer(
+        &s.source,
+        &s.destination,
+        &1,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "Source does not hold token"))
+    );
+}
+
+#[test]
+fn test_validate_token_transfer_rejects_unknown_token() {
+    let s = setup();
+
+    let result = s.client.validate_token_transfer(
+        &s.source,
+        &s.destination,
+        &7,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert!(!result.is_compliant);
+}
+
+#[test]
+fn test_validate_token_transfer_keeps_rule_rejection() {
+    let s = setup();
+    s.carbon.set_owner(&1, &s.source);
+
+    // No rule covers the reverse direction, so the rule check fails first
+    let result = s.client.validate_token_transfer(
+        &s.destination,
+        &s.source,
+        &1,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "No matching rule found"))
+    );
+}
+
+#[test]
+fn test_validate_and_stage_requires_token_ownership() {
+    let s = setup();
+
+    let authority = Address::generate(&s.env);
+    let mut rule = s
+        .client
+        .get_rule(&String::from_str(&s.env, "US-EU"))
+        .unwrap();
+    rule.required_authority = Some(authority);
+    s.client.update_rule(&s.governance, &rule);
+
+    // Someone else holds the token, so no approval is staged for the source
+    let other = Address::generate(&s.env);
+    s.carbon.set_owner(&1, &other);
+    let result = s.client.try_validate_and_stage(
+        &s.source,
+        &s.destination,
+        &1,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotTokenOwner)));
+
+    // Unknown tokens are refused the same way
+    let result = s.client.try_validate_and_stage(
+        &s.source,
+        &s.destination,
+        &2,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotTokenOwner)));
+
+    s.carbon.set_owner(&1, &s.source);
+    let result = s.client.validate_and_stage(
+        &s.source,
+        &s.destination,
+        &1,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    let approval_key = result.approval_key.unwrap();
+    assert!(!s.client.check_approval(&approval_key));
+}
+
+/// Limit US to EU to one transfer per hour and use it up
+fn exhaust_pair_limit(s: &Setup) {
+    s.client.set_pair_rate_limit(
+        &s.governance,
+        &String::from_str(&s.env, "US"),
+        &String::from_str(&s.env, "EU"),
+        &Some(PairRateLimit {
+            max_count: 1,
+            window_seconds: 3_600,
+        }),
+    );
+    s.client
+        .record_pair_transfer(&s.carbon.address, &s.source, &s.destination);
+}
+
+#[test]
+fn test_validate_token_transfer_applies_pair_limit() {
+    let s = setup();
+    s.carbon.set_owner(&1, &s.source);
+    exhaust_pair_limit(&s);
+
+    let result = s.client.validate_token_transfer(
+        &s.source,
+        &s.destination,
+        &1,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "Pair rate limit exceeded"))
+    );
+}
+
+#[test]
+fn test_validate_and_stage_applies_pair_limit() {
+    let s = setup();
+    let mut rule = s
+        .client
+        .get_rule(&String::from_str(&s.env, "US-EU"))
+        .unwrap();
+    rule.required_authority = Some(Address::generate(&s.env));
+    s.client.update_rule(&s.governance, &rule);
+    s.carbon.set_owner(&1, &s.source);
+    exhaust_pair_limit(&s);
+
+    // Nothing is staged for a corridor that is full
+    let result = s.client.validate_and_stage(
+        &s.source,
+        &s.destination,
+        &1,
+        &OperationType::TRANSFER,
+        &s.host,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(result.approval_key, None);
+
+    let approval_key = s
+        .client
+        .validate_transaction(
+            &s.source,
+            &s.destination,
+            &OperationType::TRANSFER,
+            &s.host,
+            &None,
+        )
+        .approval_key;
+    assert_eq!(approval_key, None);
+    s.client.set_pair_rate_limit(
+        &s.governance,
+        &String::from_str(&s.env, "US"),
+        &String::from_str(&s.env, "EU"),
+        &None,
+    );
+    let approval_key = s
+        .client
+        .validate_transaction(
+            &s.source,
+            &s.destination,
+            &OperationType::TRANSFER,
+            &s.host,
+            &None,
+        )
+        .approval_key
+        .unwrap();
+    assert!(s.client.get_pending_approval(&approval_key).is_none());
+}
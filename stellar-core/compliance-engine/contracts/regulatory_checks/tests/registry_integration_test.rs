@@ -0,0 +1,442 @@
+#![cfg(test)]
+
+use regulatory_checks::{
+    ContractError, JurisdictionRule, OperationType, PairRateLimit, RegulatoryCheck,
+    RegulatoryCheckClient,
+};
+use shared_types::{Project, ProjectStatus, RevocationStatus};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, testutils::Address as _, Address, Env,
+    String,
+};
+
+/// Minimal stand-in for the project registry's project, token, jurisdiction
+/// and KYC attestation lookups
+#[contract]
+pub struct MockRegistry;
+
+#[contracttype]
+enum MockKey {
+    Project(String),
+    TokenProject(u32),
+    Jurisdiction(String),
+    Attested(Address),
+}
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MockRegistryError {
+    ProjectNotFound = 1,
+    TokenNotLinked = 23,
+    JurisdictionNotSet = 24,
+}
+
+#[contractimpl]
+impl MockRegistry {
+    pub fn set_project_status(env: Env, project_id: String, status: ProjectStatus) {
+        let project = Project {
+            owner: env.current_contract_address(),
+            status,
+            registered_at: 0,
+            expires_at: u64::MAX,
+            expired: false,
+            revocation: RevocationStatus::NotRevoked,
+            total_retired: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&MockKey::Project(project_id), &project);
+    }
+
+    pub fn get_project(env: Env, project_id: String) -> Result<Project, MockRegistryError> {
+        env.storage()
+            .persistent()
+            .get(&MockKey::Project(project_id))
+            .ok_or(MockRegistryError::ProjectNotFound)
+    }
+
+    pub fn link_token(env: Env, project_id: String, token_id: u32) {
+        env.storage()
+            .persistent()
+            .set(&MockKey::TokenProject(token_id), &project_id);
+    }
+
+    pub fn set_project_jurisdiction(env: Env, project_id: String, jurisdiction: String) {
+        env.storage()
+            .persistent()
+            .set(&MockKey::Jurisdiction(project_id), &jurisdiction);
+    }
+
+    pub fn get_token_project(env: Env, token_id: u32) -> Result<String, MockRegistryError> {
+        env.storage()
+            .persistent()
+            .get(&MockKey::TokenProject(token_id))
+            .ok_or(MockRegistryError::TokenNotLinked)
+    }
+
+    pub fn get_project_jurisdiction(
+        env: Env,
+        project_id: String,
+    ) -> Result<String, MockRegistryError> {
+        env.storage()
+            .persistent()
+            .get(&MockKey::Jurisdiction(project_id))
+            .ok_or(MockRegistryError::JurisdictionNotSet)
+    }
+
+    pub fn set_attested(env: Env, account: Address, attested: bool) {
+        env.storage()
+            .persistent()
+            .set(&MockKey::Attested(account), &attested);
+    }
+
+    pub fn has_valid_address_attestation(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&MockKey::Attested(account))
+            .unwrap_or(false)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    carbon_contract: Address,
+    governance: Address,
+    client: RegulatoryCheckClient<'a>,
+    registry: MockRegistryClient<'a>,
+    source: Address,
+    destination: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+
+    let client = RegulatoryCheckClient::new(&env, &env.register(RegulatoryCheck, ()));
+    client.initialize(&admin, &governance, &carbon_contract);
+
+    let registry_id = env.register(MockRegistry, ());
+    let registry = MockRegistryClient::new(&env, &registry_id);
+
+    // Only transfers hosted in Brazil are allowed
+    client.add_rule(
+        &governance,
+        &JurisdictionRule {
+            rule_id: String::from_str(&env, "BR-HOSTED"),
+            description: String::from_str(&env, "Transfers of Brazilian projects"),
+            source_jur: String::from_str(&env, "ANY"),
+            dest_jur: String::from_str(&env, "ANY"),
+            host_jur: String::from_str(&env, "BR"),
+            operation: OperationType::TRANSFER,
+            is_allowed: true,
+            required_authority: None,
+            expires_at: None,
+            project_id: None,
+            use_default_authority: false,
+            cooldown: None,
+            authority_min_amount: None,
+            requires_attestation: false,
+            category: None,
+        },
+    );
+
+    let source = Address::generate(&env);
+    let destination = Address::generate(&env);
+    client.set_address_jurisdiction(&admin, &source, &String::from_str(&env, "US"));
+    client.set_address_jurisdiction(&admin, &destination, &String::from_str(&env, "EU"));
+
+    Setup {
+        env,
+        admin,
+        carbon_contract,
+        governance,
+        client,
+        registry,
+        source,
+        destination,
+    }
+}
+
+#[test]
+fn test_validate_for_token_uses_registry_jurisdiction() {
+    let s = setup();
+    s.client
+        .set_registry_contract(&s.admin, &s.registry.address);
+    assert_eq!(
+        s.client.get_registry_contract(),
+        Some(s.registry.address.clone())
+    );
+
+    let brazil = String::from_str(&s.env, "PROJ-BR");
+    let kenya = String::from_str(&s.env, "PROJ-KE");
+    s.registry
+        .set_project_jurisdiction(&brazil, &String::from_str(&s.env, "BR"));
+    s.registry
+        .set_project_jurisdiction(&kenya, &String::from_str(&s.env, "KE"));
+    s.registry.link_token(&brazil, &1);
+    s.registry.link_token(&kenya, &2);
+
+    let result = s.client.validate_transaction_for_token(
+        &s.source,
+        &s.destination,
+        &OperationType::TRANSFER,
+        &1,
+    );
+    assert!(result.is_compliant);
+    assert_eq!(result.rule_id, Some(String::from_str(&s.env, "BR-HOSTED")));
+
+    let result = s.client.validate_transaction_for_token(
+        &s.source,
+        &s.destination,
+        &OperationType::TRANSFER,
+        &2,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "No matching rule found"))
+    );
+}
+
+#[test]
+fn test_validate_for_token_reports_missing_registry_data() {
+    let s = setup();
+
+    let result = s.client.validate_transaction_for_token(
+        &s.source,
+        &s.destination,
+        &OperationType::TRANSFER,
+        &1,
+    );
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "Registry contract not set"))
+    );
+
+    s.client
+        .set_registry_contract(&s.admin, &s.registry.address);
+    let result = s.client.validate_transaction_for_token(
+        &s.source,
+        &s.destination,
+        &OperationType::TRANSFER,
+        &1,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "Token not linked to a project"))
+    );
+
+    s.registry
+        .link_token(&String::from_str(&s.env, "PROJ-BR"), &1);
+    let result = s.client.validate_transaction_for_token(
+        &s.source,
+        &s.destination,
+        &OperationType::TRANSFER,
+        &1,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "Jurisdiction not set for project"))
+    );
+}
+
+#[test]
+fn test_set_registry_contract_requires_admin() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_set_registry_contract(&outsider, &s.registry.address);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+}
+
+fn project_rule(env: &Env, rule_id: &str, project_id: &String) -> JurisdictionRule {
+    JurisdictionRule {
+        rule_id: String::from_str(env, rule_id),
+        description: String::from_str(env, "Project-specific transfers"),
+        source_jur: String::from_str(env, "US"),
+        dest_jur: String::from_str(env, "EU"),
+        host_jur: String::from_str(env, "ANY"),
+        operation: OperationType::TRANSFER,
+        is_allowed: true,
+        required_authority: None,
+        expires_at: None,
+        project_id: Some(project_id.clone()),
+        use_default_authority: false,
+        cooldown: None,
+        authority_min_amount: None,
+        requires_attestation: false,
+        category: None,
+    }
+}
+
+#[test]
+fn test_project_rule_requires_verified_project() {
+    let s = setup();
+    s.client
+        .set_registry_contract(&s.admin, &s.registry.address);
+    let project_id = String::from_str(&s.env, "PROJ-BR");
+
+    // Unknown to the registry
+    let rule = project_rule(&s.env, "PROJ-BR-RULE", &project_id);
+    let result = s.client.try_add_rule(&s.governance, &rule);
+    assert_eq!(result, Err(Ok(ContractError::ProjectNotVerified)));
+
+    for status in [
+        ProjectStatus::Registered,
+        ProjectStatus::UnderReview,
+        ProjectStatus::Rejected,
+        ProjectStatus::Retired,
+    ] {
+        s.registry.set_project_status(&project_id, &status);
+        let result = s.client.try_add_rule(&s.governance, &rule);
+        assert_eq!(result, Err(Ok(ContractError::ProjectNotVerified)));
+    }
+    assert!(s.client.get_rule(&rule.rule_id).is_none());
+
+    s.registry
+        .set_project_status(&project_id, &ProjectStatus::Verified);
+    s.client.add_rule(&s.governance, &rule);
+    let stored = s.client.get_rule(&rule.rule_id).unwrap();
+    assert_eq!(stored.project_id, Some(project_id));
+}
+
+#[test]
+fn test_update_rule_rechecks_project_status() {
+    let s = setup();
+    s.client
+        .set_registry_contract(&s.admin, &s.registry.address);
+    let project_id = String::from_str(&s.env, "PROJ-BR");
+    s.registry
+        .set_project_status(&project_id, &ProjectStatus::Verified);
+
+    let mut rule = project_rule(&s.env, "PROJ-BR-RULE", &project_id);
+    s.client.add_rule(&s.governance, &rule);
+
+    s.registry
+        .set_project_status(&project_id, &ProjectStatus::Retired);
+    rule.is_allowed = false;
+    let result = s.client.try_update_rule(&s.governance, &rule);
+    assert_eq!(result, Err(Ok(ContractError::ProjectNotVerified)));
+
+    let stored = s.client.get_rule(&rule.rule_id).unwrap();
+    assert!(stored.is_allowed);
+}
+
+#[test]
+fn test_attestation_check_consults_registry() {
+    let s = setup();
+    let host = String::from_str(&s.env, "BR");
+    let validate = || {
+        s.client.validate_transaction(
+            &s.source,
+            &s.destination,
+            &OperationType::TRANSFER,
+            &host,
+            &None,
+        )
+    };
+
+    let rule = JurisdictionRule {
+        rule_id: String::from_str(&s.env, "KYC-RULE"),
+        description: String::from_str(&s.env, "Transfers between attested parties"),
+        source_jur: String::from_str(&s.env, "US"),
+        dest_jur: String::from_str(&s.env, "EU"),
+        host_jur: String::from_str(&s.env, "ANY"),
+        operation: OperationType::TRANSFER,
+        is_allowed: true,
+        required_authority: None,
+        expires_at: None,
+        project_id: None,
+        use_default_authority: false,
+        cooldown: None,
+        authority_min_amount: None,
+        requires_attestation: true,
+        category: None,
+    };
+    s.client.add_rule(&s.governance, &rule);
+    s.client
+        .deactivate_rule(&s.governance, &String::from_str(&s.env, "BR-HOSTED"));
+
+    // The rule's requirement is ignored until governance enables the check
+    assert!(validate().is_compliant);
+
+    let outsider = Address::generate(&s.env);
+    let result = s.client.try_set_attestation_check(&outsider, &true);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    s.client.set_attestation_check(&s.governance, &true);
+    assert!(s.client.is_attestation_check_enabled());
+
+    // Without a registry nobody counts as attested
+    let result = validate();
+    assert!(!result.is_compliant);
+    assert_eq!(result.rule_id, Some(String::from_str(&s.env, "KYC-RULE")));
+
+    s.client
+        .set_registry_contract(&s.admin, &s.registry.address);
+    s.registry.set_attested(&s.source, &true);
+    let result = validate();
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "Address lacks KYC attestation"))
+    );
+
+    s.registry.set_attested(&s.destination, &true);
+    let result = validate();
+    assert!(result.is_compliant);
+    assert_eq!(result.rule_id, Some(String::from_str(&s.env, "KYC-RULE")));
+
+    s.registry.set_attested(&s.source, &false);
+    assert!(!validate().is_compliant);
+
+    s.client.set_attestation_check(&s.governance, &false);
+    assert!(validate().is_compliant);
+}
+
+#[test]
+fn test_validate_for_token_applies_pair_limit() {
+    let s = setup();
+    s.client
+        .set_registry_contract(&s.admin, &s.registry.address);
+    let brazil = String::from_str(&s.env, "PROJ-BR");
+    s.registry
+        .set_project_jurisdiction(&brazil, &String::from_str(&s.env, "BR"));
+    s.registry.link_token(&brazil, &1);
+
+    s.client.set_pair_rate_limit(
+        &s.governance,
+        &String::from_str(&s.env, "US"),
+        &String::from_str(&s.env, "EU"),
+        &Some(PairRateLimit {
+            max_count: 1,
+            window_seconds: 3_600,
+        }),
+    );
+    let validate = || {
+        s.client.validate_transaction_for_token(
+            &s.source,
+            &s.destination,
+            &OperationType::TRANSFER,
+            &1,
+        )
+    };
+    assert!(validate().is_compliant);
+
+    s.client
+        .record_pair_transfer(&s.carbon_contract, &s.source, &s.destination);
+    let result = validate();
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(String::from_str(&s.env, "Pair rate limit exceeded"))
+    );
+}
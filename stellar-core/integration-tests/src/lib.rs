@@ -0,0 +1,192 @@
+//! Test wiring that deploys the registry, regulatory check, buffer pool and
+//! carbon asset contracts into one `Env`, so flows that cross contract boundaries can be
+//! exercised against the real implementations instead of mocks.
+
+use buffer_pool::{BufferPoolContract, BufferPoolContractClient};
+use carbon_asset::{CarbonAsset, CarbonAssetClient};
+use pause_coordinator::{PauseCoordinator, PauseCoordinatorClient};
+use registry_contract::{ProjectRegistry, ProjectRegistryClient};
+use regulatory_checks::{JurisdictionRule, OperationType, RegulatoryCheck, RegulatoryCheckClient};
+use shared_types::ProjectStatus;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+/// Replenishment percentage the pool is initialized with, in basis points
+pub const REPLENISHMENT_BPS: i64 = 500;
+
+/// Far enough in the future that no project expires during a test
+pub const PROJECT_EXPIRES_AT: u64 = 10_000_000;
+
+/// All four contracts wired to each other, with the accounts that administer them
+pub struct Harness<'a> {
+    pub env: Env,
+    pub admin: Address,
+    pub governance: Address,
+    pub verifier: Address,
+    /// Address of `carbon`, also used as the caller of the pool and
+    /// registry retirement hooks
+    pub carbon_contract: Address,
+    pub carbon: CarbonAssetClient<'a>,
+    pub registry: ProjectRegistryClient<'a>,
+    pub regulatory: RegulatoryCheckClient<'a>,
+    pub pool: BufferPoolContractClient<'a>,
+}
+
+impl<'a> Harness<'a> {
+    /// Deploy and initialize every contract, point the regulatory check and
+    /// buffer pool at the registry and the carbon asset at the regulatory
+    /// check. All auths are mocked.
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let governance = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let carbon = CarbonAssetClient::new(&env, &env.register(CarbonAsset, ()));
+        let carbon_contract = carbon.address.clone();
+
+        let registry = ProjectRegistryClient::new(&env, &env.register(ProjectRegistry, ()));
+        registry.initialize(&admin);
+        registry.set_governance(&governance);
+        registry.add_verifier(&governance, &verifier);
+        registry.set_carbon_asset_contract(&admin, &carbon_contract);
+
+        let regulatory = RegulatoryCheckClient::new(&env, &env.register(RegulatoryCheck, ()));
+        regulatory.initialize(&admin, &governance, &carbon_contract);
+        regulatory.set_registry_contract(&admin, &registry.address);
+
+        let pool = BufferPoolContractClient::new(&env, &env.register(BufferPoolContract, ()));
+        pool.initialize(&admin, &governance, &carbon_contract, &REPLENISHMENT_BPS);
+        pool.set_registry_contract(&admin, &registry.address);
+
+        carbon.initialize(
+            &admin,
+            &String::from_str(&env, "Carbon Credit"),
+            &String::from_str(&env, "CARBON"),
+            &Address::generate(&env),
+            &String::from_str(&env, "US"),
+        );
+        carbon.set_regulatory_check(&admin, &regulatory.address);
+
+        Harness {
+            env,
+            admin,
+            governance,
+            verifier,
+            carbon_contract,
+            carbon,
+            registry,
+            regulatory,
+            pool,
+        }
+    }
+
+    pub fn string(&self, value: &str) -> String {
+        String::from_str(&self.env, value)
+    }
+
+    pub fn bytes(&self, fill: u8) -> BytesN<32> {
+        BytesN::from_array(&self.env, &[fill; 32])
+    }
+
+    /// Register a project hosted in `jurisdiction` and leave it `Registered`
+    pub fn register_project(&self, project_id: &str, jurisdiction: &str) -> String {
+        let project_id = self.string(project_id);
+        let owner = Address::generate(&self.env);
+
+        self.registry
+            .register_project(&project_id, &owner, &PROJECT_EXPIRES_AT, &None);
+        self.registry
+            .set_project_jurisdiction(&project_id, &self.string(jurisdiction));
+
+        project_id
+    }
+
+    /// Register a project, attest to it and move it through review to `Verified`
+    pub fn register_verified_project(&self, project_id: &str, jurisdiction: &str) -> String {
+        let project_id = self.register_project(project_id, jurisdiction);
+
+        self.registry
+            .attest_project(&self.verifier, &project_id, &self.bytes(1));
+        self.registry
+            .set_project_status(&project_id, &ProjectStatus::UnderReview);
+        self.registry
+            .set_project_status(&project_id, &ProjectStatus::Verified);
+
+        project_id
+    }
+
+    /// Link an issued token carrying `credits` to its project in the registry
+    pub fn link_token(&self, project_id: &String, token_id: u32, credits: i128) {
+        self.registry.link_token(project_id, &token_id, &credits);
+    }
+
+    /// Deploy a pause coordinator over all three contracts and set it as each
+    /// one's coordinator
+    pub fn deploy_coordinator(&self) -> PauseCoordinatorClient<'a> {
+        let coordinator =
+            PauseCoordinatorClient::new(&self.env, &self.env.register(PauseCoordinator, ()));
+        coordinator.initialize(
+            &self.admin,
+            &vec![
+                &self.env,
+                self.regulatory.address.clone(),
+                self.pool.address.clone(),
+                self.registry.address.clone(),
+            ],
+        );
+
+        self.regulatory
+            .set_coordinator(&self.admin, &coordinator.address);
+        self.pool.set_coordinator(&self.admin, &coordinator.address);
+        self.registry
+            .set_coordinator(&self.admin, &coordinator.address);
+
+        coordinator
+    }
+
+    /// Generate an account and record its jurisdiction with the regulatory check
+    pub fn account(&self, jurisdiction: &str) -> Address {
+        let account = Address::generate(&self.env);
+        self.regulatory
+            .set_address_jurisdiction(&self.admin, &account, &self.string(jurisdiction));
+        account
+    }
+
+    /// Add a rule allowing `operation` from `source` to `dest` for any host,
+    /// optionally requiring sign-off from `authority`
+    pub fn allow(
+        &self,
+        rule_id: &str,
+        source: &str,
+        dest: &str,
+        operation: OperationType,
+        authority: Option<Address>,
+    ) -> JurisdictionRule {
+        let rule = JurisdictionRule {
+            rule_id: self.string(rule_id),
+            description: self.string("Integration test rule"),
+            source_jur: self.string(source),
+            dest_jur: self.string(dest),
+            host_jur: self.string("ANY"),
+            operation,
+            is_allowed: true,
+            required_authority: authority,
+            expires_at: None,
+            project_id: None,
+            use_default_authority: false,
+            cooldown: None,
+            authority_min_amount: None,
+            requires_attestation: false,
+            category: None,
+        };
+        self.regulatory.add_rule(&self.governance, &rule);
+        rule
+    }
+}
+
+impl Default for Harness<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
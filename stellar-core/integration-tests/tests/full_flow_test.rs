@@ -0,0 +1,259 @@
+use carbon_asset::CarbonAssetMetadata;
+use integration_tests::{Harness, REPLENISHMENT_BPS};
+use regulatory_checks::{ContractError, OperationType, PairRateLimit};
+use shared_types::{ProjectStatus, RevocationReason};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::Address;
+
+#[test]
+fn test_deposit_validate_retire_flow() {
+    let h = Harness::new();
+
+    // Registry: a verified Brazilian project with one issued token
+    let project_id = h.register_verified_project("PROJ-BR", "BR");
+    h.link_token(&project_id, 1, 1);
+    assert_eq!(
+        h.registry.get_project(&project_id).status,
+        ProjectStatus::Verified
+    );
+
+    // Rules: a project-gated transfer rule that needs an authority's sign-off
+    let authority = Address::generate(&h.env);
+    let mut rule = h.allow(
+        "US-EU",
+        "US",
+        "EU",
+        OperationType::TRANSFER,
+        Some(authority.clone()),
+    );
+    rule.project_id = Some(project_id.clone());
+    h.regulatory.update_rule(&h.governance, &rule);
+
+    // Pool: the registry-verified project may deposit its buffer share
+    h.pool
+        .deposit(&h.admin, &100, &project_id, &1, &Some(h.bytes(7)), &None);
+    assert!(h.pool.is_token_in_pool(&100));
+    assert_eq!(h.pool.get_total_value_locked(), 1);
+    assert_eq!(
+        h.pool.get_custody_record(&100).unwrap().project_id,
+        project_id
+    );
+
+    // Validation: the host jurisdiction comes from the registry via the token link
+    let source = h.account("US");
+    let destination = h.account("EU");
+    let result = h.regulatory.validate_transaction_for_token(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &1,
+    );
+    assert!(result.is_compliant);
+    assert!(result.requires_authorization);
+    assert_eq!(result.authority_address, Some(authority.clone()));
+
+    let by_host = h.regulatory.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &h.string("BR"),
+        &Some(h.bytes(8)),
+    );
+    assert_eq!(by_host.rule_id, result.rule_id);
+
+    // Authorization: stage the approval and have the authority sign off
+    let approval_key = h.bytes(9);
+    h.regulatory.create_pending_approval(
+        &approval_key,
+        &1,
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+    );
+    assert!(
+        !h.regulatory
+            .finalize_if_approved(&source, &approval_key)
+            .is_compliant
+    );
+    h.regulatory.record_authorization(&authority, &approval_key);
+    assert!(h.regulatory.check_approval(&approval_key));
+    assert!(
+        h.regulatory
+            .finalize_if_approved(&source, &approval_key)
+            .is_compliant
+    );
+
+    // Retirement: the pool owes its share, and the next deposit pays it down
+    let needed = h
+        .pool
+        .on_retirement(&h.carbon_contract, &project_id, &100, &900);
+    assert_eq!(needed, 100 * REPLENISHMENT_BPS as i128 / 10_000);
+    assert_eq!(h.pool.get_replenishment_due(&project_id), needed);
+
+    // The registry keeps the retirement against the project for accounting,
+    // bounded by the one credit it issued
+    let beneficiary = Address::generate(&h.env);
+    h.registry
+        .record_retirement(&h.carbon_contract, &project_id, &1, &1, &beneficiary);
+    let retirements = h.registry.get_retirements(&project_id);
+    assert_eq!(retirements.len(), 1);
+    assert_eq!(retirements.get(0).unwrap().beneficiary, beneficiary);
+    assert_eq!(h.registry.get_project(&project_id).total_retired, 1);
+
+    h.pool
+        .deposit(&h.admin, &200, &project_id, &(needed - 1), &None, &None);
+    assert_eq!(h.pool.get_replenishment_due(&project_id), 1);
+    h.pool
+        .deposit(&h.admin, &201, &project_id, &1, &None, &None);
+    assert_eq!(h.pool.get_replenishment_due(&project_id), 0);
+    assert_eq!(h.pool.get_total_value_locked(), 1 + needed);
+
+    // Every contract reports a consistent view of the setup
+    assert_eq!(h.registry.health_check().entity_count, 1);
+    assert_eq!(h.regulatory.health_check().entity_count, 1);
+    assert_eq!(h.pool.health_check().entity_count, 3);
+}
+
+#[test]
+fn test_unverified_project_is_blocked_across_contracts() {
+    let h = Harness::new();
+    let project_id = h.register_project("PROJ-KE", "KE");
+
+    let mut rule = h.allow("US-EU", "US", "EU", OperationType::TRANSFER, None);
+    rule.project_id = Some(project_id.clone());
+    let result = h.regulatory.try_update_rule(&h.governance, &rule);
+    assert_eq!(result, Err(Ok(ContractError::ProjectNotVerified)));
+
+    // A token the registry has not linked has no host jurisdiction to check
+    let source = h.account("US");
+    let destination = h.account("EU");
+    let result = h.regulatory.validate_transaction_for_token(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &1,
+    );
+    assert!(!result.is_compliant);
+    assert_eq!(
+        result.error_message,
+        Some(h.string("Token not linked to a project"))
+    );
+
+    // The pool refuses deposits for projects the registry has revoked
+    h.registry
+        .set_project_status(&project_id, &ProjectStatus::UnderReview);
+    h.registry.revoke_project(
+        &h.governance,
+        &project_id,
+        &RevocationReason::Fraud,
+        &h.string("Fabricated monitoring reports"),
+    );
+    let result = h
+        .pool
+        .try_deposit(&h.admin, &1, &project_id, &1, &None, &None);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::UnknownProject)));
+    assert_eq!(h.pool.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_coordinator_pauses_all_contracts() {
+    let h = Harness::new();
+    let project_id = h.register_verified_project("PROJ-BR", "BR");
+    h.allow("US-EU", "US", "EU", OperationType::TRANSFER, None);
+    let source = h.account("US");
+    let destination = h.account("EU");
+    let coordinator = h.deploy_coordinator();
+
+    coordinator.pause_all(&h.admin);
+    assert!(h.regulatory.is_paused());
+    assert!(h.pool.is_paused());
+    assert!(h.registry.is_paused());
+
+    let result = h.regulatory.validate_transaction(
+        &source,
+        &destination,
+        &OperationType::TRANSFER,
+        &h.string("BR"),
+        &None,
+    );
+    assert!(!result.is_compliant);
+    let result = h
+        .pool
+        .try_deposit(&h.admin, &1, &project_id, &1, &None, &None);
+    assert_eq!(result, Err(Ok(buffer_pool::Error::Paused)));
+    assert!(h.registry.try_link_token(&project_id, &1, &1).is_err());
+
+    // Only the coordinator's admin can throw the switch
+    let outsider = Address::generate(&h.env);
+    assert!(coordinator.try_resume_all(&outsider).is_err());
+    assert!(h.pool.is_paused());
+
+    coordinator.resume_all(&h.admin);
+    assert!(!h.regulatory.is_paused());
+    assert!(!h.pool.is_paused());
+    assert!(!h.registry.is_paused());
+    h.pool.deposit(&h.admin, &1, &project_id, &1, &None, &None);
+    h.link_token(&project_id, 1, 1);
+}
+
+#[test]
+fn test_carbon_transfers_count_against_pair_limit() {
+    let h = Harness::new();
+    h.allow("US-EU", "US", "EU", OperationType::TRANSFER, None);
+    h.regulatory.set_pair_rate_limit(
+        &h.governance,
+        &h.string("US"),
+        &h.string("EU"),
+        &Some(PairRateLimit {
+            max_count: 2,
+            window_seconds: 3_600,
+        }),
+    );
+    let source = h.account("US");
+    let destination = h.account("EU");
+    let metadata = CarbonAssetMetadata {
+        project_id: h.string("PROJ-1"),
+        vintage_year: 1704067200,
+        methodology_id: 1,
+        geo_hash: h.bytes(3),
+    };
+    for _ in 0..3 {
+        h.carbon.mint(&h.admin, &source, &metadata);
+    }
+
+    // Validation alone never spends the corridor's allowance
+    for _ in 0..3 {
+        h.regulatory.validate_transaction(
+            &source,
+            &destination,
+            &OperationType::TRANSFER,
+            &h.string("US"),
+            &None,
+        );
+    }
+    assert_eq!(
+        h.regulatory
+            .get_pair_count(&h.string("US"), &h.string("EU")),
+        0
+    );
+
+    // Each executed transfer is counted until the window is full
+    h.carbon.transfer(&source, &destination, &1);
+    h.carbon.transfer(&source, &destination, &1);
+    assert_eq!(
+        h.regulatory
+            .get_pair_count(&h.string("US"), &h.string("EU")),
+        2
+    );
+    let result = h.carbon.try_transfer(&source, &destination, &1);
+    assert_eq!(
+        result,
+        Err(Ok(carbon_asset::ContractError::ComplianceFailed))
+    );
+    assert_eq!(h.carbon.balance(&destination), 2);
+
+    // A new window admits transfers again
+    h.env.ledger().with_mut(|li| li.timestamp += 3_600);
+    h.carbon.transfer(&source, &destination, &1);
+    assert_eq!(h.carbon.balance(&destination), 3);
+}
@@ -0,0 +1,171 @@
+#![no_std]
+
+//! Types shared by the stellar-core contracts. Anything passed across a
+//! contract boundary lives here so every contract encodes it the same way.
+
+use soroban_sdk::{contracttype, Address, BytesN, String};
+
+/// Error codes are split into disjoint ranges, one per contract, so an error
+/// surfaced through a cross-contract call identifies the contract that raised
+/// it. Each `#[contracterror]` enum numbers its variants `BASE + 1` upwards.
+pub const ERROR_RANGE_SIZE: u32 = 100;
+pub const REGISTRY_ERROR_BASE: u32 = 0;
+pub const CARBON_ASSET_ERROR_BASE: u32 = 100;
+pub const REGULATORY_ERROR_BASE: u32 = 200;
+pub const BUFFER_POOL_ERROR_BASE: u32 = 300;
+
+/// Contract owning an error code range
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorSource {
+    Registry,
+    CarbonAsset,
+    Regulatory,
+    BufferPool,
+}
+
+/// Map a contract error code back to the contract that defines it
+pub fn error_source(code: u32) -> Option<ErrorSource> {
+    match code / ERROR_RANGE_SIZE * ERROR_RANGE_SIZE {
+        // 0 is not an error code, so the registry's range starts at 1
+        REGISTRY_ERROR_BASE if code > REGISTRY_ERROR_BASE => Some(ErrorSource::Registry),
+        CARBON_ASSET_ERROR_BASE => Some(ErrorSource::CarbonAsset),
+        REGULATORY_ERROR_BASE => Some(ErrorSource::Regulatory),
+        BUFFER_POOL_ERROR_BASE => Some(ErrorSource::BufferPool),
+        _ => None,
+    }
+}
+
+/// Monitoring snapshot returned by each contract's `health_check`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct HealthStatus {
+    pub initialized: bool,
+    pub paused: bool,
+    /// Number of primary entities held: rules, custody records or projects
+    pub entity_count: u32,
+}
+
+/// Operation being validated by the RegulatoryCheck contract
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum OperationType {
+    TRANSFER,
+    RETIREMENT,
+}
+
+/// Result returned by the RegulatoryCheck validation entry points
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ValidationResult {
+    pub is_compliant: bool,
+    pub rule_id: Option<String>,
+    pub requires_authorization: bool,
+    pub authority_address: Option<Address>,
+    pub error_message: Option<String>,
+    /// Key to stage and approve the transaction under, set whenever
+    /// `requires_authorization` is. `validate_and_stage` stages it directly.
+    pub approval_key: Option<BytesN<32>>,
+}
+
+/// Lifecycle status of a registered project
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProjectStatus {
+    Registered,
+    UnderReview,
+    Verified,
+    Rejected,
+    Retired,
+    /// Temporarily withdrawn; reactivation restores the status held before
+    Inactive,
+}
+
+/// Structured reason code for revoking a project
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RevocationReason {
+    Fraud,
+    DoubleCounting,
+    MethodologyViolation,
+    Expired,
+    Other,
+}
+
+/// Revocation details stored on a project
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Revocation {
+    /// Structured reason code for statistics
+    pub code: RevocationReason,
+    /// Free-text explanation
+    pub reason: String,
+    /// Ledger timestamp when the project was revoked
+    pub revoked_at: u64,
+}
+
+/// Revocation state stored on a project
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RevocationStatus {
+    NotRevoked,
+    Revoked(Revocation),
+}
+
+/// Project record storing ownership and lifecycle state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Project {
+    /// Current owner of the project
+    pub owner: Address,
+    /// Current lifecycle status
+    pub status: ProjectStatus,
+    /// Ledger timestamp when the project was registered
+    pub registered_at: u64,
+    /// Ledger timestamp when the crediting period ends
+    pub expires_at: u64,
+    /// Whether the crediting period has ended (computed on read)
+    pub expired: bool,
+    /// Revocation details, if the project has been revoked
+    pub revocation: RevocationStatus,
+    /// Credits retired against the project, as recorded by the carbon asset contract
+    pub total_retired: i128,
+}
+
+/// Largest page a paginated getter returns, whatever `limit` the caller asks for
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// Declare a page of results returned by a paginated getter:
+/// `items`, the `next_cursor` to pass back for the following page (`None` on
+/// the last page) and the `total` number of items across all pages.
+///
+/// Contract types cannot be generic, so each getter's page is declared with
+/// this macro rather than as a shared `Page<T>`, which keeps the layout
+/// identical across contracts. The item must be named by a bare type name in
+/// scope, since the contract type derive cannot see through a `ty` fragment.
+#[macro_export]
+macro_rules! page_type {
+    ($(#[$attr:meta])* $name:ident, $item:ident) => {
+        $(#[$attr])*
+        #[soroban_sdk::contracttype]
+        pub struct $name {
+            pub items: soroban_sdk::Vec<$item>,
+            pub next_cursor: Option<u32>,
+            pub total: u32,
+        }
+    };
+}
+
+/// Index range `start..end` of the page at `cursor` out of `total` items,
+/// with `limit` capped at `MAX_PAGE_LIMIT`, plus the cursor of the next page.
+/// A zero `limit` gives an empty page with no next cursor.
+pub fn page_bounds(total: u32, cursor: u32, limit: u32) -> (u32, u32, Option<u32>) {
+    let start = cursor.min(total);
+    let end = start.saturating_add(limit.min(MAX_PAGE_LIMIT)).min(total);
+    let next_cursor = if end < total && end > start {
+        Some(end)
+    } else {
+        None
+    };
+    (start, end, next_cursor)
+}
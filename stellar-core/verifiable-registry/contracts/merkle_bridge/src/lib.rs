@@ -514,8 +514,8 @@ impl MerkleBridge {
         let id_len = registry_credit_id.len() as usize;
         let mut id_buffer = [0u8; 256]; // Max length buffer
         registry_credit_id.copy_into_slice(&mut id_buffer[..id_len]);
-        for i in 0..id_len {
-            data.push_back(id_buffer[i]);
+        for byte in &id_buffer[..id_len] {
+            data.push_back(*byte);
         }
 
         // Append status bytes
@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Env, String};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String};
 
 /// Structured event emitted when a document is anchored
 /// This enables off-chain indexing and real-time updates to Layer 3 portals
@@ -28,3 +28,89 @@ pub fn emit_document_anchored_event(
     }
     .publish(env);
 }
+
+/// Structured event emitted when a project's metadata hash is anchored
+/// Topics are ("anchor", project_id) so indexers can build a per-project anchor timeline
+#[contractevent(topics = ["anchor"])]
+pub struct MetadataAnchored {
+    #[topic]
+    pub project_id: String,
+    pub metadata_hash: BytesN<32>,
+    pub version: u32,
+    pub timestamp: u64,
+}
+
+/// Emit a structured event when a metadata hash is anchored
+pub fn emit_metadata_anchored_event(
+    env: &Env,
+    project_id: String,
+    metadata_hash: BytesN<32>,
+    version: u32,
+    timestamp: u64,
+) {
+    MetadataAnchored {
+        project_id,
+        metadata_hash,
+        version,
+        timestamp,
+    }
+    .publish(env);
+}
+
+/// Structured event emitted when a project is registered
+/// Topics are ("registered", project_id, correlation_id); the correlation id is void
+/// unless the caller supplied one to tie the registration to a multi-contract flow
+#[contractevent(topics = ["registered"])]
+pub struct ProjectRegistered {
+    #[topic]
+    pub project_id: String,
+    #[topic]
+    pub correlation_id: Option<BytesN<32>>,
+    pub owner: Address,
+    pub expires_at: u64,
+}
+
+/// Emit a structured event when a project is registered
+pub fn emit_project_registered_event(
+    env: &Env,
+    project_id: String,
+    correlation_id: Option<BytesN<32>>,
+    owner: Address,
+    expires_at: u64,
+) {
+    ProjectRegistered {
+        project_id,
+        correlation_id,
+        owner,
+        expires_at,
+    }
+    .publish(env);
+}
+
+/// Structured event emitted when a retirement is recorded against a project
+/// Topics are ("retired", project_id) so indexers can follow a project's retirements
+#[contractevent(topics = ["retired"])]
+pub struct RetirementRecorded {
+    #[topic]
+    pub project_id: String,
+    pub token_id: u32,
+    pub amount: i128,
+    pub beneficiary: Address,
+}
+
+/// Emit a structured event when a retirement is recorded
+pub fn emit_retirement_recorded_event(
+    env: &Env,
+    project_id: String,
+    token_id: u32,
+    amount: i128,
+    beneficiary: Address,
+) {
+    RetirementRecorded {
+        project_id,
+        token_id,
+        amount,
+        beneficiary,
+    }
+    .publish(env);
+}
@@ -5,11 +5,19 @@ mod storage;
 mod types;
 mod validation;
 
-use events::emit_document_anchored_event;
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use events::{
+    emit_document_anchored_event, emit_metadata_anchored_event, emit_project_registered_event,
+    emit_retirement_recorded_event,
+};
+use shared_types::page_bounds;
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Vec};
 use storage::extend_instance_ttl;
-use types::{DocumentRecord, Error};
-use validation::validate_ipfs_cid;
+use types::{
+    Attestation, Dispute, DocumentRecord, Error, HashAlgorithm, HealthStatus, MetadataRecord,
+    NamedDocument, Project, ProjectDetail, ProjectPage, ProjectStatus, ProjectSupply,
+    RetirementRecord, Revocation, RevocationReason, RevocationStatus, VerifierStats,
+};
+use validation::{is_valid_transition, validate_ipfs_cid};
 
 #[contract]
 pub struct ProjectRegistry;
@@ -29,31 +37,817 @@ impl ProjectRegistry {
         Ok(())
     }
 
+    /// Upgrade the contract to new Wasm, preserving all storage (admin only)
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &caller)?;
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Pause project registration, attestation and anchoring (admin only)
+    pub fn pause(env: Env, caller: Address) -> Result<(), Error> {
+        require_admin(&env, &caller)?;
+
+        storage::set_paused(&env, true);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Resume a paused contract (admin only)
+    pub fn unpause(env: Env, caller: Address) -> Result<(), Error> {
+        require_admin(&env, &caller)?;
+
+        storage::set_paused(&env, false);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Set the coordinator that may pause and resume the registry together
+    /// with the other contracts during an incident (admin only)
+    pub fn set_coordinator(env: Env, caller: Address, coordinator: Address) -> Result<(), Error> {
+        require_admin(&env, &caller)?;
+
+        storage::set_coordinator(&env, &coordinator);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Pause or resume on behalf of the configured coordinator
+    pub fn set_paused_by_coordinator(
+        env: Env,
+        coordinator_caller: Address,
+        paused: bool,
+    ) -> Result<(), Error> {
+        coordinator_caller.require_auth();
+
+        if storage::get_coordinator(&env) != Some(coordinator_caller) {
+            return Err(Error::NotAuthorized);
+        }
+
+        storage::set_paused(&env, paused);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Set the governance address (admin only)
+    pub fn set_governance(env: Env, governance: Address) -> Result<(), Error> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+
+        storage::set_governance(&env, &governance);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
     /// Register a new project and assign initial owner (admin only)
-    pub fn register_project(env: Env, project_id: String, owner: Address) -> Result<(), Error> {
+    /// The crediting period ends at `expires_at` unless the project is renewed.
+    /// A `correlation_id`, when given, is added to the event topics for tracing
+    pub fn register_project(
+        env: Env,
+        project_id: String,
+        owner: Address,
+        expires_at: u64,
+        correlation_id: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+
+        if storage::has_project(&env, &project_id) {
+            return Err(Error::ProjectAlreadyExists);
+        }
+
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            return Err(Error::InvalidExpiry);
+        }
+
+        let project = Project {
+            owner: owner.clone(),
+            status: ProjectStatus::Registered,
+            registered_at: now,
+            expires_at,
+            expired: false,
+            revocation: RevocationStatus::NotRevoked,
+            total_retired: 0,
+        };
+
+        storage::set_project(&env, &project_id, &project);
+        storage::add_project_to_status(&env, ProjectStatus::Registered, &project_id);
+        storage::increment_project_count(&env);
+        storage::add_project_id(&env, &project_id);
+        extend_instance_ttl(&env);
+
+        emit_project_registered_event(&env, project_id, correlation_id, owner, expires_at);
+
+        Ok(())
+    }
+
+    /// Update the lifecycle status of a project (admin only)
+    /// Only transitions allowed by `is_valid_transition` are accepted. Expired projects cannot be moved to `Verified` until renewed, and
+    /// verification requires a quorum of non-expired attestations
+    pub fn set_project_status(
+        env: Env,
+        project_id: String,
+        status: ProjectStatus,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+
+        let mut project = storage::get_project(&env, &project_id)?;
+
+        if !is_valid_transition(project.status, status) {
+            return Err(Error::InvalidTransition);
+        }
+
+        if status == ProjectStatus::Verified {
+            ensure_methodology_not_frozen(&env, &project_id)?;
+
+            if is_expired(&env, &project) {
+                return Err(Error::ProjectExpired);
+            }
+
+            let valid_attestations = valid_attestations(&env, &project_id);
+            if valid_attestations.len() < storage::get_attestation_quorum(&env) {
+                return Err(Error::QuorumNotMet);
+            }
+        }
+
+        set_status(&env, &project_id, &mut project, status);
+        storage::set_project(&env, &project_id, &project);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Extend the crediting period of a project (governance only)
+    pub fn renew_project(
+        env: Env,
+        caller: Address,
+        project_id: String,
+        new_expiry: u64,
+    ) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        let mut project = storage::get_project(&env, &project_id)?;
+
+        if new_expiry <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+
+        project.expires_at = new_expiry;
+        storage::set_project(&env, &project_id, &project);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Transfer project ownership to another address
+    pub fn transfer_project_ownership(
+        env: Env,
+        project_id: String,
+        new_owner: Address,
+    ) -> Result<(), Error> {
+        let mut project = storage::get_project(&env, &project_id)?;
+        project.owner.require_auth();
+
+        project.owner = new_owner;
+        storage::set_project(&env, &project_id, &project);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Record the jurisdiction a project operates in (owner only)
+    pub fn set_project_jurisdiction(
+        env: Env,
+        project_id: String,
+        jurisdiction: String,
+    ) -> Result<(), Error> {
+        let project = storage::get_project(&env, &project_id)?;
+        project.owner.require_auth();
+
+        storage::set_project_jurisdiction(&env, &project_id, &jurisdiction);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Record the methodology a project is credited under (admin only)
+    pub fn set_project_methodology(
+        env: Env,
+        project_id: String,
+        methodology_id: u32,
+    ) -> Result<(), Error> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+
+        if !storage::has_project(&env, &project_id) {
+            return Err(Error::ProjectNotFound);
+        }
+
+        if let Ok(previous) = storage::get_project_methodology(&env, &project_id) {
+            storage::remove_project_from_methodology(&env, previous, &project_id);
+        }
+        storage::set_project_methodology(&env, &project_id, methodology_id);
+        storage::add_project_to_methodology(&env, methodology_id, &project_id);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Block verification and new token links for every project under a
+    /// methodology found to be flawed, until `unfreeze_methodology` (governance only)
+    pub fn freeze_methodology(env: Env, caller: Address, methodology_id: u32) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        storage::set_methodology_frozen(&env, methodology_id, true);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Lift a freeze placed by `freeze_methodology` (governance only)
+    pub fn unfreeze_methodology(
+        env: Env,
+        caller: Address,
+        methodology_id: u32,
+    ) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        storage::set_methodology_frozen(&env, methodology_id, false);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Link an issued carbon asset token to the project it was minted for (admin only)
+    /// The token's `credits` are added to the credits issued to the project.
+    pub fn link_token(
+        env: Env,
+        project_id: String,
+        token_id: u32,
+        credits: i128,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
         let admin = storage::get_admin(&env)?;
         admin.require_auth();
 
-        if storage::has_project_owner(&env, &project_id) {
-            return Err(Error::ProjectAlreadyExists);
+        if credits <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        if !storage::has_project(&env, &project_id) {
+            return Err(Error::ProjectNotFound);
+        }
+
+        ensure_methodology_not_frozen(&env, &project_id)?;
+
+        if storage::has_token_project(&env, token_id) {
+            return Err(Error::TokenAlreadyLinked);
+        }
+
+        let issued = storage::get_issued_credits(&env, &project_id)
+            .checked_add(credits)
+            .ok_or(Error::InvalidAmount)?;
+
+        storage::set_token_project(&env, token_id, &project_id);
+        storage::set_token_credits(&env, token_id, credits);
+        storage::set_issued_credits(&env, &project_id, issued);
+        storage::increment_linked_token_count(&env, &project_id);
+        let mut token_ids = storage::get_project_tokens(&env, &project_id);
+        token_ids.push_back(token_id);
+        storage::set_project_tokens(&env, &project_id, &token_ids);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Link a minted batch of tokens to a project in one call (governance or
+    /// the carbon asset contract), each token carrying `credits`. Fails
+    /// without linking any if a token is already linked or appears twice in
+    /// the batch.
+    pub fn batch_link_tokens(
+        env: Env,
+        caller: Address,
+        project_id: String,
+        token_ids: Vec<u32>,
+        credits: i128,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        caller.require_auth();
+
+        if caller != storage::get_governance(&env)?
+            && storage::get_carbon_asset_contract(&env) != Some(caller)
+        {
+            return Err(Error::NotAuthorized);
+        }
+
+        if token_ids.is_empty() {
+            return Err(Error::EmptyBatch);
+        }
+
+        if credits <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        if !storage::has_project(&env, &project_id) {
+            return Err(Error::ProjectNotFound);
+        }
+
+        ensure_methodology_not_frozen(&env, &project_id)?;
+
+        for (index, token_id) in token_ids.iter().enumerate() {
+            if storage::has_token_project(&env, token_id)
+                || token_ids.slice(..index as u32).contains(token_id)
+            {
+                return Err(Error::TokenAlreadyLinked);
+            }
+        }
+
+        let issued = credits
+            .checked_mul(token_ids.len() as i128)
+            .and_then(|batch| storage::get_issued_credits(&env, &project_id).checked_add(batch))
+            .ok_or(Error::InvalidAmount)?;
+
+        let mut linked = storage::get_project_tokens(&env, &project_id);
+        for token_id in token_ids.iter() {
+            storage::set_token_project(&env, token_id, &project_id);
+            storage::set_token_credits(&env, token_id, credits);
+            storage::increment_linked_token_count(&env, &project_id);
+            linked.push_back(token_id);
+        }
+        storage::set_project_tokens(&env, &project_id, &linked);
+        storage::set_issued_credits(&env, &project_id, issued);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Set the carbon asset contract allowed to record retirements (admin only)
+    pub fn set_carbon_asset_contract(
+        env: Env,
+        caller: Address,
+        contract: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &caller)?;
+
+        storage::set_carbon_asset_contract(&env, &contract);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Record the retirement of a linked token against its project (carbon asset contract only)
+    /// The retirement is appended to the project's log and added to its `total_retired`.
+    /// Each token is retired once, for no more than the credits it was linked with.
+    pub fn record_retirement(
+        env: Env,
+        caller: Address,
+        project_id: String,
+        token_id: u32,
+        amount: i128,
+        beneficiary: Address,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        caller.require_auth();
+
+        if storage::get_carbon_asset_contract(&env) != Some(caller) {
+            return Err(Error::NotAuthorized);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut project = storage::get_project(&env, &project_id)?;
+
+        if storage::get_token_project(&env, token_id)? != project_id {
+            return Err(Error::TokenNotLinked);
+        }
+
+        if storage::is_token_retired(&env, token_id) {
+            return Err(Error::TokenAlreadyRetired);
+        }
+
+        if amount > storage::get_token_credits(&env, token_id) {
+            return Err(Error::OverRetirement);
+        }
+
+        let total_retired = project
+            .total_retired
+            .checked_add(amount)
+            .ok_or(Error::OverRetirement)?;
+        if total_retired > storage::get_issued_credits(&env, &project_id) {
+            return Err(Error::OverRetirement);
+        }
+
+        project.total_retired = total_retired;
+        storage::set_project(&env, &project_id, &project);
+        storage::set_token_retired(&env, token_id);
+        storage::add_retirement(
+            &env,
+            &project_id,
+            &RetirementRecord {
+                token_id,
+                amount,
+                beneficiary: beneficiary.clone(),
+                retired_at: env.ledger().timestamp(),
+            },
+        );
+        extend_instance_ttl(&env);
+
+        emit_retirement_recorded_event(&env, project_id, token_id, amount, beneficiary);
+
+        Ok(())
+    }
+
+    /// Revoke a project with a structured reason code (governance only)
+    /// The project is moved to `Rejected` and the revocation details are stored on it
+    pub fn revoke_project(
+        env: Env,
+        caller: Address,
+        project_id: String,
+        code: RevocationReason,
+        reason: String,
+    ) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        let mut project = storage::get_project(&env, &project_id)?;
+
+        if project.revocation != RevocationStatus::NotRevoked {
+            return Err(Error::ProjectRevoked);
+        }
+
+        if !is_valid_transition(project.status, ProjectStatus::Rejected) {
+            return Err(Error::InvalidTransition);
+        }
+
+        set_status(&env, &project_id, &mut project, ProjectStatus::Rejected);
+        project.revocation = RevocationStatus::Revoked(Revocation {
+            code,
+            reason,
+            revoked_at: env.ledger().timestamp(),
+        });
+
+        storage::set_project(&env, &project_id, &project);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Temporarily withdraw a project without revoking it (governance only)
+    /// The project moves to `Inactive` and drops out of `list_projects`
+    /// unless inactive projects are asked for. Rejected and retired projects
+    /// cannot be deactivated.
+    pub fn deactivate_project(env: Env, caller: Address, project_id: String) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        let mut project = storage::get_project(&env, &project_id)?;
+
+        if matches!(
+            project.status,
+            ProjectStatus::Rejected | ProjectStatus::Retired | ProjectStatus::Inactive
+        ) {
+            return Err(Error::InvalidTransition);
+        }
+
+        storage::set_status_before_inactive(&env, &project_id, project.status);
+        set_status(&env, &project_id, &mut project, ProjectStatus::Inactive);
+        storage::set_project(&env, &project_id, &project);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Return an inactive project to the status it held before deactivation
+    /// (governance only)
+    pub fn reactivate_project(env: Env, caller: Address, project_id: String) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        let mut project = storage::get_project(&env, &project_id)?;
+
+        if project.status != ProjectStatus::Inactive {
+            return Err(Error::InvalidTransition);
+        }
+
+        let status = storage::get_status_before_inactive(&env, &project_id)
+            .unwrap_or(ProjectStatus::Registered);
+        storage::remove_status_before_inactive(&env, &project_id);
+        set_status(&env, &project_id, &mut project, status);
+        storage::set_project(&env, &project_id, &project);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Add an address to the verifier registry (governance only)
+    pub fn add_verifier(env: Env, caller: Address, verifier: Address) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        storage::set_verifier(&env, &verifier);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Remove an address from the verifier registry (governance only)
+    pub fn remove_verifier(env: Env, caller: Address, verifier: Address) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        storage::remove_verifier(&env, &verifier);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Set the number of valid attestations required for verification (governance only)
+    pub fn set_attestation_quorum(env: Env, caller: Address, quorum: u32) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        if quorum == 0 {
+            return Err(Error::InvalidAttestationConfig);
+        }
+
+        storage::set_attestation_quorum(&env, quorum);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Set how long new attestations remain valid, in seconds (governance only)
+    pub fn set_attestation_validity(env: Env, caller: Address, validity: u64) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        if validity == 0 {
+            return Err(Error::InvalidAttestationConfig);
         }
 
-        storage::set_project_owner(&env, &project_id, &owner);
+        storage::set_attestation_validity(&env, validity);
         extend_instance_ttl(&env);
 
         Ok(())
     }
 
-    /// Transfer project ownership to another address
-    pub fn transfer_project_ownership(
+    /// Set the minimum seconds between metadata updates of a project (governance only)
+    pub fn set_min_update_interval(env: Env, caller: Address, interval: u64) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        storage::set_min_update_interval(&env, interval);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Set how many metadata versions each project keeps, evicting the
+    /// oldest beyond that on update; 0 keeps every version (governance only)
+    pub fn set_max_versions(env: Env, caller: Address, max_versions: u32) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        storage::set_max_metadata_versions(&env, max_versions);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Attest to a project as a registered verifier
+    /// A repeat attestation from the same verifier replaces the previous one
+    pub fn attest_project(
         env: Env,
+        verifier: Address,
         project_id: String,
-        new_owner: Address,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        verifier.require_auth();
+
+        if !storage::is_verifier(&env, &verifier) {
+            return Err(Error::NotVerifier);
+        }
+
+        if !storage::has_project(&env, &project_id) {
+            return Err(Error::ProjectNotFound);
+        }
+
+        record_attestation(&env, &verifier, &project_id, evidence_hash);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Attest to an address's KYC as a registered verifier, replacing any
+    /// earlier attestation for it. It counts for the attestation validity.
+    pub fn attest_address(
+        env: Env,
+        verifier: Address,
+        account: Address,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        verifier.require_auth();
+
+        if !storage::is_verifier(&env, &verifier) {
+            return Err(Error::NotVerifier);
+        }
+
+        let now = env.ledger().timestamp();
+        let attestation = Attestation {
+            verifier,
+            evidence_hash,
+            attested_at: now,
+            expires_at: now.saturating_add(storage::get_attestation_validity(&env)),
+        };
+        storage::set_address_attestation(&env, &account, &attestation);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Attest to several projects in one call as a registered verifier
+    /// Fails without recording anything if any project does not exist
+    pub fn batch_attest(
+        env: Env,
+        verifier: Address,
+        attestations: Vec<(String, BytesN<32>)>, // Vec of (project_id, evidence_hash)
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+
+        verifier.require_auth();
+
+        if !storage::is_verifier(&env, &verifier) {
+            return Err(Error::NotVerifier);
+        }
+
+        if attestations.is_empty() {
+            return Err(Error::EmptyBatch);
+        }
+
+        // Validate the whole batch before writing anything
+        for (project_id, _) in attestations.iter() {
+            if !storage::has_project(&env, &project_id) {
+                return Err(Error::ProjectNotFound);
+            }
+        }
+
+        for (project_id, evidence_hash) in attestations.iter() {
+            record_attestation(&env, &verifier, &project_id, evidence_hash);
+        }
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Raise a dispute against a project's verification
+    pub fn raise_dispute(
+        env: Env,
+        challenger: Address,
+        project_id: String,
+        reason: String,
+    ) -> Result<(), Error> {
+        challenger.require_auth();
+
+        if !storage::has_project(&env, &project_id) {
+            return Err(Error::ProjectNotFound);
+        }
+
+        if storage::get_dispute(&env, &project_id).is_some() {
+            return Err(Error::DisputeAlreadyOpen);
+        }
+
+        let dispute = Dispute {
+            challenger,
+            reason,
+            raised_at: env.ledger().timestamp(),
+        };
+        storage::set_dispute(&env, &project_id, &dispute);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Resolve an open dispute (governance only)
+    /// When upheld, the project's attestations are invalidated and counted
+    /// against each attesting verifier
+    pub fn resolve_dispute(
+        env: Env,
+        caller: Address,
+        project_id: String,
+        upheld: bool,
+    ) -> Result<(), Error> {
+        require_governance(&env, &caller)?;
+
+        if storage::get_dispute(&env, &project_id).is_none() {
+            return Err(Error::NoOpenDispute);
+        }
+
+        if upheld {
+            for attestation in storage::get_attestations(&env, &project_id).iter() {
+                let mut stats = storage::get_verifier_stats(&env, &attestation.verifier);
+                stats.attestations_invalidated += 1;
+                storage::set_verifier_stats(&env, &attestation.verifier, &stats);
+            }
+            storage::set_attestations(&env, &project_id, &Vec::new(&env));
+        }
+
+        storage::remove_dispute(&env, &project_id);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Anchor the first metadata hash for a project (owner only).
+    /// `algorithm` records how the hash was computed and defaults to SHA-256.
+    pub fn anchor_metadata(
+        env: Env,
+        project_id: String,
+        metadata_hash: BytesN<32>,
+        algorithm: Option<HashAlgorithm>,
+    ) -> Result<u32, Error> {
+        if !storage::get_metadata_history(&env, &project_id).is_empty() {
+            return Err(Error::MetadataAlreadyAnchored);
+        }
+
+        let owner = storage::get_project_owner(&env, &project_id)?;
+        owner.require_auth();
+
+        append_metadata(&env, &project_id, owner, metadata_hash, algorithm)
+    }
+
+    /// Anchor a new version of a project's metadata hash (owner or
+    /// governance). The owner fails with `UpdateTooSoon` within the minimum
+    /// update interval of the latest version; governance is not held to it.
+    /// `algorithm` is tagged as in `anchor_metadata`.
+    pub fn update_project_metadata(
+        env: Env,
+        caller: Address,
+        project_id: String,
+        metadata_hash: BytesN<32>,
+        algorithm: Option<HashAlgorithm>,
+    ) -> Result<u32, Error> {
+        let Some(latest) = storage::get_metadata_history(&env, &project_id).last() else {
+            return Err(Error::NoMetadataFound);
+        };
+
+        caller.require_auth();
+
+        if caller != storage::get_governance(&env)? {
+            if storage::get_project_owner(&env, &project_id)? != caller {
+                return Err(Error::NotAuthorized);
+            }
+
+            let interval = storage::get_min_update_interval(&env);
+            if env.ledger().timestamp() < latest.timestamp.saturating_add(interval) {
+                return Err(Error::UpdateTooSoon);
+            }
+        }
+
+        append_metadata(&env, &project_id, caller, metadata_hash, algorithm)
+    }
+
+    /// Anchor the hash of a named supporting document to a project (owner
+    /// only). Each name is anchored once; a revised document needs a new name.
+    /// `algorithm` records how `hash` was computed and defaults to SHA-256.
+    pub fn add_document(
+        env: Env,
+        caller: Address,
+        project_id: String,
+        name: String,
+        hash: BytesN<32>,
+        algorithm: Option<HashAlgorithm>,
     ) -> Result<(), Error> {
-        let current_owner = storage::get_project_owner(&env, &project_id)?;
-        current_owner.require_auth();
+        ensure_not_paused(&env)?;
+        caller.require_auth();
+
+        if storage::get_project_owner(&env, &project_id)? != caller {
+            return Err(Error::NotAuthorized);
+        }
+
+        let mut documents = storage::get_project_documents(&env, &project_id);
+        if documents.iter().any(|document| document.name == name) {
+            return Err(Error::DocumentAlreadyExists);
+        }
 
-        storage::set_project_owner(&env, &project_id, &new_owner);
+        documents.push_back(NamedDocument {
+            name,
+            hash,
+            algorithm: algorithm.unwrap_or_default(),
+        });
+        storage::set_project_documents(&env, &project_id, &documents);
         extend_instance_ttl(&env);
 
         Ok(())
@@ -66,6 +860,8 @@ impl ProjectRegistry {
         ipfs_cid: String,
         document_type: String,
     ) -> Result<u32, Error> {
+        ensure_not_paused(&env)?;
+
         let owner = storage::get_project_owner(&env, &project_id)?;
         owner.require_auth();
 
@@ -114,6 +910,8 @@ impl ProjectRegistry {
         project_id: String,
         documents: Vec<(String, String)>, // Vec of (ipfs_cid, document_type)
     ) -> Result<Vec<u32>, Error> {
+        ensure_not_paused(&env)?;
+
         let owner = storage::get_project_owner(&env, &project_id)?;
         owner.require_auth();
 
@@ -183,6 +981,82 @@ impl ProjectRegistry {
         Ok(latest.ipfs_cid)
     }
 
+    /// Get the latest anchored metadata record for a project
+    pub fn get_metadata(env: Env, project_id: String) -> Result<MetadataRecord, Error> {
+        storage::get_metadata_history(&env, &project_id)
+            .last()
+            .ok_or(Error::NoMetadataFound)
+    }
+
+    /// Get the metadata hash anchored at a specific version, if it exists
+    pub fn get_metadata_at_version(
+        env: Env,
+        project_id: String,
+        version: u32,
+    ) -> Option<BytesN<32>> {
+        metadata_at_version(&env, &project_id, version)
+    }
+
+    /// Compare two metadata versions, returning whether their hashes differ
+    pub fn compare_versions(env: Env, project_id: String, v1: u32, v2: u32) -> Result<bool, Error> {
+        let hash1 = metadata_at_version(&env, &project_id, v1).ok_or(Error::VersionNotFound)?;
+        let hash2 = metadata_at_version(&env, &project_id, v2).ok_or(Error::VersionNotFound)?;
+        Ok(hash1 != hash2)
+    }
+
+    /// Check `candidate`, computed with `algorithm`, against the latest
+    /// metadata hash anchored for a project. A hash tagged with a different
+    /// algorithm or a project without metadata does not verify.
+    pub fn verify_metadata(
+        env: Env,
+        project_id: String,
+        candidate: BytesN<32>,
+        algorithm: HashAlgorithm,
+    ) -> bool {
+        storage::get_metadata_history(&env, &project_id)
+            .last()
+            .is_some_and(|record| {
+                record.algorithm == algorithm && record.metadata_hash == candidate
+            })
+    }
+
+    /// Hash `data` with the algorithm the latest metadata version was
+    /// anchored with and check it against the anchored hash
+    pub fn verify_metadata_bytes(env: Env, project_id: String, data: Bytes) -> bool {
+        storage::get_metadata_history(&env, &project_id)
+            .last()
+            .is_some_and(|record| digest(&env, record.algorithm, &data) == record.metadata_hash)
+    }
+
+    /// Check `candidate`, computed with `algorithm`, against the hash anchored
+    /// for a project's named document. A hash tagged with a different
+    /// algorithm, an unknown project or an unknown name does not verify.
+    pub fn verify_document(
+        env: Env,
+        project_id: String,
+        name: String,
+        candidate: BytesN<32>,
+        algorithm: HashAlgorithm,
+    ) -> bool {
+        find_document(&env, &project_id, &name)
+            .is_some_and(|document| document.algorithm == algorithm && document.hash == candidate)
+    }
+
+    /// Hash `data` with the algorithm the named document was anchored with
+    /// and check it against the anchored hash
+    pub fn verify_document_bytes(env: Env, project_id: String, name: String, data: Bytes) -> bool {
+        let Some(document) = find_document(&env, &project_id, &name) else {
+            return false;
+        };
+
+        digest(&env, document.algorithm, &data) == document.hash
+    }
+
+    /// Get the named document hashes anchored for a project, in the order added
+    pub fn get_documents(env: Env, project_id: String) -> Vec<NamedDocument> {
+        storage::get_project_documents(&env, &project_id)
+    }
+
     /// Get the complete document history for a project
     pub fn get_document_history(
         env: Env,
@@ -196,15 +1070,379 @@ impl ProjectRegistry {
         storage::get_anchorer_projects(&env, &anchorer)
     }
 
+    /// Get a project, flagging it as expired if its crediting period has ended
+    pub fn get_project(env: Env, project_id: String) -> Result<Project, Error> {
+        let mut project = storage::get_project(&env, &project_id)?;
+        project.expired = is_expired(&env, &project);
+        Ok(project)
+    }
+
+    /// Get the status of each project in `project_ids`, in the same order,
+    /// with `None` for projects that are not registered
+    pub fn get_project_statuses(env: Env, project_ids: Vec<String>) -> Vec<Option<ProjectStatus>> {
+        let mut statuses = Vec::new(&env);
+        for project_id in project_ids.iter() {
+            let status = storage::get_project(&env, &project_id)
+                .ok()
+                .map(|project| project.status);
+            statuses.push_back(status);
+        }
+        statuses
+    }
+
+    /// Get the jurisdiction recorded for a project
+    pub fn get_project_jurisdiction(env: Env, project_id: String) -> Result<String, Error> {
+        storage::get_project_jurisdiction(&env, &project_id)
+    }
+
+    /// Get the methodology a project is credited under
+    pub fn get_project_methodology(env: Env, project_id: String) -> Result<u32, Error> {
+        storage::get_project_methodology(&env, &project_id)
+    }
+
+    /// Get the projects credited under a methodology
+    pub fn get_projects_by_methodology(env: Env, methodology_id: u32) -> Vec<String> {
+        storage::get_methodology_projects(&env, methodology_id)
+    }
+
+    /// Check whether a methodology is frozen
+    pub fn is_methodology_frozen(env: Env, methodology_id: u32) -> bool {
+        storage::is_methodology_frozen(&env, methodology_id)
+    }
+
+    /// Get the project a token was linked to
+    pub fn get_token_project(env: Env, token_id: u32) -> Result<String, Error> {
+        storage::get_token_project(&env, token_id)
+    }
+
+    /// Get the tokens linked to a project, in link order
+    pub fn get_project_tokens(env: Env, project_id: String) -> Vec<u32> {
+        storage::get_project_tokens(&env, &project_id)
+    }
+
+    /// Get the retirements recorded against a project, oldest first
+    pub fn get_retirements(env: Env, project_id: String) -> Vec<RetirementRecord> {
+        storage::get_retirements(&env, &project_id)
+    }
+
+    /// Get a project's issued credits, summed over its linked tokens, against those retired
+    pub fn get_project_supply(env: Env, project_id: String) -> Result<ProjectSupply, Error> {
+        let project = storage::get_project(&env, &project_id)?;
+        let total_issued = storage::get_issued_credits(&env, &project_id);
+
+        Ok(ProjectSupply {
+            total_issued,
+            total_retired: project.total_retired,
+            net: total_issued - project.total_retired,
+        })
+    }
+
+    /// Get a project together with its attestation and linked token counts
+    /// in one call, or `None` if it is not registered
+    pub fn get_project_detail(env: Env, project_id: String) -> Option<ProjectDetail> {
+        let project = Self::get_project(env.clone(), project_id.clone()).ok()?;
+
+        Some(ProjectDetail {
+            attestation_count: storage::get_attestations(&env, &project_id).len(),
+            linked_token_count: storage::get_linked_token_count(&env, &project_id),
+            status: project.status,
+            project,
+        })
+    }
+
+    /// Get the carbon asset contract allowed to record retirements, if set
+    pub fn get_carbon_asset_contract(env: Env) -> Option<Address> {
+        storage::get_carbon_asset_contract(&env)
+    }
+
+    /// List up to `limit` project IDs starting at `cursor`, in registration order.
+    /// Inactive projects are skipped unless `include_inactive` is set.
+    pub fn list_projects(env: Env, cursor: u32, limit: u32, include_inactive: bool) -> ProjectPage {
+        let mut project_ids = storage::get_project_ids(&env);
+        if !include_inactive {
+            let inactive = storage::get_projects_by_status(&env, ProjectStatus::Inactive);
+            let mut active_ids = Vec::new(&env);
+            for project_id in project_ids.iter() {
+                if !inactive.contains(&project_id) {
+                    active_ids.push_back(project_id);
+                }
+            }
+            project_ids = active_ids;
+        }
+        let (start, end, next_cursor) = page_bounds(project_ids.len(), cursor, limit);
+
+        ProjectPage {
+            items: project_ids.slice(start..end),
+            next_cursor,
+            total: project_ids.len(),
+        }
+    }
+
+    /// Get the IDs of all projects currently in a status
+    pub fn get_projects_by_status(env: Env, status: ProjectStatus) -> Vec<String> {
+        storage::get_projects_by_status(&env, status)
+    }
+
+    /// Get all attestations recorded for a project, including expired ones
+    pub fn get_attestations(env: Env, project_id: String) -> Vec<Attestation> {
+        storage::get_attestations(&env, &project_id)
+    }
+
+    /// Whether an address holds a KYC attestation that has not yet expired
+    pub fn has_valid_address_attestation(env: Env, account: Address) -> bool {
+        storage::get_address_attestation(&env, &account)
+            .is_some_and(|attestation| env.ledger().timestamp() < attestation.expires_at)
+    }
+
+    /// Get the attestations for a project that have not yet expired
+    pub fn get_valid_attestations(env: Env, project_id: String) -> Vec<Attestation> {
+        valid_attestations(&env, &project_id)
+    }
+
+    /// Get the open dispute for a project, if any
+    pub fn get_dispute(env: Env, project_id: String) -> Option<Dispute> {
+        storage::get_dispute(&env, &project_id)
+    }
+
+    /// Get the reputation counters for a verifier
+    pub fn get_verifier_stats(env: Env, verifier: Address) -> VerifierStats {
+        storage::get_verifier_stats(&env, &verifier)
+    }
+
+    /// Get every project a verifier has attested, in first-attestation order
+    pub fn get_projects_attested_by(env: Env, verifier: Address) -> Vec<String> {
+        storage::get_verifier_projects(&env, &verifier)
+    }
+
+    /// Check whether an address is a registered verifier
+    pub fn is_verifier(env: Env, verifier: Address) -> bool {
+        storage::is_verifier(&env, &verifier)
+    }
+
+    /// Get the attestation quorum required for verification
+    pub fn get_attestation_quorum(env: Env) -> u32 {
+        storage::get_attestation_quorum(&env)
+    }
+
+    /// Get the minimum time between metadata updates by a project owner, in seconds
+    pub fn get_min_update_interval(env: Env) -> u64 {
+        storage::get_min_update_interval(&env)
+    }
+
+    /// Get how many metadata versions each project keeps, 0 for all
+    pub fn get_max_versions(env: Env) -> u32 {
+        storage::get_max_metadata_versions(&env)
+    }
+
+    /// Get the validity period applied to new attestations, in seconds
+    pub fn get_attestation_validity(env: Env) -> u64 {
+        storage::get_attestation_validity(&env)
+    }
+
     /// Get the owner of a project
     pub fn get_project_owner(env: Env, project_id: String) -> Result<Address, Error> {
         storage::get_project_owner(&env, &project_id)
     }
 
+    /// Report initialization, pause state and the number of registered projects
+    pub fn health_check(env: Env) -> HealthStatus {
+        HealthStatus {
+            initialized: storage::has_admin(&env),
+            paused: storage::is_paused(&env),
+            entity_count: storage::get_project_count(&env),
+        }
+    }
+
+    /// Get the configured coordinator, if any
+    pub fn get_coordinator(env: Env) -> Option<Address> {
+        storage::get_coordinator(&env)
+    }
+
+    /// Whether the contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        storage::is_paused(&env)
+    }
+
     /// Get the admin address
     pub fn get_admin(env: Env) -> Result<Address, Error> {
         storage::get_admin(&env)
     }
+
+    /// Get the governance address (defaults to the admin)
+    pub fn get_governance(env: Env) -> Result<Address, Error> {
+        storage::get_governance(&env)
+    }
+}
+
+/// Require the caller to be the admin address
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    if *caller != storage::get_admin(env)? {
+        return Err(Error::NotAuthorized);
+    }
+
+    Ok(())
+}
+
+/// Require the caller to be the governance address
+fn require_governance(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    if *caller != storage::get_governance(env)? {
+        return Err(Error::NotAuthorized);
+    }
+
+    Ok(())
+}
+
+/// Reject verification and token links for a project under a frozen methodology
+fn ensure_methodology_not_frozen(env: &Env, project_id: &String) -> Result<(), Error> {
+    if let Ok(methodology_id) = storage::get_project_methodology(env, project_id) {
+        if storage::is_methodology_frozen(env, methodology_id) {
+            return Err(Error::MethodologyFrozen);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reject state changes while the contract is paused
+fn ensure_not_paused(env: &Env) -> Result<(), Error> {
+    if storage::is_paused(env) {
+        return Err(Error::Paused);
+    }
+
+    Ok(())
+}
+
+/// Change a project's status and move it between the status index lists
+fn set_status(env: &Env, project_id: &String, project: &mut Project, status: ProjectStatus) {
+    storage::remove_project_from_status(env, project.status, project_id);
+    storage::add_project_to_status(env, status, project_id);
+    project.status = status;
+}
+
+/// Append a new metadata version for a project and emit the anchor event
+fn append_metadata(
+    env: &Env,
+    project_id: &String,
+    anchorer: Address,
+    metadata_hash: BytesN<32>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<u32, Error> {
+    ensure_not_paused(env)?;
+
+    let mut history = storage::get_metadata_history(env, project_id);
+    let version = match history.last() {
+        Some(latest) => latest.version + 1,
+        None => 1,
+    };
+    let timestamp = env.ledger().timestamp();
+
+    history.push_back(MetadataRecord {
+        metadata_hash: metadata_hash.clone(),
+        version,
+        timestamp,
+        anchorer,
+        algorithm: algorithm.unwrap_or_default(),
+    });
+
+    // Keep a rolling window; versions keep counting up from the latest entry
+    let max_versions = storage::get_max_metadata_versions(env);
+    while max_versions > 0 && history.len() > max_versions {
+        history.pop_front();
+    }
+    storage::set_metadata_history(env, project_id, &history);
+    extend_instance_ttl(env);
+
+    // Emitted after the new version is committed to storage
+    emit_metadata_anchored_event(env, project_id.clone(), metadata_hash, version, timestamp);
+
+    Ok(version)
+}
+
+/// Record an attestation, replacing any previous one from the same verifier
+fn record_attestation(
+    env: &Env,
+    verifier: &Address,
+    project_id: &String,
+    evidence_hash: BytesN<32>,
+) {
+    let now = env.ledger().timestamp();
+    let attestation = Attestation {
+        verifier: verifier.clone(),
+        evidence_hash,
+        attested_at: now,
+        expires_at: now.saturating_add(storage::get_attestation_validity(env)),
+    };
+
+    let existing = storage::get_attestations(env, project_id);
+    let mut attestations = Vec::new(env);
+    let mut replaced = false;
+    for item in existing.iter() {
+        if item.verifier == *verifier {
+            replaced = true;
+        } else {
+            attestations.push_back(item);
+        }
+    }
+    attestations.push_back(attestation);
+
+    storage::set_attestations(env, project_id, &attestations);
+
+    // Replacing the verifier's own attestation is not a new one
+    if !replaced {
+        let mut stats = storage::get_verifier_stats(env, verifier);
+        stats.attestations_made += 1;
+        storage::set_verifier_stats(env, verifier, &stats);
+    }
+
+    let mut attested_projects = storage::get_verifier_projects(env, verifier);
+    if !attested_projects.contains(project_id) {
+        attested_projects.push_back(project_id.clone());
+        storage::set_verifier_projects(env, verifier, &attested_projects);
+    }
+}
+
+/// Find the metadata hash anchored at a specific version
+fn metadata_at_version(env: &Env, project_id: &String, version: u32) -> Option<BytesN<32>> {
+    storage::get_metadata_history(env, project_id)
+        .iter()
+        .find(|record| record.version == version)
+        .map(|record| record.metadata_hash)
+}
+
+/// Hash `data` with `algorithm`
+fn digest(env: &Env, algorithm: HashAlgorithm, data: &Bytes) -> BytesN<32> {
+    match algorithm {
+        HashAlgorithm::Sha256 => env.crypto().sha256(data).to_bytes(),
+        HashAlgorithm::Keccak256 => env.crypto().keccak256(data).to_bytes(),
+    }
+}
+
+/// Find a project's named document
+fn find_document(env: &Env, project_id: &String, name: &String) -> Option<NamedDocument> {
+    storage::get_project_documents(env, project_id)
+        .iter()
+        .find(|document| document.name == *name)
+}
+
+/// Collect the attestations for a project that have not yet expired
+fn valid_attestations(env: &Env, project_id: &String) -> Vec<Attestation> {
+    let now = env.ledger().timestamp();
+    let mut valid = Vec::new(env);
+    for attestation in storage::get_attestations(env, project_id).iter() {
+        if now < attestation.expires_at {
+            valid.push_back(attestation);
+        }
+    }
+    valid
+}
+
+/// Check whether a project's crediting period has ended
+fn is_expired(env: &Env, project: &Project) -> bool {
+    env.ledger().timestamp() >= project.expires_at
 }
 
 #[cfg(test)]
@@ -1,17 +1,54 @@
-use crate::types::{DocumentRecord, Error};
+use crate::types::{
+    Attestation, Dispute, DocumentRecord, Error, MetadataRecord, NamedDocument, Project,
+    ProjectStatus, RetirementRecord, VerifierStats,
+};
 use soroban_sdk::{contracttype, Address, Env, String, Vec};
 
 const DAY_IN_LEDGERS: u32 = 17280; // Approximately 1 day worth of ledgers (5s per ledger)
 const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS; // 30 days
 const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
+const DEFAULT_ATTESTATION_QUORUM: u32 = 1;
+const DEFAULT_ATTESTATION_VALIDITY: u64 = 365 * 24 * 60 * 60; // 1 year in seconds
+
 #[contracttype]
 #[derive(Clone)]
 pub enum StorageKey {
     Admin,
-    ProjectOwner(String),
+    Governance,
+    Project(String),
     DocumentHistory(String),
     AncorerProjects(Address),
+    MetadataHistory(String),
+    ProjectsByStatus(ProjectStatus),
+    Verifier(Address),
+    Attestations(String),
+    AttestationQuorum,
+    AttestationValidity,
+    VerifierStats(Address),
+    Dispute(String),
+    ProjectJurisdiction(String),
+    TokenProject(u32),
+    Paused,
+    ProjectCount,
+    ProjectIds,
+    CarbonAssetContract,
+    Retirements(String),
+    LinkedTokenCount(String),
+    VerifierProjects(Address),
+    MinUpdateInterval,
+    ProjectTokens(String),
+    ProjectMethodology(String),
+    MethodologyProjects(u32),
+    FrozenMethodology(u32),
+    MaxMetadataVersions,
+    Coordinator,
+    ProjectDocuments(String),
+    StatusBeforeInactive(String),
+    AddressAttestation(Address),
+    RetiredToken(u32),
+    TokenCredits(u32),
+    IssuedCredits(String),
 }
 
 /// Extend the TTL of instance storage
@@ -37,28 +74,356 @@ pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&StorageKey::Admin, admin);
 }
 
-// Project owner storage functions
-pub fn has_project_owner(env: &Env, project_id: &String) -> bool {
-    let key = StorageKey::ProjectOwner(project_id.clone());
+// Governance storage functions
+/// Get the governance address, falling back to the admin when none has been set
+pub fn get_governance(env: &Env) -> Result<Address, Error> {
+    match env.storage().instance().get(&StorageKey::Governance) {
+        Some(governance) => Ok(governance),
+        None => get_admin(env),
+    }
+}
+
+pub fn set_governance(env: &Env, governance: &Address) {
+    env.storage()
+        .instance()
+        .set(&StorageKey::Governance, governance);
+}
+
+// Pause storage functions
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&StorageKey::Paused)
+        .unwrap_or(false)
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&StorageKey::Paused, &paused);
+}
+
+pub fn get_coordinator(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&StorageKey::Coordinator)
+}
+
+pub fn set_coordinator(env: &Env, coordinator: &Address) {
+    env.storage()
+        .instance()
+        .set(&StorageKey::Coordinator, coordinator);
+}
+
+// Project count storage functions
+pub fn get_project_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&StorageKey::ProjectCount)
+        .unwrap_or(0)
+}
+
+pub fn increment_project_count(env: &Env) {
+    let count = get_project_count(env);
+    env.storage()
+        .instance()
+        .set(&StorageKey::ProjectCount, &(count + 1));
+}
+
+/// IDs of every registered project, in registration order
+pub fn get_project_ids(env: &Env) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&StorageKey::ProjectIds)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn add_project_id(env: &Env, project_id: &String) {
+    let mut project_ids = get_project_ids(env);
+    project_ids.push_back(project_id.clone());
+    env.storage()
+        .persistent()
+        .set(&StorageKey::ProjectIds, &project_ids);
+    env.storage().persistent().extend_ttl(
+        &StorageKey::ProjectIds,
+        INSTANCE_LIFETIME_THRESHOLD,
+        INSTANCE_BUMP_AMOUNT,
+    );
+}
+
+// Project storage functions
+pub fn has_project(env: &Env, project_id: &String) -> bool {
+    let key = StorageKey::Project(project_id.clone());
     env.storage().persistent().has(&key)
 }
 
-pub fn get_project_owner(env: &Env, project_id: &String) -> Result<Address, Error> {
-    let key = StorageKey::ProjectOwner(project_id.clone());
+pub fn get_project(env: &Env, project_id: &String) -> Result<Project, Error> {
+    let key = StorageKey::Project(project_id.clone());
     env.storage()
         .persistent()
         .get(&key)
         .ok_or(Error::ProjectNotFound)
 }
 
-pub fn set_project_owner(env: &Env, project_id: &String, owner: &Address) {
-    let key = StorageKey::ProjectOwner(project_id.clone());
-    env.storage().persistent().set(&key, owner);
+pub fn set_project(env: &Env, project_id: &String, project: &Project) {
+    let key = StorageKey::Project(project_id.clone());
+    env.storage().persistent().set(&key, project);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+pub fn get_project_owner(env: &Env, project_id: &String) -> Result<Address, Error> {
+    Ok(get_project(env, project_id)?.owner)
+}
+
+// Project jurisdiction storage functions
+pub fn get_project_jurisdiction(env: &Env, project_id: &String) -> Result<String, Error> {
+    let key = StorageKey::ProjectJurisdiction(project_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::JurisdictionNotSet)
+}
+
+pub fn set_project_jurisdiction(env: &Env, project_id: &String, jurisdiction: &String) {
+    let key = StorageKey::ProjectJurisdiction(project_id.clone());
+    env.storage().persistent().set(&key, jurisdiction);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Project methodology storage functions
+pub fn get_project_methodology(env: &Env, project_id: &String) -> Result<u32, Error> {
+    let key = StorageKey::ProjectMethodology(project_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::MethodologyNotSet)
+}
+
+pub fn set_project_methodology(env: &Env, project_id: &String, methodology_id: u32) {
+    let key = StorageKey::ProjectMethodology(project_id.clone());
+    env.storage().persistent().set(&key, &methodology_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Projects recorded under a methodology, in the order they were assigned
+pub fn get_methodology_projects(env: &Env, methodology_id: u32) -> Vec<String> {
+    let key = StorageKey::MethodologyProjects(methodology_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn add_project_to_methodology(env: &Env, methodology_id: u32, project_id: &String) {
+    let key = StorageKey::MethodologyProjects(methodology_id);
+    let mut projects = get_methodology_projects(env, methodology_id);
+    if !projects.contains(project_id) {
+        projects.push_back(project_id.clone());
+        env.storage().persistent().set(&key, &projects);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+    }
+}
+
+pub fn remove_project_from_methodology(env: &Env, methodology_id: u32, project_id: &String) {
+    let key = StorageKey::MethodologyProjects(methodology_id);
+    let mut projects = get_methodology_projects(env, methodology_id);
+    if let Some(index) = projects.first_index_of(project_id) {
+        projects.remove(index);
+        env.storage().persistent().set(&key, &projects);
+    }
+}
+
+pub fn is_methodology_frozen(env: &Env, methodology_id: u32) -> bool {
+    let key = StorageKey::FrozenMethodology(methodology_id);
+    env.storage().persistent().get(&key).unwrap_or(false)
+}
+
+pub fn set_methodology_frozen(env: &Env, methodology_id: u32, frozen: bool) {
+    let key = StorageKey::FrozenMethodology(methodology_id);
+    if frozen {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// Token link storage functions
+pub fn get_token_project(env: &Env, token_id: u32) -> Result<String, Error> {
+    let key = StorageKey::TokenProject(token_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::TokenNotLinked)
+}
+
+pub fn has_token_project(env: &Env, token_id: u32) -> bool {
+    let key = StorageKey::TokenProject(token_id);
+    env.storage().persistent().has(&key)
+}
+
+pub fn set_token_project(env: &Env, token_id: u32, project_id: &String) {
+    let key = StorageKey::TokenProject(token_id);
+    env.storage().persistent().set(&key, project_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Tokens linked to a project, in link order
+pub fn get_project_tokens(env: &Env, project_id: &String) -> Vec<u32> {
+    let key = StorageKey::ProjectTokens(project_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_project_tokens(env: &Env, project_id: &String, token_ids: &Vec<u32>) {
+    let key = StorageKey::ProjectTokens(project_id.clone());
+    env.storage().persistent().set(&key, token_ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Carbon asset contract storage functions
+pub fn get_carbon_asset_contract(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&StorageKey::CarbonAssetContract)
+}
+
+pub fn set_carbon_asset_contract(env: &Env, contract: &Address) {
+    env.storage()
+        .instance()
+        .set(&StorageKey::CarbonAssetContract, contract);
+}
+
+// Retirement log storage functions
+pub fn get_retirements(env: &Env, project_id: &String) -> Vec<RetirementRecord> {
+    let key = StorageKey::Retirements(project_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn add_retirement(env: &Env, project_id: &String, record: &RetirementRecord) {
+    let key = StorageKey::Retirements(project_id.clone());
+    let mut retirements = get_retirements(env, project_id);
+    retirements.push_back(record.clone());
+    env.storage().persistent().set(&key, &retirements);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Whether a retirement has already been recorded for a token
+pub fn is_token_retired(env: &Env, token_id: u32) -> bool {
+    env.storage()
+        .persistent()
+        .has(&StorageKey::RetiredToken(token_id))
+}
+
+pub fn set_token_retired(env: &Env, token_id: u32) {
+    let key = StorageKey::RetiredToken(token_id);
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Number of tokens linked to a project
+pub fn get_linked_token_count(env: &Env, project_id: &String) -> u32 {
+    let key = StorageKey::LinkedTokenCount(project_id.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn increment_linked_token_count(env: &Env, project_id: &String) {
+    let key = StorageKey::LinkedTokenCount(project_id.clone());
+    let count = get_linked_token_count(env, project_id);
+    env.storage().persistent().set(&key, &(count + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Credits carried by a linked token
+pub fn get_token_credits(env: &Env, token_id: u32) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&StorageKey::TokenCredits(token_id))
+        .unwrap_or(0)
+}
+
+pub fn set_token_credits(env: &Env, token_id: u32, credits: i128) {
+    let key = StorageKey::TokenCredits(token_id);
+    env.storage().persistent().set(&key, &credits);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Credits issued to a project, summed over its linked tokens
+pub fn get_issued_credits(env: &Env, project_id: &String) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&StorageKey::IssuedCredits(project_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_issued_credits(env: &Env, project_id: &String, credits: i128) {
+    let key = StorageKey::IssuedCredits(project_id.clone());
+    env.storage().persistent().set(&key, &credits);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Status index storage functions
+pub fn get_projects_by_status(env: &Env, status: ProjectStatus) -> Vec<String> {
+    let key = StorageKey::ProjectsByStatus(status);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_projects_by_status(env: &Env, status: ProjectStatus, projects: &Vec<String>) {
+    let key = StorageKey::ProjectsByStatus(status);
+    env.storage().persistent().set(&key, projects);
     env.storage()
         .persistent()
         .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
 
+pub fn add_project_to_status(env: &Env, status: ProjectStatus, project_id: &String) {
+    let mut projects = get_projects_by_status(env, status);
+    if !projects.contains(project_id) {
+        projects.push_back(project_id.clone());
+        set_projects_by_status(env, status, &projects);
+    }
+}
+
+pub fn remove_project_from_status(env: &Env, status: ProjectStatus, project_id: &String) {
+    let mut projects = get_projects_by_status(env, status);
+    if let Some(index) = projects.first_index_of(project_id) {
+        projects.remove(index);
+        set_projects_by_status(env, status, &projects);
+    }
+}
+
 // Document history storage functions
 pub fn get_document_history(env: &Env, project_id: &String) -> Result<Vec<DocumentRecord>, Error> {
     let key = StorageKey::DocumentHistory(project_id.clone());
@@ -76,6 +441,62 @@ pub fn set_document_history(env: &Env, project_id: &String, history: &Vec<Docume
         .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
 
+// Metadata history storage functions
+pub fn get_metadata_history(env: &Env, project_id: &String) -> Vec<MetadataRecord> {
+    let key = StorageKey::MetadataHistory(project_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_metadata_history(env: &Env, project_id: &String, history: &Vec<MetadataRecord>) {
+    let key = StorageKey::MetadataHistory(project_id.clone());
+    env.storage().persistent().set(&key, history);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Deactivation storage functions
+/// Status a project held before it was deactivated
+pub fn get_status_before_inactive(env: &Env, project_id: &String) -> Option<ProjectStatus> {
+    env.storage()
+        .persistent()
+        .get(&StorageKey::StatusBeforeInactive(project_id.clone()))
+}
+
+pub fn set_status_before_inactive(env: &Env, project_id: &String, status: ProjectStatus) {
+    let key = StorageKey::StatusBeforeInactive(project_id.clone());
+    env.storage().persistent().set(&key, &status);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+pub fn remove_status_before_inactive(env: &Env, project_id: &String) {
+    env.storage()
+        .persistent()
+        .remove(&StorageKey::StatusBeforeInactive(project_id.clone()));
+}
+
+// Named document hash storage functions
+pub fn get_project_documents(env: &Env, project_id: &String) -> Vec<NamedDocument> {
+    let key = StorageKey::ProjectDocuments(project_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_project_documents(env: &Env, project_id: &String, documents: &Vec<NamedDocument>) {
+    let key = StorageKey::ProjectDocuments(project_id.clone());
+    env.storage().persistent().set(&key, documents);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
 // Anchorer index storage functions
 pub fn get_anchorer_projects(env: &Env, anchorer: &Address) -> Result<Vec<String>, Error> {
     let key = StorageKey::AncorerProjects(anchorer.clone());
@@ -92,3 +513,159 @@ pub fn set_anchorer_projects(env: &Env, anchorer: &Address, projects: &Vec<Strin
         .persistent()
         .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
+
+// Verifier storage functions
+pub fn is_verifier(env: &Env, verifier: &Address) -> bool {
+    let key = StorageKey::Verifier(verifier.clone());
+    env.storage().persistent().has(&key)
+}
+
+pub fn set_verifier(env: &Env, verifier: &Address) {
+    let key = StorageKey::Verifier(verifier.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+pub fn remove_verifier(env: &Env, verifier: &Address) {
+    let key = StorageKey::Verifier(verifier.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// Attestation storage functions
+pub fn get_attestations(env: &Env, project_id: &String) -> Vec<Attestation> {
+    let key = StorageKey::Attestations(project_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_attestations(env: &Env, project_id: &String, attestations: &Vec<Attestation>) {
+    let key = StorageKey::Attestations(project_id.clone());
+    env.storage().persistent().set(&key, attestations);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Address attestation storage functions
+/// Latest KYC attestation a verifier made for an address
+pub fn get_address_attestation(env: &Env, account: &Address) -> Option<Attestation> {
+    env.storage()
+        .persistent()
+        .get(&StorageKey::AddressAttestation(account.clone()))
+}
+
+pub fn set_address_attestation(env: &Env, account: &Address, attestation: &Attestation) {
+    let key = StorageKey::AddressAttestation(account.clone());
+    env.storage().persistent().set(&key, attestation);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Verifier stats storage functions
+pub fn get_verifier_stats(env: &Env, verifier: &Address) -> VerifierStats {
+    let key = StorageKey::VerifierStats(verifier.clone());
+    env.storage().persistent().get(&key).unwrap_or_default()
+}
+
+pub fn set_verifier_stats(env: &Env, verifier: &Address, stats: &VerifierStats) {
+    let key = StorageKey::VerifierStats(verifier.clone());
+    env.storage().persistent().set(&key, stats);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Verifier project index storage functions
+pub fn get_verifier_projects(env: &Env, verifier: &Address) -> Vec<String> {
+    let key = StorageKey::VerifierProjects(verifier.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_verifier_projects(env: &Env, verifier: &Address, projects: &Vec<String>) {
+    let key = StorageKey::VerifierProjects(verifier.clone());
+    env.storage().persistent().set(&key, projects);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Dispute storage functions
+pub fn get_dispute(env: &Env, project_id: &String) -> Option<Dispute> {
+    let key = StorageKey::Dispute(project_id.clone());
+    env.storage().persistent().get(&key)
+}
+
+pub fn set_dispute(env: &Env, project_id: &String, dispute: &Dispute) {
+    let key = StorageKey::Dispute(project_id.clone());
+    env.storage().persistent().set(&key, dispute);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+pub fn remove_dispute(env: &Env, project_id: &String) {
+    let key = StorageKey::Dispute(project_id.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// Attestation policy storage functions
+pub fn get_attestation_quorum(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&StorageKey::AttestationQuorum)
+        .unwrap_or(DEFAULT_ATTESTATION_QUORUM)
+}
+
+pub fn set_attestation_quorum(env: &Env, quorum: u32) {
+    env.storage()
+        .instance()
+        .set(&StorageKey::AttestationQuorum, &quorum);
+}
+
+pub fn get_min_update_interval(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&StorageKey::MinUpdateInterval)
+        .unwrap_or(0)
+}
+
+pub fn set_min_update_interval(env: &Env, interval: u64) {
+    env.storage()
+        .instance()
+        .set(&StorageKey::MinUpdateInterval, &interval);
+}
+
+/// Metadata versions kept per project, 0 for no limit
+pub fn get_max_metadata_versions(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&StorageKey::MaxMetadataVersions)
+        .unwrap_or(0)
+}
+
+pub fn set_max_metadata_versions(env: &Env, max_versions: u32) {
+    env.storage()
+        .instance()
+        .set(&StorageKey::MaxMetadataVersions, &max_versions);
+}
+
+pub fn get_attestation_validity(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&StorageKey::AttestationValidity)
+        .unwrap_or(DEFAULT_ATTESTATION_VALIDITY)
+}
+
+pub fn set_attestation_validity(env: &Env, validity: u64) {
+    env.storage()
+        .instance()
+        .set(&StorageKey::AttestationValidity, &validity);
+}
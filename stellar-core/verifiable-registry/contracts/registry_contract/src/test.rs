@@ -1,11 +1,25 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString, Vec};
+use shared_types::{error_source, ErrorSource};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, Bytes, BytesN, Env, Event, String as SorobanString, Vec,
+};
 
-use crate::types::Error;
-use crate::validation::validate_ipfs_cid;
+use crate::events::{MetadataAnchored, ProjectRegistered, RetirementRecorded};
+use crate::types::{
+    Error, HashAlgorithm, NamedDocument, ProjectDetail, ProjectStatus, ProjectSupply,
+    RevocationReason, RevocationStatus,
+};
+use crate::validation::{is_valid_transition, validate_ipfs_cid};
 use crate::{ProjectRegistry, ProjectRegistryClient};
 
+const PROJECT_EXPIRY: u64 = 1_000_000;
+
+mod upgrade_target {
+    soroban_sdk::contractimport!(file = "test_wasms/upgrade_target.wasm");
+}
+
 fn create_contract() -> (Env, Address, ProjectRegistryClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
@@ -16,6 +30,18 @@ fn create_contract() -> (Env, Address, ProjectRegistryClient<'static>) {
     (env, contract_id, client)
 }
 
+fn add_verifier_and_attest(
+    env: &Env,
+    client: &ProjectRegistryClient,
+    governance: &Address,
+    project_id: &SorobanString,
+) -> Address {
+    let verifier = Address::generate(env);
+    client.add_verifier(governance, &verifier);
+    client.attest_project(&verifier, project_id, &BytesN::from_array(env, &[1u8; 32]));
+    verifier
+}
+
 // ========== Contract Tests ==========
 
 #[test]
@@ -47,7 +73,7 @@ fn test_register_project() {
     let project_id = SorobanString::from_str(&env, "PROJ-001");
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
 
     let owner = client.get_project_owner(&project_id);
     assert_eq!(owner, project_owner);
@@ -62,8 +88,9 @@ fn test_register_duplicate_project() {
     let project_id = SorobanString::from_str(&env, "PROJ-001");
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
-    client.register_project(&project_id, &project_owner); // Should panic
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    // Should panic
 }
 
 #[test]
@@ -75,7 +102,7 @@ fn test_transfer_project_ownership() {
     let project_id = SorobanString::from_str(&env, "PROJ-001");
 
     client.initialize(&admin);
-    client.register_project(&project_id, &original_owner);
+    client.register_project(&project_id, &original_owner, &PROJECT_EXPIRY, &None);
 
     client.transfer_project_ownership(&project_id, &new_owner);
 
@@ -93,7 +120,7 @@ fn test_anchor_document() {
     let doc_type = SorobanString::from_str(&env, "PDD");
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
 
     let version_index = client.anchor_document(&project_id, &ipfs_cid, &doc_type);
 
@@ -114,7 +141,7 @@ fn test_anchor_document_multiple_versions() {
     let doc_type = SorobanString::from_str(&env, "PDD");
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
 
     let v1 = client.anchor_document(&project_id, &cid1, &doc_type);
     let v2 = client.anchor_document(&project_id, &cid2, &doc_type);
@@ -151,7 +178,7 @@ fn test_anchor_document_batch() {
     ));
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
 
     let version_indices = client.anchor_document_batch(&project_id, &documents);
 
@@ -175,7 +202,7 @@ fn test_get_document_history() {
     let doc_type = SorobanString::from_str(&env, "PDD");
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
 
     client.anchor_document(&project_id, &cid1, &doc_type);
     client.anchor_document(&project_id, &cid2, &doc_type);
@@ -202,8 +229,8 @@ fn test_get_projects_by_anchorer() {
     let doc_type = SorobanString::from_str(&env, "PDD");
 
     client.initialize(&admin);
-    client.register_project(&project_id1, &project_owner);
-    client.register_project(&project_id2, &project_owner);
+    client.register_project(&project_id1, &project_owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&project_id2, &project_owner, &PROJECT_EXPIRY, &None);
 
     client.anchor_document(&project_id1, &ipfs_cid, &doc_type);
     client.anchor_document(&project_id2, &ipfs_cid, &doc_type);
@@ -225,7 +252,7 @@ fn test_invalid_cid_format() {
     let doc_type = SorobanString::from_str(&env, "PDD");
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
 
     client.anchor_document(&project_id, &invalid_cid, &doc_type); // Should panic
 }
@@ -241,52 +268,1929 @@ fn test_empty_batch() {
     let empty_documents = Vec::new(&env);
 
     client.initialize(&admin);
-    client.register_project(&project_id, &project_owner);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
 
     client.anchor_document_batch(&project_id, &empty_documents); // Should panic
 }
 
-// ========== Validation Tests ==========
+#[test]
+fn test_get_project() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let project = client.get_project(&project_id);
+    assert_eq!(project.owner, project_owner);
+    assert_eq!(project.status, ProjectStatus::Registered);
+    assert_eq!(project.expires_at, PROJECT_EXPIRY);
+    assert!(!project.expired);
+}
 
 #[test]
-fn test_valid_cidv0() {
-    let env = Env::default();
-    let cid = SorobanString::from_str(&env, "QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6uco");
-    assert!(validate_ipfs_cid(&cid).is_ok());
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_register_project_with_past_expiry() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    env.ledger().set_timestamp(5_000);
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &5_000, &None); // Should panic
 }
 
 #[test]
-fn test_valid_cidv1_base32() {
-    let env = Env::default();
-    let cid = SorobanString::from_str(
-        &env,
-        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+fn test_expired_project_blocked_from_verification() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    add_verifier_and_attest(&env, &client, &admin, &project_id);
+
+    env.ledger().set_timestamp(PROJECT_EXPIRY);
+    assert!(client.get_project(&project_id).expired);
+
+    let result = client.try_set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(result, Err(Ok(Error::ProjectExpired)));
+
+    // Other transitions are still allowed on expired projects
+    client.set_project_status(&project_id, &ProjectStatus::Rejected);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Rejected
     );
-    assert!(validate_ipfs_cid(&cid).is_ok());
 }
 
 #[test]
-fn test_invalid_cid_too_short() {
-    let env = Env::default();
-    let cid = SorobanString::from_str(&env, "Qm123");
-    assert_eq!(validate_ipfs_cid(&cid), Err(Error::InvalidCidFormat));
+fn test_renew_project_restores_verification() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    add_verifier_and_attest(&env, &client, &admin, &project_id);
+
+    env.ledger().set_timestamp(PROJECT_EXPIRY + 10);
+    client.renew_project(&admin, &project_id, &(PROJECT_EXPIRY * 2));
+
+    let project = client.get_project(&project_id);
+    assert_eq!(project.expires_at, PROJECT_EXPIRY * 2);
+    assert!(!project.expired);
+
+    client.set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Verified
+    );
 }
 
 #[test]
-fn test_invalid_cid_too_long() {
-    let env = Env::default();
-    // Create a string that's over 100 characters
-    let long_cid = SorobanString::from_str(
-        &env,
-        "QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6ucoQmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6ucoExtra",
+fn test_renew_project_governance_only() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.set_governance(&governance);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_renew_project(&admin, &project_id, &(PROJECT_EXPIRY * 2));
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.renew_project(&governance, &project_id, &(PROJECT_EXPIRY * 2));
+    assert_eq!(
+        client.get_project(&project_id).expires_at,
+        PROJECT_EXPIRY * 2
     );
-    assert_eq!(validate_ipfs_cid(&long_cid), Err(Error::InvalidCidFormat));
 }
 
 #[test]
-fn test_valid_cid_min_length() {
-    let env = Env::default();
-    // Exactly 46 characters should pass
-    let cid = SorobanString::from_str(&env, "QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6uco");
-    assert!(validate_ipfs_cid(&cid).is_ok());
+fn test_attest_project() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+
+    env.ledger().set_timestamp(100);
+    let verifier = add_verifier_and_attest(&env, &client, &admin, &project_id);
+
+    let attestations = client.get_attestations(&project_id);
+    assert_eq!(attestations.len(), 1);
+
+    let attestation = attestations.get(0).unwrap();
+    assert_eq!(attestation.verifier, verifier);
+    assert_eq!(attestation.attested_at, 100);
+    assert_eq!(
+        attestation.expires_at,
+        100 + client.get_attestation_validity()
+    );
+
+    client.set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Verified
+    );
+}
+
+#[test]
+fn test_attest_project_requires_verifier() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_attest_project(
+        &outsider,
+        &project_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert_eq!(result, Err(Ok(Error::NotVerifier)));
+}
+
+#[test]
+fn test_attest_address() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let account = Address::generate(&env);
+    let evidence = BytesN::from_array(&env, &[7u8; 32]);
+    client.initialize(&admin);
+    client.set_attestation_validity(&admin, &100);
+
+    let result = client.try_attest_address(&verifier, &account, &evidence);
+    assert_eq!(result, Err(Ok(Error::NotVerifier)));
+    assert!(!client.has_valid_address_attestation(&account));
+
+    client.add_verifier(&admin, &verifier);
+    client.attest_address(&verifier, &account, &evidence);
+    assert!(client.has_valid_address_attestation(&account));
+    assert!(!client.has_valid_address_attestation(&Address::generate(&env)));
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 100);
+    assert!(!client.has_valid_address_attestation(&account));
+}
+
+#[test]
+fn test_huge_attestation_validity_saturates() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let account = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let evidence = BytesN::from_array(&env, &[7u8; 32]);
+
+    client.initialize(&admin);
+    client.add_verifier(&admin, &verifier);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_attestation_validity(&admin, &u64::MAX);
+    env.ledger().set_timestamp(1_000);
+
+    // The expiry saturates instead of overflowing
+    client.attest_project(&verifier, &project_id, &evidence);
+    assert_eq!(
+        client
+            .get_attestations(&project_id)
+            .get(0)
+            .unwrap()
+            .expires_at,
+        u64::MAX
+    );
+    client.attest_address(&verifier, &account, &evidence);
+    assert!(client.has_valid_address_attestation(&account));
+}
+
+#[test]
+fn test_batch_attest() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id1 = SorobanString::from_str(&env, "PROJ-001");
+    let project_id2 = SorobanString::from_str(&env, "PROJ-002");
+    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
+    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.initialize(&admin);
+    client.add_verifier(&admin, &verifier);
+    client.register_project(&project_id1, &project_owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&project_id2, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let mut batch = Vec::new(&env);
+    batch.push_back((project_id1.clone(), hash1.clone()));
+    batch.push_back((project_id2.clone(), hash2.clone()));
+    client.batch_attest(&verifier, &batch);
+
+    let attestations1 = client.get_attestations(&project_id1);
+    assert_eq!(attestations1.len(), 1);
+    assert_eq!(attestations1.get(0).unwrap().evidence_hash, hash1);
+    assert_eq!(attestations1.get(0).unwrap().verifier, verifier);
+
+    let attestations2 = client.get_attestations(&project_id2);
+    assert_eq!(attestations2.len(), 1);
+    assert_eq!(attestations2.get(0).unwrap().evidence_hash, hash2);
+}
+
+#[test]
+fn test_get_projects_attested_by() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let other_verifier = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.initialize(&admin);
+    client.add_verifier(&admin, &verifier);
+    client.add_verifier(&admin, &other_verifier);
+    let ids = ["PROJ-001", "PROJ-002", "PROJ-003"].map(|id| SorobanString::from_str(&env, id));
+    for project_id in ids.iter() {
+        client.register_project(project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    }
+    assert_eq!(client.get_projects_attested_by(&verifier).len(), 0);
+
+    client.attest_project(&verifier, &ids[1], &hash);
+    let mut batch = Vec::new(&env);
+    batch.push_back((ids[0].clone(), hash.clone()));
+    batch.push_back((ids[1].clone(), hash.clone()));
+    client.batch_attest(&verifier, &batch);
+    client.attest_project(&other_verifier, &ids[2], &hash);
+
+    // Re-attesting a project does not list it twice
+    let mut expected = Vec::new(&env);
+    expected.push_back(ids[1].clone());
+    expected.push_back(ids[0].clone());
+    assert_eq!(client.get_projects_attested_by(&verifier), expected);
+
+    let mut expected = Vec::new(&env);
+    expected.push_back(ids[2].clone());
+    assert_eq!(client.get_projects_attested_by(&other_verifier), expected);
+}
+
+#[test]
+fn test_batch_attest_unknown_project_is_atomic() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let unknown_id = SorobanString::from_str(&env, "PROJ-404");
+
+    client.initialize(&admin);
+    client.add_verifier(&admin, &verifier);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let mut batch = Vec::new(&env);
+    batch.push_back((project_id.clone(), BytesN::from_array(&env, &[1u8; 32])));
+    batch.push_back((unknown_id, BytesN::from_array(&env, &[2u8; 32])));
+
+    let result = client.try_batch_attest(&verifier, &batch);
+    assert_eq!(result, Err(Ok(Error::ProjectNotFound)));
+
+    // The valid entry in the failed batch was not recorded
+    assert!(client.get_attestations(&project_id).is_empty());
+}
+
+#[test]
+fn test_verifier_stats_after_dispute() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let challenger = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id1 = SorobanString::from_str(&env, "PROJ-001");
+    let project_id2 = SorobanString::from_str(&env, "PROJ-002");
+    let reason = SorobanString::from_str(&env, "Baseline overstated");
+
+    client.initialize(&admin);
+    client.register_project(&project_id1, &project_owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&project_id2, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let verifier = add_verifier_and_attest(&env, &client, &admin, &project_id1);
+    client.attest_project(
+        &verifier,
+        &project_id2,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    let stats = client.get_verifier_stats(&verifier);
+    assert_eq!(stats.attestations_made, 2);
+    assert_eq!(stats.attestations_invalidated, 0);
+
+    // Re-attesting a project, alone or twice in one batch, replaces the
+    // standing attestation rather than adding to the count
+    client.attest_project(
+        &verifier,
+        &project_id2,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+    client.batch_attest(
+        &verifier,
+        &vec![
+            &env,
+            (project_id1.clone(), BytesN::from_array(&env, &[4u8; 32])),
+            (project_id1.clone(), BytesN::from_array(&env, &[5u8; 32])),
+        ],
+    );
+    assert_eq!(client.get_verifier_stats(&verifier).attestations_made, 2);
+    assert_eq!(client.get_attestations(&project_id1).len(), 1);
+
+    // A rejected dispute leaves the counters untouched
+    client.raise_dispute(&challenger, &project_id2, &reason);
+    client.resolve_dispute(&admin, &project_id2, &false);
+    assert_eq!(
+        client
+            .get_verifier_stats(&verifier)
+            .attestations_invalidated,
+        0
+    );
+    assert_eq!(client.get_attestations(&project_id2).len(), 1);
+
+    // An upheld dispute invalidates the project's attestations
+    client.raise_dispute(&challenger, &project_id1, &reason);
+    assert!(client.get_dispute(&project_id1).is_some());
+    client.resolve_dispute(&admin, &project_id1, &true);
+
+    let stats = client.get_verifier_stats(&verifier);
+    assert_eq!(stats.attestations_made, 2);
+    assert_eq!(stats.attestations_invalidated, 1);
+    assert!(client.get_attestations(&project_id1).is_empty());
+    assert!(client.get_dispute(&project_id1).is_none());
+}
+
+#[test]
+fn test_dispute_lifecycle_errors() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let challenger = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let reason = SorobanString::from_str(&env, "Leakage not accounted for");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_resolve_dispute(&admin, &project_id, &true);
+    assert_eq!(result, Err(Ok(Error::NoOpenDispute)));
+
+    client.raise_dispute(&challenger, &project_id, &reason);
+    let result = client.try_raise_dispute(&challenger, &project_id, &reason);
+    assert_eq!(result, Err(Ok(Error::DisputeAlreadyOpen)));
+
+    let result = client.try_resolve_dispute(&challenger, &project_id, &true);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_verification_requires_quorum() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+
+    let result = client.try_set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(result, Err(Ok(Error::QuorumNotMet)));
+}
+
+#[test]
+fn test_attestation_expiry_drops_below_quorum() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.set_attestation_quorum(&admin, &2);
+    client.set_attestation_validity(&admin, &1_000);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+
+    add_verifier_and_attest(&env, &client, &admin, &project_id);
+    env.ledger().set_timestamp(500);
+    let second_verifier = add_verifier_and_attest(&env, &client, &admin, &project_id);
+
+    assert_eq!(client.get_valid_attestations(&project_id).len(), 2);
+
+    // The first attestation expires at 1_000, leaving only one valid attestation
+    env.ledger().set_timestamp(1_200);
+    let valid = client.get_valid_attestations(&project_id);
+    assert_eq!(valid.len(), 1);
+    assert_eq!(valid.get(0).unwrap().verifier, second_verifier);
+    assert_eq!(client.get_attestations(&project_id).len(), 2);
+
+    let result = client.try_set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(result, Err(Ok(Error::QuorumNotMet)));
+}
+
+#[test]
+fn test_revoke_project_persists_reason_code() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let reason = SorobanString::from_str(&env, "Credits also issued on another registry");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    assert_eq!(
+        client.get_project(&project_id).revocation,
+        RevocationStatus::NotRevoked
+    );
+
+    env.ledger().set_timestamp(42);
+    client.revoke_project(
+        &admin,
+        &project_id,
+        &RevocationReason::DoubleCounting,
+        &reason,
+    );
+
+    let project = client.get_project(&project_id);
+    assert_eq!(project.status, ProjectStatus::Rejected);
+
+    let RevocationStatus::Revoked(revocation) = project.revocation else {
+        panic!("expected project to be revoked");
+    };
+    assert_eq!(revocation.code, RevocationReason::DoubleCounting);
+    assert_eq!(revocation.reason, reason);
+    assert_eq!(revocation.revoked_at, 42);
+}
+
+#[test]
+fn test_revoke_project_twice_fails() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let reason = SorobanString::from_str(&env, "Fabricated monitoring data");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    client.revoke_project(&admin, &project_id, &RevocationReason::Fraud, &reason);
+
+    let result = client.try_revoke_project(&admin, &project_id, &RevocationReason::Other, &reason);
+    assert_eq!(result, Err(Ok(Error::ProjectRevoked)));
+    let RevocationStatus::Revoked(revocation) = client.get_project(&project_id).revocation else {
+        panic!("expected project to be revoked");
+    };
+    assert_eq!(revocation.code, RevocationReason::Fraud);
+}
+
+#[test]
+fn test_status_lifecycle() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    add_verifier_and_attest(&env, &client, &admin, &project_id);
+
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    client.set_project_status(&project_id, &ProjectStatus::Verified);
+    client.set_project_status(&project_id, &ProjectStatus::Retired);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Retired
+    );
+
+    // Retired is terminal
+    let result = client.try_set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+}
+
+#[test]
+fn test_skipping_review_is_rejected() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    add_verifier_and_attest(&env, &client, &admin, &project_id);
+
+    let result = client.try_set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Registered
+    );
+}
+
+#[test]
+fn test_revoke_registered_project_is_rejected() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let reason = SorobanString::from_str(&env, "Not yet reviewed");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_revoke_project(&admin, &project_id, &RevocationReason::Other, &reason);
+    assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+}
+
+#[test]
+fn test_anchor_metadata_versions() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
+    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    assert_eq!(client.anchor_metadata(&project_id, &hash1, &None), 1);
+    assert_eq!(
+        client.update_project_metadata(&project_owner, &project_id, &hash2, &None),
+        2
+    );
+
+    let latest = client.get_metadata(&project_id);
+    assert_eq!(latest.metadata_hash, hash2);
+    assert_eq!(latest.version, 2);
+    assert_eq!(latest.anchorer, project_owner);
+
+    let result = client.try_anchor_metadata(&project_id, &hash1, &None);
+    assert_eq!(result, Err(Ok(Error::MetadataAlreadyAnchored)));
+}
+
+#[test]
+fn test_metadata_hash_algorithm_round_trips_and_verifies() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let v1 = Bytes::from_slice(&env, b"metadata v1");
+    let v2 = Bytes::from_slice(&env, b"metadata v2");
+    let v1_hash = env.crypto().sha256(&v1).to_bytes();
+    let v2_hash = env.crypto().keccak256(&v2).to_bytes();
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    assert!(!client.verify_metadata(&project_id, &v1_hash, &HashAlgorithm::Sha256));
+    assert!(!client.verify_metadata_bytes(&project_id, &v1));
+
+    // Untagged hashes default to SHA-256
+    client.anchor_metadata(&project_id, &v1_hash, &None);
+    assert_eq!(
+        client.get_metadata(&project_id).algorithm,
+        HashAlgorithm::Sha256
+    );
+    assert!(client.verify_metadata(&project_id, &v1_hash, &HashAlgorithm::Sha256));
+    assert!(client.verify_metadata_bytes(&project_id, &v1));
+
+    client.update_project_metadata(
+        &project_owner,
+        &project_id,
+        &v2_hash,
+        &Some(HashAlgorithm::Keccak256),
+    );
+    assert_eq!(
+        client.get_metadata(&project_id).algorithm,
+        HashAlgorithm::Keccak256
+    );
+    assert!(client.verify_metadata(&project_id, &v2_hash, &HashAlgorithm::Keccak256));
+    assert!(client.verify_metadata_bytes(&project_id, &v2));
+
+    // The right digest under the wrong algorithm tag does not verify
+    assert!(!client.verify_metadata(&project_id, &v2_hash, &HashAlgorithm::Sha256));
+    assert!(!client.verify_metadata_bytes(&project_id, &v1));
+}
+
+#[test]
+fn test_add_and_verify_documents() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let pdd = SorobanString::from_str(&env, "PDD");
+    let report = SorobanString::from_str(&env, "MONITORING_REPORT");
+    let pdd_hash = BytesN::from_array(&env, &[1u8; 32]);
+    let report_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let sha256 = HashAlgorithm::Sha256;
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    client.add_document(&project_owner, &project_id, &pdd, &pdd_hash, &None);
+    client.add_document(&project_owner, &project_id, &report, &report_hash, &None);
+    assert_eq!(client.get_documents(&project_id).len(), 2);
+
+    assert!(client.verify_document(&project_id, &pdd, &pdd_hash, &sha256));
+    assert!(client.verify_document(&project_id, &report, &report_hash, &sha256));
+    assert!(!client.verify_document(&project_id, &pdd, &report_hash, &sha256));
+    assert!(!client.verify_document(&project_id, &report, &pdd_hash, &sha256));
+    assert!(!client.verify_document(
+        &project_id,
+        &SorobanString::from_str(&env, "AUDIT"),
+        &pdd_hash,
+        &sha256
+    ));
+
+    let result = client.try_add_document(&project_owner, &project_id, &pdd, &report_hash, &None);
+    assert_eq!(result, Err(Ok(Error::DocumentAlreadyExists)));
+    assert!(client.verify_document(&project_id, &pdd, &pdd_hash, &sha256));
+
+    let outsider = Address::generate(&env);
+    let audit = SorobanString::from_str(&env, "AUDIT");
+    let result = client.try_add_document(&outsider, &project_id, &audit, &pdd_hash, &None);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let unknown = SorobanString::from_str(&env, "PROJ-404");
+    let result = client.try_add_document(&project_owner, &unknown, &audit, &pdd_hash, &None);
+    assert_eq!(result, Err(Ok(Error::ProjectNotFound)));
+}
+
+#[test]
+fn test_document_hash_algorithm() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let pdd = SorobanString::from_str(&env, "PDD");
+    let report = SorobanString::from_str(&env, "MONITORING_REPORT");
+    let contents = Bytes::from_slice(&env, b"project design document");
+    let sha_hash = env.crypto().sha256(&contents).to_bytes();
+    let keccak_hash = env.crypto().keccak256(&contents).to_bytes();
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.add_document(&project_owner, &project_id, &pdd, &sha_hash, &None);
+    client.add_document(
+        &project_owner,
+        &project_id,
+        &report,
+        &keccak_hash,
+        &Some(HashAlgorithm::Keccak256),
+    );
+
+    // The tag is stored with the hash, SHA-256 when none was given
+    let documents = client.get_documents(&project_id);
+    assert_eq!(
+        documents.get(0).unwrap(),
+        NamedDocument {
+            name: pdd.clone(),
+            hash: sha_hash.clone(),
+            algorithm: HashAlgorithm::Sha256,
+        }
+    );
+    assert_eq!(
+        documents.get(1).unwrap().algorithm,
+        HashAlgorithm::Keccak256
+    );
+
+    // A matching hash claimed under the wrong algorithm does not verify
+    assert!(client.verify_document(
+        &project_id,
+        &report,
+        &keccak_hash,
+        &HashAlgorithm::Keccak256
+    ));
+    assert!(!client.verify_document(&project_id, &report, &keccak_hash, &HashAlgorithm::Sha256));
+    assert!(!client.verify_document(&project_id, &pdd, &sha_hash, &HashAlgorithm::Keccak256));
+
+    // Raw bytes are hashed with each document's own algorithm
+    assert!(client.verify_document_bytes(&project_id, &pdd, &contents));
+    assert!(client.verify_document_bytes(&project_id, &report, &contents));
+    let altered = Bytes::from_slice(&env, b"project design document v2");
+    assert!(!client.verify_document_bytes(&project_id, &pdd, &altered));
+
+    // A keccak hash mislabelled as SHA-256 fails byte verification
+    let audit = SorobanString::from_str(&env, "AUDIT");
+    client.add_document(&project_owner, &project_id, &audit, &keccak_hash, &None);
+    assert!(!client.verify_document_bytes(&project_id, &audit, &contents));
+    assert!(!client.verify_document_bytes(
+        &project_id,
+        &SorobanString::from_str(&env, "MISSING"),
+        &contents
+    ));
+}
+
+#[test]
+fn test_min_update_interval_throttles_metadata_updates() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
+    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_set_min_update_interval(&project_owner, &600);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    client.set_min_update_interval(&admin, &600);
+    assert_eq!(client.get_min_update_interval(), 600);
+
+    env.ledger().set_timestamp(1_000);
+    client.anchor_metadata(&project_id, &hash1, &None);
+
+    env.ledger().set_timestamp(1_599);
+    let result = client.try_update_project_metadata(&project_owner, &project_id, &hash2, &None);
+    assert_eq!(result, Err(Ok(Error::UpdateTooSoon)));
+    assert_eq!(client.get_metadata(&project_id).version, 1);
+
+    // The interval counts from the latest version, not the first anchor
+    env.ledger().set_timestamp(1_600);
+    assert_eq!(
+        client.update_project_metadata(&project_owner, &project_id, &hash2, &None),
+        2
+    );
+    let result = client.try_update_project_metadata(&project_owner, &project_id, &hash1, &None);
+    assert_eq!(result, Err(Ok(Error::UpdateTooSoon)));
+    env.ledger().set_timestamp(2_200);
+    assert_eq!(
+        client.update_project_metadata(&project_owner, &project_id, &hash1, &None),
+        3
+    );
+}
+
+#[test]
+fn test_governance_metadata_update_skips_interval() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
+    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.initialize(&admin);
+    client.set_governance(&governance);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_min_update_interval(&governance, &600);
+
+    env.ledger().set_timestamp(1_000);
+    client.anchor_metadata(&project_id, &hash1, &None);
+
+    // Only the owner and governance may anchor new versions
+    let outsider = Address::generate(&env);
+    let result = client.try_update_project_metadata(&outsider, &project_id, &hash2, &None);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let result = client.try_update_project_metadata(&project_owner, &project_id, &hash2, &None);
+    assert_eq!(result, Err(Ok(Error::UpdateTooSoon)));
+
+    assert_eq!(
+        client.update_project_metadata(&governance, &project_id, &hash2, &None),
+        2
+    );
+    let latest = client.get_metadata(&project_id);
+    assert_eq!(latest.metadata_hash, hash2);
+    assert_eq!(latest.anchorer, governance);
+}
+
+#[test]
+fn test_max_versions_evicts_oldest_metadata() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_set_max_versions(&project_owner, &2);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    client.set_max_versions(&admin, &2);
+    assert_eq!(client.get_max_versions(), 2);
+
+    client.anchor_metadata(&project_id, &BytesN::from_array(&env, &[1u8; 32]), &None);
+    client.update_project_metadata(
+        &project_owner,
+        &project_id,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &None,
+    );
+    assert!(client.get_metadata_at_version(&project_id, &1).is_some());
+
+    // Versions keep counting up while only the latest two are kept
+    assert_eq!(
+        client.update_project_metadata(
+            &project_owner,
+            &project_id,
+            &BytesN::from_array(&env, &[3u8; 32]),
+            &None
+        ),
+        3
+    );
+    assert_eq!(
+        client.update_project_metadata(
+            &project_owner,
+            &project_id,
+            &BytesN::from_array(&env, &[4u8; 32]),
+            &None
+        ),
+        4
+    );
+    assert_eq!(client.get_metadata_at_version(&project_id, &1), None);
+    assert_eq!(client.get_metadata_at_version(&project_id, &2), None);
+    assert_eq!(
+        client.get_metadata_at_version(&project_id, &3),
+        Some(BytesN::from_array(&env, &[3u8; 32]))
+    );
+    assert_eq!(client.get_metadata(&project_id).version, 4);
+    let result = client.try_compare_versions(&project_id, &2, &4);
+    assert_eq!(result, Err(Ok(Error::VersionNotFound)));
+}
+
+#[test]
+fn test_metadata_at_version_and_compare() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
+    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    client.anchor_metadata(&project_id, &hash1, &None);
+    client.update_project_metadata(&project_owner, &project_id, &hash1, &None);
+    client.update_project_metadata(&project_owner, &project_id, &hash2, &None);
+
+    assert_eq!(
+        client.get_metadata_at_version(&project_id, &1),
+        Some(hash1.clone())
+    );
+    assert_eq!(client.get_metadata_at_version(&project_id, &2), Some(hash1));
+    assert_eq!(client.get_metadata_at_version(&project_id, &3), Some(hash2));
+
+    // Re-anchoring the same hash is not a change
+    assert!(!client.compare_versions(&project_id, &1, &2));
+    assert!(client.compare_versions(&project_id, &2, &3));
+}
+
+#[test]
+fn test_metadata_version_out_of_range() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.anchor_metadata(&project_id, &BytesN::from_array(&env, &[1u8; 32]), &None);
+
+    assert_eq!(client.get_metadata_at_version(&project_id, &0), None);
+    assert_eq!(client.get_metadata_at_version(&project_id, &2), None);
+
+    let result = client.try_compare_versions(&project_id, &1, &2);
+    assert_eq!(result, Err(Ok(Error::VersionNotFound)));
+}
+
+#[test]
+fn test_update_metadata_requires_anchor() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_update_project_metadata(
+        &project_owner,
+        &project_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(Error::NoMetadataFound)));
+}
+
+#[test]
+fn test_anchor_event_payload() {
+    let (env, contract_id, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
+    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    env.ledger().set_timestamp(100);
+    client.anchor_metadata(&project_id, &hash1, &None);
+    let expected = MetadataAnchored {
+        project_id: project_id.clone(),
+        metadata_hash: hash1,
+        version: 1,
+        timestamp: 100,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+
+    env.ledger().set_timestamp(200);
+    client.update_project_metadata(&project_owner, &project_id, &hash2, &None);
+    let expected = MetadataAnchored {
+        project_id: project_id.clone(),
+        metadata_hash: hash2,
+        version: 2,
+        timestamp: 200,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (contract_id, expected.topics(&env), expected.data(&env))
+        ]
+    );
+}
+
+#[test]
+fn test_get_projects_by_status() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id1 = SorobanString::from_str(&env, "PROJ-001");
+    let project_id2 = SorobanString::from_str(&env, "PROJ-002");
+    let statuses = [
+        ProjectStatus::Registered,
+        ProjectStatus::UnderReview,
+        ProjectStatus::Verified,
+        ProjectStatus::Rejected,
+        ProjectStatus::Retired,
+    ];
+
+    client.initialize(&admin);
+    client.register_project(&project_id1, &project_owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&project_id2, &project_owner, &PROJECT_EXPIRY, &None);
+    add_verifier_and_attest(&env, &client, &admin, &project_id1);
+
+    let registered = client.get_projects_by_status(&ProjectStatus::Registered);
+    assert_eq!(registered.len(), 2);
+
+    for status in [
+        ProjectStatus::UnderReview,
+        ProjectStatus::Verified,
+        ProjectStatus::Retired,
+    ] {
+        client.set_project_status(&project_id1, &status);
+
+        // The project appears only in its current status list
+        for listed in statuses {
+            let projects = client.get_projects_by_status(&listed);
+            assert_eq!(projects.contains(&project_id1), listed == status);
+        }
+    }
+
+    let registered = client.get_projects_by_status(&ProjectStatus::Registered);
+    assert_eq!(registered, Vec::from_array(&env, [project_id2]));
+}
+
+#[test]
+fn test_revoked_project_moves_to_rejected_list() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let reason = SorobanString::from_str(&env, "Methodology misapplied");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    client.revoke_project(
+        &admin,
+        &project_id,
+        &RevocationReason::MethodologyViolation,
+        &reason,
+    );
+
+    assert!(client
+        .get_projects_by_status(&ProjectStatus::UnderReview)
+        .is_empty());
+    assert_eq!(
+        client.get_projects_by_status(&ProjectStatus::Rejected),
+        Vec::from_array(&env, [project_id])
+    );
+}
+
+#[test]
+fn test_get_project_statuses() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let known = SorobanString::from_str(&env, "PROJ-001");
+    let revoked = SorobanString::from_str(&env, "PROJ-002");
+    let missing = SorobanString::from_str(&env, "PROJ-404");
+
+    client.initialize(&admin);
+    client.register_project(&known, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&known, &ProjectStatus::UnderReview);
+    client.register_project(&revoked, &project_owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&revoked, &ProjectStatus::UnderReview);
+    client.revoke_project(
+        &admin,
+        &revoked,
+        &RevocationReason::MethodologyViolation,
+        &SorobanString::from_str(&env, "Methodology misapplied"),
+    );
+
+    let ids = Vec::from_array(
+        &env,
+        [missing.clone(), known.clone(), revoked.clone(), missing],
+    );
+    assert_eq!(
+        client.get_project_statuses(&ids),
+        Vec::from_array(
+            &env,
+            [
+                None,
+                Some(ProjectStatus::UnderReview),
+                Some(ProjectStatus::Rejected),
+                None,
+            ]
+        )
+    );
+    assert!(client.get_project_statuses(&Vec::new(&env)).is_empty());
+}
+
+#[test]
+fn test_upgrade_by_admin() {
+    let (env, contract_id, client) = create_contract();
+    let admin = Address::generate(&env);
+    let project_owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &project_owner, &PROJECT_EXPIRY, &None);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgrade_target::WASM);
+    client.upgrade(&admin, &new_wasm_hash);
+
+    // The contract address now runs the new Wasm
+    let upgraded = upgrade_target::Client::new(&env, &contract_id);
+    assert_eq!(upgraded.add(&2, &3), 5);
+}
+
+#[test]
+fn test_upgrade_rejects_non_admin() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgrade_target::WASM);
+    let result = client.try_upgrade(&outsider, &new_wasm_hash);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    // Storage and code are untouched
+    assert_eq!(client.get_admin(), admin);
+}
+
+// ========== Validation Tests ==========
+
+#[test]
+fn test_valid_cidv0() {
+    let env = Env::default();
+    let cid = SorobanString::from_str(&env, "QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6uco");
+    assert!(validate_ipfs_cid(&cid).is_ok());
+}
+
+#[test]
+fn test_valid_cidv1_base32() {
+    let env = Env::default();
+    let cid = SorobanString::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    assert!(validate_ipfs_cid(&cid).is_ok());
+}
+
+#[test]
+fn test_invalid_cid_too_short() {
+    let env = Env::default();
+    let cid = SorobanString::from_str(&env, "Qm123");
+    assert_eq!(validate_ipfs_cid(&cid), Err(Error::InvalidCidFormat));
+}
+
+#[test]
+fn test_invalid_cid_too_long() {
+    let env = Env::default();
+    // Create a string that's over 100 characters
+    let long_cid = SorobanString::from_str(
+        &env,
+        "QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6ucoQmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6ucoExtra",
+    );
+    assert_eq!(validate_ipfs_cid(&long_cid), Err(Error::InvalidCidFormat));
+}
+
+#[test]
+fn test_valid_cid_min_length() {
+    let env = Env::default();
+    // Exactly 46 characters should pass
+    let cid = SorobanString::from_str(&env, "QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6uco");
+    assert!(validate_ipfs_cid(&cid).is_ok());
+}
+
+#[test]
+fn test_status_transitions_exhaustive() {
+    let statuses = [
+        ProjectStatus::Registered,
+        ProjectStatus::UnderReview,
+        ProjectStatus::Verified,
+        ProjectStatus::Rejected,
+        ProjectStatus::Retired,
+        ProjectStatus::Inactive,
+    ];
+    let allowed = [
+        (ProjectStatus::Registered, ProjectStatus::UnderReview),
+        (ProjectStatus::UnderReview, ProjectStatus::Verified),
+        (ProjectStatus::UnderReview, ProjectStatus::Rejected),
+        (ProjectStatus::Verified, ProjectStatus::Retired),
+        (ProjectStatus::Verified, ProjectStatus::Rejected),
+    ];
+
+    for from in statuses {
+        for to in statuses {
+            assert_eq!(
+                is_valid_transition(from, to),
+                allowed.contains(&(from, to)),
+                "unexpected result for {:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+}
+
+#[test]
+fn test_project_jurisdiction() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_get_project_jurisdiction(&project_id);
+    assert_eq!(result, Err(Ok(Error::JurisdictionNotSet)));
+
+    let jurisdiction = SorobanString::from_str(&env, "BR");
+    client.set_project_jurisdiction(&project_id, &jurisdiction);
+    assert_eq!(client.get_project_jurisdiction(&project_id), jurisdiction);
+
+    let missing = SorobanString::from_str(&env, "PROJ-404");
+    let result = client.try_set_project_jurisdiction(&missing, &jurisdiction);
+    assert_eq!(result, Err(Ok(Error::ProjectNotFound)));
+}
+
+#[test]
+fn test_link_token() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_get_token_project(&7);
+    assert_eq!(result, Err(Ok(Error::TokenNotLinked)));
+
+    client.link_token(&project_id, &7, &1);
+    assert_eq!(client.get_token_project(&7), project_id);
+
+    let other = SorobanString::from_str(&env, "PROJ-002");
+    client.register_project(&other, &owner, &PROJECT_EXPIRY, &None);
+    let result = client.try_link_token(&other, &7, &1);
+    assert_eq!(result, Err(Ok(Error::TokenAlreadyLinked)));
+
+    let missing = SorobanString::from_str(&env, "PROJ-404");
+    let result = client.try_link_token(&missing, &8, &1);
+    assert_eq!(result, Err(Ok(Error::ProjectNotFound)));
+
+    let result = client.try_link_token(&project_id, &8, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+    assert_eq!(client.get_project_tokens(&project_id), vec![&env, 7]);
+}
+
+#[test]
+fn test_batch_link_tokens() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.set_governance(&governance);
+    client.set_carbon_asset_contract(&admin, &carbon_contract);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    client.link_token(&project_id, &1, &1);
+
+    client.batch_link_tokens(&governance, &project_id, &vec![&env, 2, 3], &5);
+    client.batch_link_tokens(&carbon_contract, &project_id, &vec![&env, 4], &1);
+
+    assert_eq!(
+        client.get_project_tokens(&project_id),
+        vec![&env, 1, 2, 3, 4]
+    );
+    for token_id in 1..=4 {
+        assert_eq!(client.get_token_project(&token_id), project_id);
+    }
+    assert_eq!(client.get_project_supply(&project_id).total_issued, 12);
+
+    let result = client.try_batch_link_tokens(&owner, &project_id, &vec![&env, 5], &1);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let result = client.try_batch_link_tokens(&governance, &project_id, &Vec::new(&env), &1);
+    assert_eq!(result, Err(Ok(Error::EmptyBatch)));
+
+    let result = client.try_batch_link_tokens(&governance, &project_id, &vec![&env, 5], &0);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+    // Duplicates reject the whole batch, within it or against earlier links
+    let result = client.try_batch_link_tokens(&governance, &project_id, &vec![&env, 5, 6, 5], &1);
+    assert_eq!(result, Err(Ok(Error::TokenAlreadyLinked)));
+    let result = client.try_batch_link_tokens(&governance, &project_id, &vec![&env, 5, 3], &1);
+    assert_eq!(result, Err(Ok(Error::TokenAlreadyLinked)));
+    assert!(client.try_get_token_project(&5).is_err());
+    assert_eq!(client.get_project_tokens(&project_id).len(), 4);
+
+    let missing = SorobanString::from_str(&env, "PROJ-404");
+    let result = client.try_batch_link_tokens(&governance, &missing, &vec![&env, 5], &1);
+    assert_eq!(result, Err(Ok(Error::ProjectNotFound)));
+}
+
+#[test]
+fn test_freeze_methodology() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let frozen_project = SorobanString::from_str(&env, "PROJ-001");
+    let other_project = SorobanString::from_str(&env, "PROJ-002");
+
+    client.initialize(&admin);
+    client.set_governance(&governance);
+    for project_id in [&frozen_project, &other_project] {
+        client.register_project(project_id, &owner, &PROJECT_EXPIRY, &None);
+        client.set_project_status(project_id, &ProjectStatus::UnderReview);
+        add_verifier_and_attest(&env, &client, &governance, project_id);
+    }
+
+    let result = client.try_get_project_methodology(&frozen_project);
+    assert_eq!(result, Err(Ok(Error::MethodologyNotSet)));
+
+    client.set_project_methodology(&frozen_project, &1);
+    client.set_project_methodology(&other_project, &1);
+    client.set_project_methodology(&other_project, &2);
+    assert_eq!(client.get_project_methodology(&other_project), 2);
+    assert_eq!(
+        client.get_projects_by_methodology(&1),
+        vec![&env, frozen_project.clone()]
+    );
+
+    let result = client.try_freeze_methodology(&owner, &1);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.freeze_methodology(&governance, &1);
+    assert!(client.is_methodology_frozen(&1));
+    assert!(!client.is_methodology_frozen(&2));
+
+    // Projects under the frozen methodology are blocked
+    let result = client.try_set_project_status(&frozen_project, &ProjectStatus::Verified);
+    assert_eq!(result, Err(Ok(Error::MethodologyFrozen)));
+    let result = client.try_link_token(&frozen_project, &1, &1);
+    assert_eq!(result, Err(Ok(Error::MethodologyFrozen)));
+    let result = client.try_batch_link_tokens(&governance, &frozen_project, &vec![&env, 1], &1);
+    assert_eq!(result, Err(Ok(Error::MethodologyFrozen)));
+
+    // Others proceed
+    client.set_project_status(&other_project, &ProjectStatus::Verified);
+    client.link_token(&other_project, &2, &1);
+
+    client.unfreeze_methodology(&governance, &1);
+    assert!(!client.is_methodology_frozen(&1));
+    client.set_project_status(&frozen_project, &ProjectStatus::Verified);
+    client.link_token(&frozen_project, &1, &1);
+    assert_eq!(client.get_token_project(&1), frozen_project);
+}
+
+#[test]
+fn test_record_retirement() {
+    let (env, contract_id, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    client.link_token(&project_id, &7, &10);
+    client.link_token(&project_id, &8, &5);
+
+    // Nothing may record retirements until the carbon contract is set
+    let result = client.try_record_retirement(&carbon_contract, &project_id, &7, &10, &beneficiary);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.set_carbon_asset_contract(&admin, &carbon_contract);
+    assert_eq!(
+        client.get_carbon_asset_contract(),
+        Some(carbon_contract.clone())
+    );
+
+    env.ledger().set_timestamp(500);
+    client.record_retirement(&carbon_contract, &project_id, &7, &10, &beneficiary);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                RetirementRecorded {
+                    project_id: project_id.clone(),
+                    token_id: 7,
+                    amount: 10,
+                    beneficiary: beneficiary.clone(),
+                }
+                .topics(&env),
+                RetirementRecorded {
+                    project_id: project_id.clone(),
+                    token_id: 7,
+                    amount: 10,
+                    beneficiary: beneficiary.clone(),
+                }
+                .data(&env),
+            ),
+        ]
+    );
+    client.record_retirement(&carbon_contract, &project_id, &8, &5, &owner);
+
+    let retirements = client.get_retirements(&project_id);
+    assert_eq!(retirements.len(), 2);
+    let first = retirements.get(0).unwrap();
+    assert_eq!(first.token_id, 7);
+    assert_eq!(first.amount, 10);
+    assert_eq!(first.beneficiary, beneficiary);
+    assert_eq!(first.retired_at, 500);
+    assert_eq!(retirements.get(1).unwrap().beneficiary, owner);
+    assert_eq!(client.get_project(&project_id).total_retired, 15);
+}
+
+#[test]
+fn test_record_retirement_rejects_over_retirement() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.set_carbon_asset_contract(&admin, &carbon_contract);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    client.link_token(&project_id, &1, &2);
+    client.link_token(&project_id, &2, &2);
+
+    // A token cannot be retired for more than it carries, even while the
+    // project as a whole still has room
+    let result = client.try_record_retirement(&carbon_contract, &project_id, &1, &3, &owner);
+    assert_eq!(result, Err(Ok(Error::OverRetirement)));
+    assert_eq!(client.get_retirements(&project_id).len(), 0);
+
+    // Retiring each token in full uses up the issued supply
+    client.record_retirement(&carbon_contract, &project_id, &1, &2, &owner);
+    client.record_retirement(&carbon_contract, &project_id, &2, &2, &owner);
+    assert_eq!(client.get_project_supply(&project_id).net, 0);
+    assert_eq!(client.get_project(&project_id).total_retired, 4);
+}
+
+#[test]
+fn test_record_retirement_of_multi_unit_token() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.set_carbon_asset_contract(&admin, &carbon_contract);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    client.link_token(&project_id, &1, &1_000);
+
+    // A single token carrying many credits can be retired in full
+    client.record_retirement(&carbon_contract, &project_id, &1, &1_000, &owner);
+    assert_eq!(
+        client.get_project_supply(&project_id),
+        ProjectSupply {
+            total_issued: 1_000,
+            total_retired: 1_000,
+            net: 0,
+        }
+    );
+}
+
+#[test]
+fn test_record_retirement_rejects_repeated_token() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.set_carbon_asset_contract(&admin, &carbon_contract);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    for token_id in 1..=3 {
+        client.link_token(&project_id, &token_id, &1);
+    }
+
+    client.record_retirement(&carbon_contract, &project_id, &1, &1, &owner);
+
+    // The supply still has room, but token 1 cannot be counted twice
+    let result = client.try_record_retirement(&carbon_contract, &project_id, &1, &1, &owner);
+    assert_eq!(result, Err(Ok(Error::TokenAlreadyRetired)));
+    assert_eq!(client.get_retirements(&project_id).len(), 1);
+    assert_eq!(client.get_project(&project_id).total_retired, 1);
+
+    client.record_retirement(&carbon_contract, &project_id, &2, &1, &owner);
+    assert_eq!(client.get_project(&project_id).total_retired, 2);
+}
+
+#[test]
+fn test_record_retirement_rejects_invalid_input() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let other = SorobanString::from_str(&env, "PROJ-002");
+
+    client.initialize(&admin);
+    client.set_carbon_asset_contract(&admin, &carbon_contract);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&other, &owner, &PROJECT_EXPIRY, &None);
+    client.link_token(&project_id, &7, &10);
+
+    let result = client.try_record_retirement(&admin, &project_id, &7, &10, &owner);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let result = client.try_record_retirement(&carbon_contract, &project_id, &7, &0, &owner);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+    // The token must be linked to the project it is retired against
+    let result = client.try_record_retirement(&carbon_contract, &other, &7, &10, &owner);
+    assert_eq!(result, Err(Ok(Error::TokenNotLinked)));
+    let result = client.try_record_retirement(&carbon_contract, &project_id, &8, &10, &owner);
+    assert_eq!(result, Err(Ok(Error::TokenNotLinked)));
+
+    let missing = SorobanString::from_str(&env, "PROJ-404");
+    let result = client.try_record_retirement(&carbon_contract, &missing, &7, &10, &owner);
+    assert_eq!(result, Err(Ok(Error::ProjectNotFound)));
+
+    assert_eq!(client.get_retirements(&project_id).len(), 0);
+    assert_eq!(client.get_project(&project_id).total_retired, 0);
+}
+
+#[test]
+fn test_get_project_detail() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    for _ in 0..2 {
+        let verifier = Address::generate(&env);
+        client.add_verifier(&admin, &verifier);
+        client.attest_project(&verifier, &project_id, &hash);
+    }
+    for token_id in 1..=3 {
+        client.link_token(&project_id, &token_id, &1);
+    }
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    client.set_project_status(&project_id, &ProjectStatus::Verified);
+
+    assert_eq!(
+        client.get_project_detail(&project_id),
+        Some(ProjectDetail {
+            project: client.get_project(&project_id),
+            attestation_count: 2,
+            linked_token_count: 3,
+            status: ProjectStatus::Verified,
+        })
+    );
+
+    let missing = SorobanString::from_str(&env, "PROJ-404");
+    assert_eq!(client.get_project_detail(&missing), None);
+}
+
+#[test]
+fn test_get_project_supply() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let carbon_contract = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let other = SorobanString::from_str(&env, "PROJ-002");
+
+    client.initialize(&admin);
+    client.set_carbon_asset_contract(&admin, &carbon_contract);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&other, &owner, &PROJECT_EXPIRY, &None);
+
+    let supply = client.get_project_supply(&project_id);
+    assert_eq!(
+        supply,
+        ProjectSupply {
+            total_issued: 0,
+            total_retired: 0,
+            net: 0,
+        }
+    );
+
+    // Supply is counted in credits, so multi-unit tokens add their full quantity
+    client.link_token(&project_id, &1, &100);
+    client.batch_link_tokens(&carbon_contract, &project_id, &vec![&env, 2, 3], &50);
+    client.link_token(&other, &4, &1);
+    client.record_retirement(&carbon_contract, &project_id, &2, &50, &owner);
+
+    assert_eq!(
+        client.get_project_supply(&project_id),
+        ProjectSupply {
+            total_issued: 200,
+            total_retired: 50,
+            net: 150,
+        }
+    );
+    assert_eq!(client.get_project_supply(&other).total_issued, 1);
+
+    let missing = SorobanString::from_str(&env, "PROJ-404");
+    let result = client.try_get_project_supply(&missing);
+    assert_eq!(result, Err(Ok(Error::ProjectNotFound)));
+}
+
+#[test]
+fn test_health_check() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+
+    let status = client.health_check();
+    assert!(!status.initialized);
+    assert!(!status.paused);
+    assert_eq!(status.entity_count, 0);
+
+    client.initialize(&admin);
+    let owner = Address::generate(&env);
+    client.register_project(
+        &SorobanString::from_str(&env, "PROJ-001"),
+        &owner,
+        &PROJECT_EXPIRY,
+        &None,
+    );
+    client.register_project(
+        &SorobanString::from_str(&env, "PROJ-002"),
+        &owner,
+        &PROJECT_EXPIRY,
+        &None,
+    );
+
+    let status = client.health_check();
+    assert!(status.initialized);
+    assert!(!status.paused);
+    assert_eq!(status.entity_count, 2);
+
+    client.pause(&admin);
+    assert!(client.health_check().paused);
+
+    client.unpause(&admin);
+    assert!(!client.health_check().paused);
+}
+
+#[test]
+fn test_set_paused_by_coordinator() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let coordinator = Address::generate(&env);
+    client.initialize(&admin);
+
+    let result = client.try_set_paused_by_coordinator(&coordinator, &true);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    let result = client.try_set_coordinator(&coordinator, &coordinator);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.set_coordinator(&admin, &coordinator);
+    assert_eq!(client.get_coordinator(), Some(coordinator.clone()));
+    client.set_paused_by_coordinator(&coordinator, &true);
+    assert!(client.is_paused());
+    client.set_paused_by_coordinator(&coordinator, &false);
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_pause_blocks_writes() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+
+    client.initialize(&admin);
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_pause(&outsider);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.pause(&admin);
+    assert!(client.is_paused());
+
+    let other = SorobanString::from_str(&env, "PROJ-002");
+    let result = client.try_register_project(&other, &owner, &PROJECT_EXPIRY, &None);
+    assert_eq!(result, Err(Ok(Error::Paused)));
+
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let result = client.try_anchor_metadata(&project_id, &hash, &None);
+    assert_eq!(result, Err(Ok(Error::Paused)));
+
+    // Reads keep working while paused
+    assert_eq!(client.get_project(&project_id).owner, owner);
+
+    client.unpause(&admin);
+    client.register_project(&other, &owner, &PROJECT_EXPIRY, &None);
+}
+
+#[test]
+fn test_register_project_correlation_id_in_event_topics() {
+    let (env, contract_id, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let correlation_id = BytesN::from_array(&env, &[9u8; 32]);
+
+    client.initialize(&admin);
+    client.register_project(
+        &project_id,
+        &owner,
+        &PROJECT_EXPIRY,
+        &Some(correlation_id.clone()),
+    );
+
+    let expected = ProjectRegistered {
+        project_id: project_id.clone(),
+        correlation_id: Some(correlation_id.clone()),
+        owner: owner.clone(),
+        expires_at: PROJECT_EXPIRY,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+    let topics = expected.topics(&env);
+    assert_eq!(topics.len(), 3);
+    let topic: BytesN<32> = soroban_sdk::FromVal::from_val(&env, &topics.get(2).unwrap());
+    assert_eq!(topic, correlation_id);
+
+    // Without a correlation id the topic slot is void
+    let other = SorobanString::from_str(&env, "PROJ-002");
+    client.register_project(&other, &owner, &PROJECT_EXPIRY, &None);
+    let expected = ProjectRegistered {
+        project_id: other,
+        correlation_id: None,
+        owner,
+        expires_at: PROJECT_EXPIRY,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (contract_id, expected.topics(&env), expected.data(&env))
+        ]
+    );
+}
+
+#[test]
+fn test_list_projects_pages() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.initialize(&admin);
+
+    for project_id in ["PROJ-001", "PROJ-002", "PROJ-003"] {
+        client.register_project(
+            &SorobanString::from_str(&env, project_id),
+            &owner,
+            &PROJECT_EXPIRY,
+            &None,
+        );
+    }
+
+    let first = client.list_projects(&0, &2, &false);
+    assert_eq!(first.total, 3);
+    assert_eq!(
+        first.items,
+        vec![
+            &env,
+            SorobanString::from_str(&env, "PROJ-001"),
+            SorobanString::from_str(&env, "PROJ-002"),
+        ]
+    );
+    assert_eq!(first.next_cursor, Some(2));
+
+    let last = client.list_projects(&2, &2, &false);
+    assert_eq!(
+        last.items,
+        vec![&env, SorobanString::from_str(&env, "PROJ-003")]
+    );
+    assert_eq!(last.next_cursor, None);
+
+    // An exactly full last page has no next cursor either
+    assert_eq!(client.list_projects(&0, &3, &false).next_cursor, None);
+}
+
+#[test]
+fn test_deactivate_and_reactivate_project() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    let other_id = SorobanString::from_str(&env, "PROJ-002");
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+    client.register_project(&other_id, &owner, &PROJECT_EXPIRY, &None);
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    add_verifier_and_attest(&env, &client, &admin, &project_id);
+    client.set_project_status(&project_id, &ProjectStatus::Verified);
+
+    client.deactivate_project(&admin, &project_id);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Inactive
+    );
+    assert_eq!(
+        client.get_projects_by_status(&ProjectStatus::Inactive),
+        vec![&env, project_id.clone()]
+    );
+    assert!(client
+        .get_projects_by_status(&ProjectStatus::Verified)
+        .is_empty());
+
+    let active = client.list_projects(&0, &10, &false);
+    assert_eq!(active.items, vec![&env, other_id.clone()]);
+    assert_eq!(active.total, 1);
+    assert_eq!(client.list_projects(&0, &10, &true).total, 2);
+
+    // Inactive is left only through reactivation
+    let result = client.try_deactivate_project(&admin, &project_id);
+    assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+    let result = client.try_set_project_status(&project_id, &ProjectStatus::Verified);
+    assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+
+    client.reactivate_project(&admin, &project_id);
+    assert_eq!(
+        client.get_project(&project_id).status,
+        ProjectStatus::Verified
+    );
+    assert!(client
+        .get_projects_by_status(&ProjectStatus::Inactive)
+        .is_empty());
+    assert_eq!(client.list_projects(&0, &10, &false).total, 2);
+
+    let result = client.try_reactivate_project(&admin, &project_id);
+    assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+
+    // The cycle can repeat, and restores whatever status the project held
+    client.deactivate_project(&admin, &other_id);
+    client.reactivate_project(&admin, &other_id);
+    assert_eq!(
+        client.get_project(&other_id).status,
+        ProjectStatus::Registered
+    );
+}
+
+#[test]
+fn test_deactivate_project_requires_governance() {
+    let (env, _, client) = create_contract();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = SorobanString::from_str(&env, "PROJ-001");
+    client.register_project(&project_id, &owner, &PROJECT_EXPIRY, &None);
+
+    let result = client.try_deactivate_project(&owner, &project_id);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.deactivate_project(&admin, &project_id);
+    let result = client.try_reactivate_project(&owner, &project_id);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.reactivate_project(&admin, &project_id);
+
+    // Revocation is permanent; a rejected project cannot be deactivated
+    client.set_project_status(&project_id, &ProjectStatus::UnderReview);
+    client.revoke_project(
+        &admin,
+        &project_id,
+        &RevocationReason::Fraud,
+        &SorobanString::from_str(&env, "Fraud"),
+    );
+    let result = client.try_deactivate_project(&admin, &project_id);
+    assert_eq!(result, Err(Ok(Error::InvalidTransition)));
+}
+
+#[test]
+fn test_error_codes_in_registry_range() {
+    let errors = [
+        Error::AlreadyInitialized,
+        Error::AdminNotFound,
+        Error::ProjectNotFound,
+        Error::ProjectAlreadyExists,
+        Error::NoDocumentsFound,
+        Error::InvalidCidFormat,
+        Error::EmptyBatch,
+        Error::NoProjectsFound,
+        Error::NotAuthorized,
+        Error::InvalidExpiry,
+        Error::ProjectExpired,
+        Error::NotVerifier,
+        Error::QuorumNotMet,
+        Error::InvalidAttestationConfig,
+        Error::ProjectRevoked,
+        Error::InvalidTransition,
+        Error::NoMetadataFound,
+        Error::MetadataAlreadyAnchored,
+        Error::DisputeAlreadyOpen,
+        Error::NoOpenDispute,
+        Error::VersionNotFound,
+        Error::TokenAlreadyLinked,
+        Error::TokenNotLinked,
+        Error::JurisdictionNotSet,
+        Error::Paused,
+        Error::InvalidAmount,
+        Error::UpdateTooSoon,
+        Error::MethodologyNotSet,
+        Error::MethodologyFrozen,
+        Error::DocumentAlreadyExists,
+        Error::OverRetirement,
+        Error::TokenAlreadyRetired,
+    ];
+
+    for error in errors {
+        assert_eq!(error_source(error as u32), Some(ErrorSource::Registry));
+    }
+    assert_eq!(error_source(0), None);
 }
@@ -1,4 +1,16 @@
-use soroban_sdk::{contracterror, contracttype, Address, String};
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, String};
+
+use shared_types::page_type;
+pub use shared_types::{
+    HealthStatus, Project, ProjectStatus, Revocation, RevocationReason, RevocationStatus,
+};
+
+page_type!(
+    /// Page of project IDs returned by `list_projects`
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    ProjectPage,
+    String
+);
 
 /// Document record structure storing metadata about an anchored document
 #[contracttype]
@@ -14,6 +26,115 @@ pub struct DocumentRecord {
     pub anchorer: Address,
 }
 
+/// Hash function an anchored metadata or document hash was computed with
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Keccak256,
+}
+
+/// Hash of a named supporting document anchored by `add_document`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NamedDocument {
+    /// Name the document is anchored under, unique per project
+    pub name: String,
+    pub hash: BytesN<32>,
+    pub algorithm: HashAlgorithm,
+}
+
+/// Versioned metadata hash anchored for a project
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetadataRecord {
+    /// Hash of the project metadata document
+    pub metadata_hash: BytesN<32>,
+    /// Metadata version, starting at 1
+    pub version: u32,
+    /// Ledger close timestamp when the hash was anchored
+    pub timestamp: u64,
+    /// Address that performed the anchoring
+    pub anchorer: Address,
+    /// Hash function `metadata_hash` was computed with
+    pub algorithm: HashAlgorithm,
+}
+
+/// Verifier attestation recorded against a project
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    /// Verifier that made the attestation
+    pub verifier: Address,
+    /// Hash of the verification evidence (e.g., report hash)
+    pub evidence_hash: BytesN<32>,
+    /// Ledger timestamp when the attestation was made
+    pub attested_at: u64,
+    /// Ledger timestamp after which the attestation no longer counts
+    pub expires_at: u64,
+}
+
+/// Per-verifier reputation counters
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifierStats {
+    /// Number of attestations the verifier has made, not counting ones that
+    /// replace its own standing attestation on a project
+    pub attestations_made: u32,
+    /// Number of the verifier's attestations invalidated by an upheld dispute
+    pub attestations_invalidated: u32,
+}
+
+/// Open dispute against a project's verification
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    /// Address that raised the dispute
+    pub challenger: Address,
+    /// Free-text description of the dispute
+    pub reason: String,
+    /// Ledger timestamp when the dispute was raised
+    pub raised_at: u64,
+}
+
+/// Retirement of a carbon asset token recorded against its project
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetirementRecord {
+    /// Token that was retired
+    pub token_id: u32,
+    /// Credits retired
+    pub amount: i128,
+    /// Address the retirement was made on behalf of
+    pub beneficiary: Address,
+    /// Ledger timestamp when the retirement was recorded
+    pub retired_at: u64,
+}
+
+/// Issued and retired credits for a project
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectSupply {
+    /// Credits issued, summed over the tokens linked to the project
+    pub total_issued: i128,
+    /// Credits retired against the project
+    pub total_retired: i128,
+    /// Credits still outstanding
+    pub net: i128,
+}
+
+/// Everything needed to render a project, from `get_project_detail`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectDetail {
+    /// The project as `get_project` returns it
+    pub project: Project,
+    pub attestation_count: u32,
+    pub linked_token_count: u32,
+    pub status: ProjectStatus,
+}
+
 /// Contract error types
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -35,4 +156,52 @@ pub enum Error {
     EmptyBatch = 7,
     /// No projects found for anchorer
     NoProjectsFound = 8,
+    /// Caller is not authorized for this operation
+    NotAuthorized = 9,
+    /// Expiry timestamp is not in the future
+    InvalidExpiry = 10,
+    /// Project crediting period has ended
+    ProjectExpired = 11,
+    /// Address is not a registered verifier
+    NotVerifier = 12,
+    /// Not enough valid attestations to verify the project
+    QuorumNotMet = 13,
+    /// Attestation validity or quorum value is invalid
+    InvalidAttestationConfig = 14,
+    /// Project has already been revoked
+    ProjectRevoked = 15,
+    /// Status transition is not allowed
+    InvalidTransition = 16,
+    /// No metadata anchored for project
+    NoMetadataFound = 17,
+    /// Metadata already anchored for project
+    MetadataAlreadyAnchored = 18,
+    /// Project already has an open dispute
+    DisputeAlreadyOpen = 19,
+    /// Project has no open dispute
+    NoOpenDispute = 20,
+    /// Metadata version not found for project
+    VersionNotFound = 21,
+    /// Token is already linked to a project
+    TokenAlreadyLinked = 22,
+    /// Token is not linked to any project
+    TokenNotLinked = 23,
+    /// No jurisdiction recorded for project
+    JurisdictionNotSet = 24,
+    /// Contract is paused
+    Paused = 25,
+    /// Amount must be positive
+    InvalidAmount = 26,
+    /// Metadata was updated less than the minimum update interval ago
+    UpdateTooSoon = 27,
+    /// No methodology recorded for project
+    MethodologyNotSet = 28,
+    /// Project's methodology is frozen
+    MethodologyFrozen = 29,
+    /// Project already has a document with this name
+    DocumentAlreadyExists = 30,
+    /// Retirement would exceed the project's issued credits
+    OverRetirement = 31,
+    /// A retirement was already recorded for the token
+    TokenAlreadyRetired = 32,
 }
@@ -1,4 +1,4 @@
-use crate::types::Error;
+use crate::types::{Error, ProjectStatus};
 use soroban_sdk::String;
 
 /// Validate IPFS CID format
@@ -82,3 +82,18 @@ fn is_base58_byte(b: u8) -> bool {
 fn is_base32_byte(b: u8) -> bool {
     matches!(b, b'a'..=b'z' | b'2'..=b'7' | b'A'..=b'Z')
 }
+
+/// Check whether a project may move from one status to another
+/// Registered -> UnderReview -> Verified -> Retired, with Rejected reachable
+/// from UnderReview or Verified. Retired and Rejected are terminal.
+/// Inactive is only entered and left through deactivation and reactivation.
+pub fn is_valid_transition(from: ProjectStatus, to: ProjectStatus) -> bool {
+    matches!(
+        (from, to),
+        (ProjectStatus::Registered, ProjectStatus::UnderReview)
+            | (ProjectStatus::UnderReview, ProjectStatus::Verified)
+            | (ProjectStatus::UnderReview, ProjectStatus::Rejected)
+            | (ProjectStatus::Verified, ProjectStatus::Retired)
+            | (ProjectStatus::Verified, ProjectStatus::Rejected)
+    )
+}
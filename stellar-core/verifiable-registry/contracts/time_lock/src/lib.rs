@@ -330,7 +330,6 @@ impl TimeLock {
     }
 
     /// Admin-only override to release a token before its unlock time.
-
     pub fn force_release(env: Env, token_id: u32) -> Result<(), TimeLockError> {
         Self::require_initialized(&env)?;
 